@@ -0,0 +1,54 @@
+//! ARM EABI build attribute tags.
+
+
+
+tags!(
+    /// ARM EABI build attribute tag (`Tag_*` of vendor "aeabi").
+    ARMAttributeTag, {
+        CPURawName             = 4  => "CPU_raw_name",
+        CPUName                = 5  => "CPU_name",
+        CPUArch                = 6  => "CPU_arch",
+        CPUArchProfile         = 7  => "CPU_arch_profile",
+        ARMISAUse              = 8  => "ARM_ISA_use",
+        ThumbISAUse            = 9  => "THUMB_ISA_use",
+        FPArch                 = 10 => "FP_arch",
+        WMMXArch               = 11 => "WMMX_arch",
+        AdvancedSIMDArch       = 12 => "Advanced_SIMD_arch",
+        PCSConfig              = 13 => "PCS_config",
+        PCSR9Use               = 14 => "ABI_PCS_R9_use",
+        PCSRWData              = 15 => "ABI_PCS_RW_data",
+        PCSROData              = 16 => "ABI_PCS_RO_data",
+        PCSGOTUse              = 17 => "ABI_PCS_GOT_use",
+        PCSWcharT              = 18 => "ABI_PCS_wchar_t",
+        FPRounding             = 19 => "ABI_FP_rounding",
+        FPDenormal             = 20 => "ABI_FP_denormal",
+        FPExceptions           = 21 => "ABI_FP_exceptions",
+        FPUserExceptions       = 22 => "ABI_FP_user_exceptions",
+        FPNumberModel          = 23 => "ABI_FP_number_model",
+        AlignNeeded            = 24 => "ABI_align_needed",
+        AlignPreserved         = 25 => "ABI_align_preserved",
+        EnumSize               = 26 => "ABI_enum_size",
+        HardFPUse              = 27 => "ABI_HardFP_use",
+        VFPArgs                = 28 => "ABI_VFP_args",
+        WMMXArgs               = 29 => "ABI_WMMX_args",
+        OptimizationGoals      = 30 => "ABI_optimization_goals",
+        FPOptimizationGoals    = 31 => "ABI_FP_optimization_goals",
+        Compatibility          = 32 => "compatibility",
+        CPUUnalignedAccess     = 34 => "CPU_unaligned_access",
+        FPHPExtension          = 36 => "FP_HP_extension",
+        FP16BitFormat          = 38 => "ABI_FP_16bit_format",
+        MPExtensionUse         = 42 => "MPextension_use",
+        DivUse                 = 44 => "DIV_use",
+        DSPExtension           = 46 => "DSP_extension",
+        MVEArch                = 48 => "MVE_arch",
+        PACExtension           = 50 => "PAC_extension",
+        BTIExtension           = 52 => "BTI_extension",
+        NoDefaults             = 64 => "nodefaults",
+        AlsoCompatibleWith     = 65 => "also_compatible_with",
+        T2EEUse                = 66 => "T2EE_use",
+        Conformance            = 67 => "conformance",
+        VirtualizationUse      = 68 => "Virtualization_use",
+        BTIUse                 = 74 => "BTI_use",
+        PACRETUse              = 76 => "PACRET_use",
+    }
+);
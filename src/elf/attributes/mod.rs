@@ -0,0 +1,534 @@
+//! Build attribute sections (`.ARM.attributes`, `.riscv.attributes`, `.gnu.attributes`)
+//! in the generic "aeabi" format of vendor subsections.
+
+
+
+/// Builds an attribute tag enum from its table of names and values.
+macro_rules! tags {
+    ($(#[$meta:meta])* $name:ident, { $($variant:ident = $value:literal => $string:literal,)* }) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+        pub enum $name {
+            $($variant,)*
+
+            /// Unknown attribute tag.
+            Unknown(u64),
+        }
+
+        impl $name {
+            /// Returns the raw value of the attribute tag.
+            pub fn value(&self) -> u64 {
+                match *self {
+                    $(Self::$variant => $value,)*
+                    Self::Unknown(x) => x,
+                }
+            }
+        }
+
+        impl core::convert::From<u64> for $name {
+            fn from(t: u64) -> Self {
+                match t {
+                    $($value => Self::$variant,)*
+                    _ => Self::Unknown(t),
+                }
+            }
+        }
+
+        impl core::fmt::Display for $name {
+            fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                match *self {
+                    $(Self::$variant => write!(f, "Tag_{}", $string),)*
+                    Self::Unknown(x) => write!(f, "Tag_{}", x),
+                }
+            }
+        }
+    };
+}
+
+
+
+mod arm;
+mod riscv;
+
+
+
+pub use arm::ARMAttributeTag;
+pub use riscv::RISCVAttributeTag;
+
+use crate::{
+    elf::{
+        common::{
+            read::{
+                self,
+                big32, little32,
+            },
+
+            Endian,
+        },
+
+        ELFError,
+    },
+};
+
+
+
+/// Format version of attribute sections.
+const VERSION: u8 = b'A';
+
+/// Tag of the attributes that apply to the whole file.
+const TAG_FILE: u8 = 1;
+
+/// Tag of the attributes that apply to a list of sections.
+const TAG_SECTION: u8 = 2;
+
+/// Tag of the attributes that apply to a list of symbols.
+const TAG_SYMBOL: u8 = 3;
+
+/// Generic tag with an integer flag and a vendor string.
+const TAG_COMPATIBILITY: u64 = 32;
+
+
+
+/// Tag of a build attribute, decoded according to the vendor of its subsection.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AttributeTag {
+    /// ARM EABI attribute (vendor "aeabi").
+    ARM(ARMAttributeTag),
+
+    /// RISC-V attribute (vendor "riscv").
+    RISCV(RISCVAttributeTag),
+
+    /// Attribute of another vendor.
+    Other(u64),
+}
+
+impl AttributeTag {
+    /// Decodes the tag of an attribute of the given vendor.
+    fn new(vendor: &str, tag: u64) -> Self {
+        match vendor {
+            "aeabi" => Self::ARM( ARMAttributeTag::from(tag) ),
+            "riscv" => Self::RISCV( RISCVAttributeTag::from(tag) ),
+            _ => Self::Other(tag),
+        }
+    }
+
+    /// Returns the raw value of the attribute tag.
+    pub fn value(&self) -> u64 {
+        match *self {
+            Self::ARM(t) => t.value(),
+            Self::RISCV(t) => t.value(),
+            Self::Other(x) => x,
+        }
+    }
+
+    /// Returns `true` if the value of this attribute is a NULL terminated string.
+    /// Tags not listed by their vendor follow the generic rule: odd tags are strings.
+    fn string(&self) -> bool {
+        match *self {
+            Self::ARM(ARMAttributeTag::CPURawName) | Self::ARM(ARMAttributeTag::CPUName) => true,
+            Self::ARM(t) if t.value() < TAG_COMPATIBILITY => false,
+
+            t => (t.value() & 1) != 0,
+        }
+    }
+}
+
+impl core::fmt::Display for AttributeTag {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match *self {
+            Self::ARM(t) => write!(f, "{}", t),
+            Self::RISCV(t) => write!(f, "{}", t),
+            Self::Other(x) => write!(f, "Tag_{}", x),
+        }
+    }
+}
+
+
+
+/// Value of a build attribute.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AttributeValue {
+    /// ULEB128 encoded integer.
+    Integer(u64),
+
+    /// NULL terminated string.
+    String(String),
+
+    /// Compatibility flag and vendor name.
+    Compatibility(u64, String),
+}
+
+impl AttributeValue {
+    /// Returns the integer value, if this is an integer attribute.
+    pub fn integer(&self) -> Option<u64> {
+        match self {
+            Self::Integer(x) => Some( *x ),
+            _ => None,
+        }
+    }
+
+    /// Returns the string value, if this is a string attribute.
+    pub fn string(&self) -> Option<&str> {
+        match self {
+            Self::String(s) => Some( s ),
+            _ => None,
+        }
+    }
+}
+
+impl core::fmt::Display for AttributeValue {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            Self::Integer(x) => write!(f, "{}", x),
+            Self::String(s) => write!(f, "\"{}\"", s),
+            Self::Compatibility(x, s) => write!(f, "{} \"{}\"", x, s),
+        }
+    }
+}
+
+
+
+/// Entities to which a group of attributes applies.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AttributeScope {
+    /// The whole file.
+    File,
+
+    /// The sections with the given indices.
+    Section(Vec<usize>),
+
+    /// The symbols with the given indices.
+    Symbol(Vec<usize>),
+}
+
+impl core::fmt::Display for AttributeScope {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let list = |l: &Vec<usize>| l.iter().map(|i| format!("{}", i)).collect::<Vec<_>>().join(", ");
+
+        match self {
+            Self::File => f.write_str("File"),
+            Self::Section(l) => write!(f, "Sections {}", list(l)),
+            Self::Symbol(l) => write!(f, "Symbols {}", list(l)),
+        }
+    }
+}
+
+
+
+/// A single build attribute.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ELFAttribute {
+    /// Tag of the attribute.
+    pub tag: AttributeTag,
+
+    /// Value of the attribute.
+    pub value: AttributeValue,
+}
+
+/// A list of attributes that apply to the same scope.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ELFAttributeGroup {
+    /// Entities to which the attributes apply.
+    pub scope: AttributeScope,
+
+    /// Attributes of the group.
+    pub attributes: Vec<ELFAttribute>,
+}
+
+/// The attributes defined by a single vendor.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ELFAttributeSubsection {
+    /// Name of the vendor ("aeabi", "riscv", "gnu", ...).
+    pub vendor: String,
+
+    /// Groups of attributes of the vendor.
+    pub groups: Vec<ELFAttributeGroup>,
+}
+
+/// Contains a build attribute section.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ELFAttributes {
+    /// Index of the attribute section.
+    pub section: usize,
+
+    /// Vendor subsections of the section.
+    pub subsections: Vec<ELFAttributeSubsection>,
+}
+
+impl ELFAttributes {
+    /// Parses the contents of an attribute section.
+    pub(crate) fn parse(data: &[u8], offset: usize, endian: Endian, section: usize) -> Result<Self, ELFError> {
+        // Get the read function.
+        let read32: fn(&[u8]) -> u32 = match endian {
+            Endian::Little => little32,
+            Endian::Big => big32,
+        };
+
+        // Check the format version.
+        match data.first() {
+            Some(&VERSION) => (),
+            _ => return Err( ELFError::BadAttributes { offset } ),
+        }
+
+        // List of all subsections.
+        let mut subsections = Vec::new();
+
+        let mut i = 1;
+
+        while i < data.len() {
+            // Get the subsection, whose length includes the length field.
+            let length = read32( read::slice(data, i, 4).map_err(|e| e.shift(offset))? ) as usize;
+
+            if (length < 4) || (length > data.len() - i) {
+                return Err( ELFError::BadAttributes { offset: offset + i } );
+            }
+
+            let mut reader = Reader { data: &data[..i+length], index: i + 4 };
+
+            // Get the vendor name.
+            let vendor = reader.string().ok_or( ELFError::BadAttributes { offset: offset + i } )?;
+
+            // Get the groups of attributes.
+            let mut groups = Vec::new();
+
+            while reader.index < reader.data.len() {
+                let start = reader.index;
+                let error = ELFError::BadAttributes { offset: offset + start };
+
+                // Get the scope tag and the size of the group, which includes both.
+                let tag = reader.data[start];
+                let size = read32( read::slice(reader.data, start + 1, 4).map_err(|e| e.shift(offset))? ) as usize;
+
+                if (size < 5) || (size > reader.data.len() - start) {
+                    return Err( error );
+                }
+
+                let mut group = Reader { data: &reader.data[..start+size], index: start + 5 };
+
+                // Get the scope, listing its entities up to a zero index.
+                let scope = match tag {
+                    TAG_FILE => AttributeScope::File,
+
+                    TAG_SECTION | TAG_SYMBOL => {
+                        let mut list = Vec::new();
+
+                        loop {
+                            match group.uleb().ok_or(error)? {
+                                0 => break,
+                                x => list.push( x as usize ),
+                            }
+                        }
+
+                        match tag {
+                            TAG_SECTION => AttributeScope::Section(list),
+                            _ => AttributeScope::Symbol(list),
+                        }
+                    },
+
+                    _ => return Err( error ),
+                };
+
+                // Get the attributes.
+                let mut attributes = Vec::new();
+
+                while group.index < group.data.len() {
+                    let tag = AttributeTag::new( &vendor, group.uleb().ok_or(error)? );
+
+                    let value = match tag.value() {
+                        TAG_COMPATIBILITY => AttributeValue::Compatibility( group.uleb().ok_or(error)?, group.string().ok_or(error)? ),
+                        _ if tag.string() => AttributeValue::String( group.string().ok_or(error)? ),
+                        _ => AttributeValue::Integer( group.uleb().ok_or(error)? ),
+                    };
+
+                    attributes.push( ELFAttribute { tag, value } );
+                }
+
+                groups.push( ELFAttributeGroup { scope, attributes } );
+
+                reader.index = start + size;
+            }
+
+            subsections.push( ELFAttributeSubsection { vendor, groups } );
+
+            i += length;
+        }
+
+        Ok( Self { section, subsections } )
+    }
+
+    /// Returns the value of the given attribute if it applies to the whole file.
+    pub fn get(&self, tag: AttributeTag) -> Option<&AttributeValue> {
+        self.subsections.iter()
+            .flat_map(|s| s.groups.iter())
+            .filter(|g| g.scope == AttributeScope::File)
+            .flat_map(|g| g.attributes.iter())
+            .find(|a| a.tag == tag)
+            .map(|a| &a.value)
+    }
+}
+
+impl core::fmt::Display for ELFAttributes {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        // Build the output string.
+        let mut string = String::from("ELF Build Attributes\n");
+
+        // Add attribute section information.
+        string += &format!("|- Section: {}\n", self.section);
+
+        for subsection in &self.subsections {
+            string += &format!("|- Vendor: {}\n", subsection.vendor);
+
+            for group in &subsection.groups {
+                string += &format!("|  |- {}\n", group.scope);
+
+                for attribute in &group.attributes {
+                    string += &format!("|  |  |- {}: {}\n", attribute.tag, attribute.value);
+                }
+            }
+        }
+
+        string += "\n";
+
+        f.write_str(&string)
+    }
+}
+
+
+
+/// Internal reader of attribute values.
+struct Reader<'data> {
+    /// Data of the subsection or group.
+    data: &'data [u8],
+
+    /// Offset of the next value.
+    index: usize,
+}
+
+impl<'data> Reader<'data> {
+    /// Reads an ULEB128 value.
+    fn uleb(&mut self) -> Option<u64> {
+        let mut value: u64 = 0;
+        let mut shift = 0;
+
+        loop {
+            let byte = *self.data.get(self.index)?;
+            self.index += 1;
+
+            if shift < 64 {
+                value |= ((byte & 0x7F) as u64) << shift;
+            }
+
+            shift += 7;
+
+            if (byte & 0x80) == 0 {
+                return Some( value );
+            }
+        }
+    }
+
+    /// Reads a NULL terminated string.
+    fn string(&mut self) -> Option<String> {
+        let data = self.data.get(self.index..)?;
+        let end = data.iter().position(|b| *b == 0)?;
+
+        self.index += end + 1;
+
+        Some( String::from_utf8_lossy(&data[..end]).into_owned() )
+    }
+}
+
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::elf::{
+        fixture::{
+            Image, Section,
+        },
+
+        ELFContent,
+    };
+
+    /// Contents of the `.ARM.attributes` section of a Cortex-M4 object.
+    const ARM: [u8; 51] = [
+        b'A', 50, 0, 0, 0, b'a', b'e', b'a', b'b', b'i', 0,
+        1, 40, 0, 0, 0,
+        67, b'2', b'.', b'0', b'9', 0,
+        5, b'c', b'o', b'r', b't', b'e', b'x', b'-', b'm', b'4', 0,
+        10, 6,  18, 4,  24, 1,  25, 1,  26, 1,  28, 1,
+        32, 1, b'g', b'n', b'u', 0,
+    ];
+
+    #[test]
+    fn arm() {
+        let attributes = ELFAttributes::parse(&ARM, 0x36, Endian::Little, 3).unwrap();
+
+        assert_eq!(attributes.subsections.len(), 1);
+        assert_eq!(attributes.subsections[0].vendor, "aeabi");
+        assert_eq!(attributes.subsections[0].groups[0].attributes.len(), 9);
+
+        let get = |tag| attributes.get( AttributeTag::ARM(tag) );
+
+        assert_eq!(get(ARMAttributeTag::from(67)).and_then(|v| v.string()), Some("2.09"));
+        assert_eq!(get(ARMAttributeTag::CPUName).and_then(|v| v.string()), Some("cortex-m4"));
+        assert_eq!(get(ARMAttributeTag::FPArch).and_then(|v| v.integer()), Some(6));
+        assert_eq!(get(ARMAttributeTag::AlignNeeded).and_then(|v| v.integer()), Some(1));
+        assert_eq!(get(ARMAttributeTag::from(32)), Some(&AttributeValue::Compatibility(1, String::from("gnu"))));
+        assert_eq!(get(ARMAttributeTag::CPUArch), None);
+    }
+
+    #[test]
+    fn riscv() {
+        // Big endian lengths and a group that only applies to sections 1 and 2.
+        let data = [
+            b'A', 0, 0, 0, 27, b'r', b'i', b's', b'c', b'v', 0,
+            1, 0, 0, 0, 7,  4, 16,
+            2, 0, 0, 0, 10,  1, 2, 0,  6, 1,
+        ];
+
+        let attributes = ELFAttributes::parse(&data, 0, Endian::Big, 1).unwrap();
+        let groups = &attributes.subsections[0].groups;
+
+        assert_eq!(attributes.get( AttributeTag::RISCV(RISCVAttributeTag::StackAlign) ), Some(&AttributeValue::Integer(16)));
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[1].scope, AttributeScope::Section(vec![1, 2]));
+        assert_eq!(attributes.get( AttributeTag::RISCV(RISCVAttributeTag::UnalignedAccess) ), None);
+    }
+
+    #[test]
+    fn malformed() {
+        // Bad format version.
+        let mut data = ARM;
+        data[0] = b'B';
+
+        assert_eq!(ELFAttributes::parse(&data, 0x36, Endian::Little, 3), Err(ELFError::BadAttributes { offset: 0x36 }));
+
+        // Subsection longer than the section.
+        assert_eq!(ELFAttributes::parse(&ARM[..50], 0x36, Endian::Little, 3), Err(ELFError::BadAttributes { offset: 0x37 }));
+
+        // Group longer than its subsection.
+        let mut data = ARM;
+        data[12] = 41;
+
+        assert_eq!(ELFAttributes::parse(&data, 0x36, Endian::Little, 3), Err(ELFError::BadAttributes { offset: 0x41 }));
+
+        // Truncated subsection length.
+        assert!(matches!(ELFAttributes::parse(&ARM[..3], 0x36, Endian::Little, 3), Err(ELFError::OutOfBounds { .. })));
+    }
+
+    #[test]
+    fn malformed_section() {
+        // 64-bit little endian RISC-V relocatable file with an attribute section of a bad version.
+        let raw = Image::new(1, 0xF3)
+            .section( Section { stype: 0x70000003, contents: b"B\0\0\0\0\0\0\0", ..Default::default() } )
+            .build();
+
+        let elf = ELFContent::parse(raw).unwrap();
+
+        assert_eq!(elf.sections.len(), 2);
+        assert!(elf.attributes.is_empty());
+        assert_eq!(elf.errors, [ELFError::BadAttributes { offset: 0x40 }]);
+    }
+}
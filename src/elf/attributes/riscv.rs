@@ -0,0 +1,17 @@
+//! RISC-V build attribute tags.
+
+
+
+tags!(
+    /// RISC-V build attribute tag (`Tag_RISCV_*` of vendor "riscv").
+    RISCVAttributeTag, {
+        StackAlign        = 4  => "RISCV_stack_align",
+        Arch              = 5  => "RISCV_arch",
+        UnalignedAccess   = 6  => "RISCV_unaligned_access",
+        PrivSpec          = 8  => "RISCV_priv_spec",
+        PrivSpecMinor     = 10 => "RISCV_priv_spec_minor",
+        PrivSpecRevision  = 12 => "RISCV_priv_spec_revision",
+        AtomicABI         = 14 => "RISCV_atomic_abi",
+        X3RegUsage        = 16 => "RISCV_x3_reg_usage",
+    }
+);
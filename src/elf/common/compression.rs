@@ -0,0 +1,50 @@
+//! Section compression algorithms.
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompressionType {
+    /// zlib (deflate) compression.
+    Zlib,
+
+    /// Zstandard compression.
+    Zstd,
+
+    /// OS specific compression.
+    OperatingSystem(u32),
+
+    /// Processor specific compression.
+    Processor(u32),
+
+    /// Unknown compression.
+    Unknown(u32),
+}
+
+impl core::convert::From<u32> for CompressionType {
+    fn from(u: u32) -> Self {
+        match u {
+            1 => CompressionType::Zlib,
+            2 => CompressionType::Zstd,
+
+            0x60000000..=0x6FFFFFFF => CompressionType::OperatingSystem(u),
+            0x70000000..=0x7FFFFFFF => CompressionType::Processor(u),
+
+            _ => CompressionType::Unknown(u),
+        }
+    }
+}
+
+impl core::fmt::Display for CompressionType {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let s = match *self {
+            CompressionType::Zlib => String::from("zlib"),
+            CompressionType::Zstd => String::from("zstd"),
+
+            CompressionType::OperatingSystem(u) => format!("OS specific ({:#X})", u),
+            CompressionType::Processor(u) => format!("Processor specific ({:#X})", u),
+
+            CompressionType::Unknown(u) => format!("Unknown ({:#X})", u),
+        };
+
+        write!(f, "{}", s)
+    }
+}
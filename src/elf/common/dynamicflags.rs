@@ -0,0 +1,251 @@
+//! Dynamic entry flags (`DT_FLAGS` and `DT_FLAGS_1`).
+
+
+
+/// Flags of the `DT_FLAGS` dynamic entry.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DynamicFlags(pub(crate) u64);
+
+impl DynamicFlags {
+    /// Returns the raw value of the flags.
+    pub fn value(&self) -> u64 {
+        self.0
+    }
+
+    /// Returns `true` if the object may use the `$ORIGIN` substitution string.
+    pub fn origin(&self) -> bool {
+        (self.0 & 0x1) != 0
+    }
+
+    /// Returns `true` if symbol resolution starts from the object itself.
+    pub fn symbolic(&self) -> bool {
+        (self.0 & 0x2) != 0
+    }
+
+    /// Returns `true` if relocations may modify non-writable segments.
+    pub fn textrel(&self) -> bool {
+        (self.0 & 0x4) != 0
+    }
+
+    /// Returns `true` if all relocations must be processed before execution.
+    pub fn bindnow(&self) -> bool {
+        (self.0 & 0x8) != 0
+    }
+
+    /// Returns `true` if the object uses the static TLS model.
+    pub fn statictls(&self) -> bool {
+        (self.0 & 0x10) != 0
+    }
+}
+
+impl core::fmt::Display for DynamicFlags {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        // Contant strings.
+        const STRINGS: [&str; 5] = [
+            "Origin", "Symbolic", "Text relocations", "Bind now",
+            "Static TLS",
+        ];
+
+        let flags = [
+            self.origin(), self.symbolic(), self.textrel(), self.bindnow(),
+            self.statictls(),
+        ];
+
+        let string = flags.iter()
+            .zip(STRINGS.iter())
+            .filter(|(f, _)| **f)
+            .map(|(_, s)| *s)
+            .collect::<Vec<_>>();
+
+        if string.is_empty() {
+            f.write_str("----")
+        } else{
+            f.write_str(&string.join(" + "))
+        }
+    }
+}
+
+
+
+/// Extended flags of the `DT_FLAGS_1` dynamic entry.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DynamicFlags1(pub(crate) u64);
+
+impl DynamicFlags1 {
+    /// Returns the raw value of the flags.
+    pub fn value(&self) -> u64 {
+        self.0
+    }
+
+    /// Returns `true` if all relocations must be processed before execution.
+    pub fn now(&self) -> bool {
+        (self.0 & 0x1) != 0
+    }
+
+    /// Returns `true` if the symbols of the object are made available globally.
+    pub fn global(&self) -> bool {
+        (self.0 & 0x2) != 0
+    }
+
+    /// Returns `true` if the object is a member of a group.
+    pub fn group(&self) -> bool {
+        (self.0 & 0x4) != 0
+    }
+
+    /// Returns `true` if the object cannot be unloaded.
+    pub fn nodelete(&self) -> bool {
+        (self.0 & 0x8) != 0
+    }
+
+    /// Returns `true` if the filtees of the object are loaded immediately.
+    pub fn loadfilter(&self) -> bool {
+        (self.0 & 0x10) != 0
+    }
+
+    /// Returns `true` if the object is initialized before any other object.
+    pub fn initfirst(&self) -> bool {
+        (self.0 & 0x20) != 0
+    }
+
+    /// Returns `true` if the object cannot be loaded with `dlopen`.
+    pub fn noopen(&self) -> bool {
+        (self.0 & 0x40) != 0
+    }
+
+    /// Returns `true` if the object may use the `$ORIGIN` substitution string.
+    pub fn origin(&self) -> bool {
+        (self.0 & 0x80) != 0
+    }
+
+    /// Returns `true` if direct bindings are enabled for the object.
+    pub fn direct(&self) -> bool {
+        (self.0 & 0x100) != 0
+    }
+
+    /// Returns `true` if the object interposes the symbols of all other objects but the executable.
+    pub fn interpose(&self) -> bool {
+        (self.0 & 0x400) != 0
+    }
+
+    /// Returns `true` if the default library search path is ignored for the object.
+    pub fn nodeflib(&self) -> bool {
+        (self.0 & 0x800) != 0
+    }
+
+    /// Returns `true` if the object cannot be dumped with `dldump`.
+    pub fn nodump(&self) -> bool {
+        (self.0 & 0x1000) != 0
+    }
+
+    /// Returns `true` if the object is an alternate configuration file.
+    pub fn confalt(&self) -> bool {
+        (self.0 & 0x2000) != 0
+    }
+
+    /// Returns `true` if the filtee terminates the search of the filter.
+    pub fn endfiltee(&self) -> bool {
+        (self.0 & 0x4000) != 0
+    }
+
+    /// Returns `true` if displacement relocations have been applied.
+    pub fn disprelocdone(&self) -> bool {
+        (self.0 & 0x8000) != 0
+    }
+
+    /// Returns `true` if displacement relocations are pending.
+    pub fn disprelocpending(&self) -> bool {
+        (self.0 & 0x10000) != 0
+    }
+
+    /// Returns `true` if the object contains symbols which cannot be bound directly.
+    pub fn nodirect(&self) -> bool {
+        (self.0 & 0x20000) != 0
+    }
+
+    /// Returns `true` if multiple definitions of symbols are ignored.
+    pub fn ignoremultidef(&self) -> bool {
+        (self.0 & 0x40000) != 0
+    }
+
+    /// Returns `true` if the symbols of the object are hidden from the kernel.
+    pub fn noksyms(&self) -> bool {
+        (self.0 & 0x80000) != 0
+    }
+
+    /// Returns `true` if the object headers are not mapped.
+    pub fn nohdr(&self) -> bool {
+        (self.0 & 0x100000) != 0
+    }
+
+    /// Returns `true` if the object has been edited after linking.
+    pub fn edited(&self) -> bool {
+        (self.0 & 0x200000) != 0
+    }
+
+    /// Returns `true` if the object has no relocations.
+    pub fn noreloc(&self) -> bool {
+        (self.0 & 0x400000) != 0
+    }
+
+    /// Returns `true` if the object contains symbols which interpose others.
+    pub fn symintpose(&self) -> bool {
+        (self.0 & 0x800000) != 0
+    }
+
+    /// Returns `true` if global auditing is required.
+    pub fn globalaudit(&self) -> bool {
+        (self.0 & 0x1000000) != 0
+    }
+
+    /// Returns `true` if the object defines singleton symbols.
+    pub fn singleton(&self) -> bool {
+        (self.0 & 0x2000000) != 0
+    }
+
+    /// Returns `true` if the object is a stub.
+    pub fn stub(&self) -> bool {
+        (self.0 & 0x4000000) != 0
+    }
+
+    /// Returns `true` if the object is a position independent executable.
+    pub fn pie(&self) -> bool {
+        (self.0 & 0x8000000) != 0
+    }
+}
+
+impl core::fmt::Display for DynamicFlags1 {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        // Contant strings.
+        const STRINGS: [&str; 27] = [
+            "Now", "Global", "Group", "No delete",
+            "Load filter", "Init first", "No open", "Origin",
+            "Direct", "Interpose", "No default lib", "No dump",
+            "Config alternative", "End filtee", "Displacement relocations done", "Displacement relocations pending",
+            "No direct", "Ignore multiple definitions", "No kernel symbols", "No header",
+            "Edited", "No relocations", "Symbol interpose", "Global audit",
+            "Singleton", "Stub", "PIE",
+        ];
+
+        let flags = [
+            self.now(), self.global(), self.group(), self.nodelete(),
+            self.loadfilter(), self.initfirst(), self.noopen(), self.origin(),
+            self.direct(), self.interpose(), self.nodeflib(), self.nodump(),
+            self.confalt(), self.endfiltee(), self.disprelocdone(), self.disprelocpending(),
+            self.nodirect(), self.ignoremultidef(), self.noksyms(), self.nohdr(),
+            self.edited(), self.noreloc(), self.symintpose(), self.globalaudit(),
+            self.singleton(), self.stub(), self.pie(),
+        ];
+
+        let string = flags.iter()
+            .zip(STRINGS.iter())
+            .filter(|(f, _)| **f)
+            .map(|(_, s)| *s)
+            .collect::<Vec<_>>();
+
+        if string.is_empty() {
+            f.write_str("----")
+        } else{
+            f.write_str(&string.join(" + "))
+        }
+    }
+}
@@ -0,0 +1,292 @@
+//! Dynamic entry tags as defined by the ELF format.
+
+
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DynamicTag {
+    /// End of the dynamic section.
+    Null,
+
+    /// Name of a needed library.
+    Needed,
+
+    /// Total size of the relocations of the PLT.
+    PLTRelSize,
+
+    /// Address of the PLT and/or GOT.
+    PLTGOT,
+
+    /// Address of the symbol hash table.
+    Hash,
+
+    /// Address of the dynamic string table.
+    StringTable,
+
+    /// Address of the dynamic symbol table.
+    SymbolTable,
+
+    /// Address of the RELA relocation table.
+    RelocationA,
+
+    /// Total size of the RELA relocation table.
+    RelocationASize,
+
+    /// Size of a RELA relocation entry.
+    RelocationAEntry,
+
+    /// Size of the dynamic string table.
+    StringTableSize,
+
+    /// Size of a symbol table entry.
+    SymbolEntry,
+
+    /// Address of the initialization function.
+    Init,
+
+    /// Address of the termination function.
+    Fini,
+
+    /// Name of the shared object.
+    SOName,
+
+    /// Library search path (deprecated).
+    RPath,
+
+    /// Start symbol resolution in the shared object itself.
+    Symbolic,
+
+    /// Address of the REL relocation table.
+    Relocation,
+
+    /// Total size of the REL relocation table.
+    RelocationSize,
+
+    /// Size of a REL relocation entry.
+    RelocationEntry,
+
+    /// Type of the relocations of the PLT.
+    PLTRel,
+
+    /// Reserved for debugging.
+    Debug,
+
+    /// Relocations may modify a non-writable segment.
+    TextRel,
+
+    /// Address of the relocations of the PLT.
+    JumpRel,
+
+    /// Process all relocations before transferring control.
+    BindNow,
+
+    /// Address of the array of initialization functions.
+    InitArray,
+
+    /// Address of the array of termination functions.
+    FiniArray,
+
+    /// Size of the array of initialization functions.
+    InitArraySize,
+
+    /// Size of the array of termination functions.
+    FiniArraySize,
+
+    /// Library search path.
+    RunPath,
+
+    /// Flags of the object.
+    Flags,
+
+    /// Address of the array of pre-initialization functions.
+    PreInitArray,
+
+    /// Size of the array of pre-initialization functions.
+    PreInitArraySize,
+
+    /// Address of the extended section index table.
+    SymbolTableX,
+
+    /// Total size of the RELR relocation table.
+    RelrSize,
+
+    /// Address of the RELR relocation table.
+    Relr,
+
+    /// Size of a RELR relocation entry.
+    RelrEntry,
+
+    /// Address of the GNU symbol hash table.
+    GNUHash,
+
+    /// Address of the symbol version table.
+    VersionSymbol,
+
+    /// Number of relative RELA relocations.
+    RelocationACount,
+
+    /// Number of relative REL relocations.
+    RelocationCount,
+
+    /// Extended flags of the object.
+    Flags1,
+
+    /// Address of the version definition table.
+    VersionDefinition,
+
+    /// Number of version definitions.
+    VersionDefinitionNum,
+
+    /// Address of the version dependency table.
+    VersionNeed,
+
+    /// Number of version dependencies.
+    VersionNeedNum,
+
+    /// Name of an auxiliary library.
+    Auxiliary,
+
+    /// Name of a filtered library.
+    Filter,
+
+    /// OS Specific.
+    OperatingSystem(u64),
+
+    /// Processor Specific.
+    Processor(u64),
+
+    /// Unknown tag.
+    Unknown(u64),
+}
+
+impl DynamicTag {
+    /// Returns `true` if the value of entries with this tag is an offset into the dynamic string table.
+    pub fn string(&self) -> bool {
+        use DynamicTag::*;
+
+        matches!(*self, Needed | SOName | RPath | RunPath | Auxiliary | Filter)
+    }
+}
+
+impl core::convert::From<u64> for DynamicTag {
+    fn from(t: u64) -> DynamicTag {
+        use DynamicTag::*;
+
+        match t {
+            0x00 => Null,
+            0x01 => Needed,
+            0x02 => PLTRelSize,
+            0x03 => PLTGOT,
+            0x04 => Hash,
+            0x05 => StringTable,
+            0x06 => SymbolTable,
+            0x07 => RelocationA,
+            0x08 => RelocationASize,
+            0x09 => RelocationAEntry,
+            0x0A => StringTableSize,
+            0x0B => SymbolEntry,
+            0x0C => Init,
+            0x0D => Fini,
+            0x0E => SOName,
+            0x0F => RPath,
+            0x10 => Symbolic,
+            0x11 => Relocation,
+            0x12 => RelocationSize,
+            0x13 => RelocationEntry,
+            0x14 => PLTRel,
+            0x15 => Debug,
+            0x16 => TextRel,
+            0x17 => JumpRel,
+            0x18 => BindNow,
+            0x19 => InitArray,
+            0x1A => FiniArray,
+            0x1B => InitArraySize,
+            0x1C => FiniArraySize,
+            0x1D => RunPath,
+            0x1E => Flags,
+            0x20 => PreInitArray,
+            0x21 => PreInitArraySize,
+            0x22 => SymbolTableX,
+            0x23 => RelrSize,
+            0x24 => Relr,
+            0x25 => RelrEntry,
+            0x6FFFFEF5 => GNUHash,
+            0x6FFFFFF0 => VersionSymbol,
+            0x6FFFFFF9 => RelocationACount,
+            0x6FFFFFFA => RelocationCount,
+            0x6FFFFFFB => Flags1,
+            0x6FFFFFFC => VersionDefinition,
+            0x6FFFFFFD => VersionDefinitionNum,
+            0x6FFFFFFE => VersionNeed,
+            0x6FFFFFFF => VersionNeedNum,
+            0x7FFFFFFD => Auxiliary,
+            0x7FFFFFFF => Filter,
+
+            0x6000000D..=0x6FFFFFFF => OperatingSystem(t),
+            0x70000000..=0x7FFFFFFF => Processor(t),
+
+            _ => Unknown(t),
+        }
+    }
+}
+
+impl core::fmt::Display for DynamicTag {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        use DynamicTag::*;
+
+        let arg = match *self {
+            Null                 => String::from("End of the dynamic section"),
+            Needed               => String::from("Needed library"),
+            PLTRelSize           => String::from("Size of the PLT relocations"),
+            PLTGOT               => String::from("PLT/GOT address"),
+            Hash                 => String::from("Symbol hash table"),
+            StringTable          => String::from("String table"),
+            SymbolTable          => String::from("Symbol table"),
+            RelocationA          => String::from("RELA relocations"),
+            RelocationASize      => String::from("Size of the RELA relocations"),
+            RelocationAEntry     => String::from("Size of a RELA entry"),
+            StringTableSize      => String::from("Size of the string table"),
+            SymbolEntry          => String::from("Size of a symbol"),
+            Init                 => String::from("Initialization function"),
+            Fini                 => String::from("Termination function"),
+            SOName               => String::from("Shared object name"),
+            RPath                => String::from("Library search path (deprecated)"),
+            Symbolic             => String::from("Symbolic resolution"),
+            Relocation           => String::from("REL relocations"),
+            RelocationSize       => String::from("Size of the REL relocations"),
+            RelocationEntry      => String::from("Size of a REL entry"),
+            PLTRel               => String::from("Type of the PLT relocations"),
+            Debug                => String::from("Debugging"),
+            TextRel              => String::from("Relocations of read-only segments"),
+            JumpRel              => String::from("PLT relocations"),
+            BindNow              => String::from("Bind now"),
+            InitArray            => String::from("Initialization functions array"),
+            FiniArray            => String::from("Termination functions array"),
+            InitArraySize        => String::from("Size of the initialization functions array"),
+            FiniArraySize        => String::from("Size of the termination functions array"),
+            RunPath              => String::from("Library search path"),
+            Flags                => String::from("Flags"),
+            PreInitArray         => String::from("Pre-initialization functions array"),
+            PreInitArraySize     => String::from("Size of the pre-initialization functions array"),
+            SymbolTableX         => String::from("Extended section indices"),
+            RelrSize             => String::from("Size of the RELR relocations"),
+            Relr                 => String::from("RELR relocations"),
+            RelrEntry            => String::from("Size of a RELR entry"),
+            GNUHash              => String::from("GNU symbol hash table"),
+            VersionSymbol        => String::from("Symbol versions"),
+            RelocationACount     => String::from("Number of relative RELA relocations"),
+            RelocationCount      => String::from("Number of relative REL relocations"),
+            Flags1               => String::from("Flags (extended)"),
+            VersionDefinition    => String::from("Version definitions"),
+            VersionDefinitionNum => String::from("Number of version definitions"),
+            VersionNeed          => String::from("Version dependencies"),
+            VersionNeedNum       => String::from("Number of version dependencies"),
+            Auxiliary            => String::from("Auxiliary library"),
+            Filter               => String::from("Filtered library"),
+            OperatingSystem(x) => format!("OS Specific ({:#X})", x),
+            Processor(x)       => format!("Processor Specific ({:#X})", x),
+            Unknown(x)         => format!("Unknown ({:#X})", x),
+        };
+
+        write!(f, "{}", arg)
+    }
+}
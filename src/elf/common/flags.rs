@@ -2,17 +2,401 @@
 
 
 
-pub struct ArchFlags(pub(super) usize);
+use super::InstructionSet;
 
-impl core::convert::From<u32> for ArchFlags {
+
+
+/// Floating point ABI of an object file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FloatABI {
+    /// Floating point arguments are passed in integer registers.
+    Soft,
+
+    /// Floating point arguments are passed in floating point registers (ARM VFP).
+    Hard,
+
+    /// Single precision arguments are passed in floating point registers.
+    Single,
+
+    /// Single and double precision arguments are passed in floating point registers.
+    Double,
+
+    /// Quad precision arguments are passed in floating point registers.
+    Quad,
+}
+
+impl core::fmt::Display for FloatABI {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let arg = match *self {
+            Self::Soft   => "Soft float",
+            Self::Hard   => "Hard float",
+            Self::Single => "Single float",
+            Self::Double => "Double float",
+            Self::Quad   => "Quad float",
+        };
+
+        f.write_str(arg)
+    }
+}
+
+
+
+/// MIPS architecture level.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MIPSArch {
+    MIPS1,
+    MIPS2,
+    MIPS3,
+    MIPS4,
+    MIPS5,
+    MIPS32,
+    MIPS64,
+    MIPS32R2,
+    MIPS64R2,
+    MIPS32R6,
+    MIPS64R6,
+
+    /// Unknown architecture level.
+    Unknown(u8),
+}
+
+impl core::convert::From<u8> for MIPSArch {
+    fn from(x: u8) -> Self {
+        match x {
+            0x0 => Self::MIPS1,
+            0x1 => Self::MIPS2,
+            0x2 => Self::MIPS3,
+            0x3 => Self::MIPS4,
+            0x4 => Self::MIPS5,
+            0x5 => Self::MIPS32,
+            0x6 => Self::MIPS64,
+            0x7 => Self::MIPS32R2,
+            0x8 => Self::MIPS64R2,
+            0x9 => Self::MIPS32R6,
+            0xA => Self::MIPS64R6,
+
+            _ => Self::Unknown(x),
+        }
+    }
+}
+
+impl core::fmt::Display for MIPSArch {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let arg = match *self {
+            Self::MIPS1    => "MIPS I",
+            Self::MIPS2    => "MIPS II",
+            Self::MIPS3    => "MIPS III",
+            Self::MIPS4    => "MIPS IV",
+            Self::MIPS5    => "MIPS V",
+            Self::MIPS32   => "MIPS32",
+            Self::MIPS64   => "MIPS64",
+            Self::MIPS32R2 => "MIPS32r2",
+            Self::MIPS64R2 => "MIPS64r2",
+            Self::MIPS32R6 => "MIPS32r6",
+            Self::MIPS64R6 => "MIPS64r6",
+
+            Self::Unknown(x) => return write!(f, "MIPS architecture {:#X}", x),
+        };
+
+        f.write_str(arg)
+    }
+}
+
+
+
+/// MIPS calling convention.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MIPSABI {
+    O32,
+    N32,
+    N64,
+    O64,
+    EABI32,
+    EABI64,
+
+    /// Unknown ABI.
+    Unknown(u8),
+}
+
+impl core::fmt::Display for MIPSABI {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let arg = match *self {
+            Self::O32    => "o32",
+            Self::N32    => "n32",
+            Self::N64    => "n64",
+            Self::O64    => "o64",
+            Self::EABI32 => "EABI32",
+            Self::EABI64 => "EABI64",
+
+            Self::Unknown(x) => return write!(f, "MIPS ABI {:#X}", x),
+        };
+
+        f.write_str(arg)
+    }
+}
+
+
+
+/// PowerPC64 ABI version.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PowerPCABI {
+    /// Unspecified, usually ELFv1 with function descriptors.
+    Unspecified,
+
+    /// ELFv1 ABI, with function descriptors.
+    ELFv1,
+
+    /// ELFv2 ABI, without function descriptors.
+    ELFv2,
+
+    /// Reserved value.
+    Reserved,
+}
+
+impl core::fmt::Display for PowerPCABI {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let arg = match *self {
+            Self::Unspecified => "Unspecified ABI",
+            Self::ELFv1       => "ELFv1 ABI",
+            Self::ELFv2       => "ELFv2 ABI",
+            Self::Reserved    => "Reserved ABI",
+        };
+
+        f.write_str(arg)
+    }
+}
+
+
+
+/// Architecture specific flags (`e_flags`), decoded according to the Instruction Set and word size of the file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ArchFlags(pub(crate) u32, pub(crate) InstructionSet, pub(crate) usize);
+
+impl ArchFlags {
+    /// Returns the raw value of the flags.
+    pub fn value(&self) -> u32 {
+        self.0
+    }
+
+    /// Returns the EABI version of an ARM file, or `None` for old ABI files.
+    pub fn eabi(&self) -> Option<u8> {
+        match (self.1, (self.0 >> 24) as u8) {
+            (InstructionSet::Aarch32, 0) => None,
+            (InstructionSet::Aarch32, v) => Some( v ),
+            _ => None,
+        }
+    }
+
+    /// Returns the floating point ABI, if the flags of the Instruction Set declare it.
+    /// ARM files without an explicit float ABI flag return `None`.
+    pub fn floatabi(&self) -> Option<FloatABI> {
+        match self.1 {
+            InstructionSet::Aarch32 if (self.0 & 0x400) != 0 => Some( FloatABI::Hard ),
+            InstructionSet::Aarch32 if (self.0 & 0x200) != 0 => Some( FloatABI::Soft ),
+
+            InstructionSet::RISCV => match (self.0 >> 1) & 0x3 {
+                0 => Some( FloatABI::Soft ),
+                1 => Some( FloatABI::Single ),
+                2 => Some( FloatABI::Double ),
+                _ => Some( FloatABI::Quad ),
+            },
+
+            InstructionSet::LoongArch => match self.0 & 0x7 {
+                1 => Some( FloatABI::Soft ),
+                2 => Some( FloatABI::Single ),
+                3 => Some( FloatABI::Double ),
+                _ => None,
+            },
+
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if this RISC-V file uses compressed instructions.
+    pub fn rvc(&self) -> bool {
+        (self.1 == InstructionSet::RISCV) && ((self.0 & 0x1) != 0)
+    }
+
+    /// Returns `true` if this RISC-V file targets the embedded (RV32E / RV64E) base ISA.
+    pub fn rve(&self) -> bool {
+        (self.1 == InstructionSet::RISCV) && ((self.0 & 0x8) != 0)
+    }
+
+    /// Returns `true` if this RISC-V file requires the Total Store Ordering memory model.
+    pub fn tso(&self) -> bool {
+        (self.1 == InstructionSet::RISCV) && ((self.0 & 0x10) != 0)
+    }
+
+    /// Returns the architecture level of a MIPS file.
+    pub fn mipsarch(&self) -> Option<MIPSArch> {
+        match self.1 {
+            InstructionSet::MIPS | InstructionSet::MIPSRS3000 => Some( MIPSArch::from( (self.0 >> 28) as u8 ) ),
+            _ => None,
+        }
+    }
+
+    /// Returns the ABI of a MIPS file.
+    /// Files without an explicit ABI use o32 in 32-bit files and n64 in 64-bit files.
+    pub fn mipsabi(&self) -> Option<MIPSABI> {
+        match self.1 {
+            InstructionSet::MIPS | InstructionSet::MIPSRS3000 => (),
+            _ => return None,
+        }
+
+        // The n32 ABI has its own flag.
+        if (self.0 & 0x20) != 0 {
+            return Some( MIPSABI::N32 );
+        }
+
+        match ((self.0 >> 12) & 0xF, self.2) {
+            (0, 8) => Some( MIPSABI::N64 ),
+            (0, _) | (1, _) => Some( MIPSABI::O32 ),
+            (2, _) => Some( MIPSABI::O64 ),
+            (3, _) => Some( MIPSABI::EABI32 ),
+            (4, _) => Some( MIPSABI::EABI64 ),
+            (x, _) => Some( MIPSABI::Unknown( x as u8 ) ),
+        }
+    }
+
+    /// Returns the ABI version of a PowerPC64 file.
+    pub fn ppcabi(&self) -> Option<PowerPCABI> {
+        match (self.1, self.0 & 0x3) {
+            (InstructionSet::PowerPCX64, 0) => Some( PowerPCABI::Unspecified ),
+            (InstructionSet::PowerPCX64, 1) => Some( PowerPCABI::ELFv1 ),
+            (InstructionSet::PowerPCX64, 2) => Some( PowerPCABI::ELFv2 ),
+            (InstructionSet::PowerPCX64, _) => Some( PowerPCABI::Reserved ),
+            _ => None,
+        }
+    }
+
+    /// Returns the object ABI version of a LoongArch file.
+    pub fn loongarchabi(&self) -> Option<u8> {
+        match self.1 {
+            InstructionSet::LoongArch => Some( ((self.0 >> 6) & 0x3) as u8 ),
+            _ => None,
+        }
+    }
+}
+
+impl core::convert::From<(u32, InstructionSet, usize)> for ArchFlags {
     #[inline(always)]
-    fn from(x: u32) -> Self {
-        Self(x as usize)
+    fn from((x, isa, word): (u32, InstructionSet, usize)) -> Self {
+        Self(x, isa, word)
     }
 }
 
 impl core::fmt::Display for ArchFlags {
     fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
-        write!(f, "0x{:08X}", self.0)
+        // Collect the decoded flags.
+        let mut string = Vec::new();
+
+        if let Some(v) = self.eabi() {
+            string.push( format!("EABI {}", v) );
+        }
+
+        if let Some(arch) = self.mipsarch() {
+            string.push( format!("{}", arch) );
+        }
+
+        if let Some(abi) = self.mipsabi() {
+            string.push( format!("{}", abi) );
+        }
+
+        if let Some(abi) = self.ppcabi() {
+            string.push( format!("{}", abi) );
+        }
+
+        if let Some(v) = self.loongarchabi() {
+            string.push( format!("Object ABI v{}", v) );
+        }
+
+        if let Some(abi) = self.floatabi() {
+            string.push( format!("{}", abi) );
+        }
+
+        if self.rvc() { string.push( String::from("RVC") ); }
+        if self.rve() { string.push( String::from("RVE") ); }
+        if self.tso() { string.push( String::from("TSO") ); }
+
+        if string.is_empty() {
+            write!(f, "0x{:08X}", self.0)
+        } else {
+            write!(f, "0x{:08X} ({})", self.0, string.join(" + "))
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn arm() {
+        let flags = ArchFlags(0x05000400, InstructionSet::Aarch32, 4);
+
+        assert_eq!(flags.eabi(), Some(5));
+        assert_eq!(flags.floatabi(), Some(FloatABI::Hard));
+        assert_eq!(format!("{}", flags), "0x05000400 (EABI 5 + Hard float)");
+
+        assert_eq!(ArchFlags(0x05000200, InstructionSet::Aarch32, 4).floatabi(), Some(FloatABI::Soft));
+
+        // Old ABI files without a float ABI flag.
+        let flags = ArchFlags(0x00000002, InstructionSet::Aarch32, 4);
+
+        assert_eq!((flags.eabi(), flags.floatabi()), (None, None));
+    }
+
+    #[test]
+    fn riscv() {
+        let flags = ArchFlags(0x0005, InstructionSet::RISCV, 8);
+
+        assert!(flags.rvc() && !flags.rve() && !flags.tso());
+        assert_eq!(flags.floatabi(), Some(FloatABI::Double));
+        assert_eq!(format!("{}", flags), "0x00000005 (Double float + RVC)");
+
+        let flags = ArchFlags(0x0018, InstructionSet::RISCV, 4);
+
+        assert!(!flags.rvc() && flags.rve() && flags.tso());
+        assert_eq!(flags.floatabi(), Some(FloatABI::Soft));
+    }
+
+    #[test]
+    fn mips() {
+        // 32-bit o32 MIPS32r2 with noreorder, PIC and CPIC.
+        let flags = ArchFlags(0x70001007, InstructionSet::MIPS, 4);
+
+        assert_eq!(flags.mipsarch(), Some(MIPSArch::MIPS32R2));
+        assert_eq!(flags.mipsabi(), Some(MIPSABI::O32));
+
+        // 64-bit files without an explicit ABI use n64, unless they have the n32 flag.
+        assert_eq!(ArchFlags(0x80000007, InstructionSet::MIPS, 8).mipsabi(), Some(MIPSABI::N64));
+        assert_eq!(ArchFlags(0x80000027, InstructionSet::MIPS, 8).mipsabi(), Some(MIPSABI::N32));
+        assert_eq!(ArchFlags(0xF0000000, InstructionSet::MIPS, 8).mipsarch(), Some(MIPSArch::Unknown(0xF)));
+    }
+
+    #[test]
+    fn powerpc() {
+        assert_eq!(ArchFlags(2, InstructionSet::PowerPCX64, 8).ppcabi(), Some(PowerPCABI::ELFv2));
+        assert_eq!(ArchFlags(0, InstructionSet::PowerPCX64, 8).ppcabi(), Some(PowerPCABI::Unspecified));
+        assert_eq!(ArchFlags(2, InstructionSet::PowerPC, 4).ppcabi(), None);
+    }
+
+    #[test]
+    fn loongarch() {
+        let flags = ArchFlags(0x43, InstructionSet::LoongArch, 8);
+
+        assert_eq!(flags.floatabi(), Some(FloatABI::Double));
+        assert_eq!(flags.loongarchabi(), Some(1));
+        assert_eq!(format!("{}", flags), "0x00000043 (Object ABI v1 + Double float)");
+    }
+
+    #[test]
+    fn other() {
+        // Flags of other Instruction Sets are not decoded.
+        let flags = ArchFlags(0x05000400, InstructionSet::AMD64, 8);
+
+        assert_eq!((flags.eabi(), flags.floatabi(), flags.mipsabi(), flags.ppcabi()), (None, None, None, None));
+        assert_eq!(format!("{}", flags), "0x05000400");
     }
 }
@@ -119,6 +119,7 @@ pub enum InstructionSet {
     BPF        = 0x0F7,
 
     WDC65C816  = 0x101,
+    LoongArch  = 0x102,
 }
 
 impl core::convert::From<u16> for InstructionSet {
@@ -232,6 +233,7 @@ impl core::convert::From<u16> for InstructionSet {
             0x0F7 => BPF,
 
             0x101 => WDC65C816,
+            0x102 => LoongArch,
 
             _ => InstructionSet::None,
         }
@@ -354,6 +356,7 @@ impl core::fmt::Display for InstructionSet {
             BPF        => "Berkeley Packet Filter",
 
             WDC65C816  => "WDC 65C816",
+            LoongArch  => "LoongArch",
         };
 
         write!(f, "{}", arg)
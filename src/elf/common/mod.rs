@@ -5,24 +5,48 @@
 pub(crate) mod read;
 
 mod abi;
+mod compression;
+mod dynamicflags;
+mod dynamictag;
 mod endian;
 mod filetype;
 mod flags;
 mod isa;
+mod relocationtype;
 mod sectionflags;
 mod sectiontype;
+mod segmentflags;
+mod segmenttype;
 mod symbolbind;
+mod symbolsection;
 mod symboltype;
+mod symbolvisibility;
 
 
 
 
 pub use abi::OperatingSystem;
+pub use compression::CompressionType;
+pub use dynamicflags::{
+    DynamicFlags, DynamicFlags1,
+};
+pub use dynamictag::DynamicTag;
 pub use endian::Endian;
 pub use filetype::FileType;
-pub use flags::ArchFlags;
+pub use flags::{
+    ArchFlags, FloatABI, MIPSABI, MIPSArch, PowerPCABI,
+};
 pub use isa::InstructionSet;
+pub use relocationtype::{
+    RelocationType,
+    Aarch32Relocation, Aarch64Relocation, AMD64Relocation, RISCVRelocation, X86Relocation,
+};
+pub(crate) use relocationtype::DataOperation;
 pub use sectionflags::SectionFlags;
 pub use sectiontype::SectionType;
+pub use segmentflags::SegmentFlags;
+pub use segmenttype::SegmentType;
 pub use symbolbind::SymbolBind;
+pub use symbolsection::SymbolSection;
 pub use symboltype::SymbolType;
+pub use symbolvisibility::SymbolVisibility;
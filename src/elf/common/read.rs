@@ -7,6 +7,12 @@ use byteorder::{
     ByteOrder,
 };
 
+use crate::{
+    elf::{
+        ELFError,
+    },
+};
+
 
 
 #[allow(dead_code)]
@@ -44,3 +50,11 @@ pub(crate) fn little64(bytes: &[u8]) -> u64 {
 pub(crate) fn big64(bytes: &[u8]) -> u64 {
     BigEndian::read_u64(bytes)
 }
+
+/// Private bounds checked access to a range of the file.
+pub(crate) fn slice(raw: &[u8], offset: usize, size: usize) -> Result<&[u8], ELFError> {
+    match offset.checked_add(size) {
+        Some(end) if end <= raw.len() => Ok( &raw[offset..end] ),
+        _ => Err( ELFError::OutOfBounds { offset, size, filesize: raw.len() } ),
+    }
+}
@@ -0,0 +1,83 @@
+//! Aarch32 / ARM relocation types.
+
+
+
+relocations!(
+    /// Aarch32 / ARM relocation type (`R_ARM_*`).
+    Aarch32Relocation, "R_ARM_", {
+        None            = 0   => "NONE",
+        PC24            = 1   => "PC24",
+        Abs32           = 2   => "ABS32",
+        Rel32           = 3   => "REL32",
+        LdrPCG0         = 4   => "LDR_PC_G0",
+        Abs16           = 5   => "ABS16",
+        Abs12           = 6   => "ABS12",
+        ThmAbs5         = 7   => "THM_ABS5",
+        Abs8            = 8   => "ABS8",
+        SBRel32         = 9   => "SBREL32",
+        ThmCall         = 10  => "THM_CALL",
+        ThmPC8          = 11  => "THM_PC8",
+        BRelAdj         = 12  => "BREL_ADJ",
+        TLSDesc         = 13  => "TLS_DESC",
+        ThmSWI8         = 14  => "THM_SWI8",
+        XPC25           = 15  => "XPC25",
+        ThmXPC22        = 16  => "THM_XPC22",
+        TLSDTPMod32     = 17  => "TLS_DTPMOD32",
+        TLSDTPOff32     = 18  => "TLS_DTPOFF32",
+        TLSTPOff32      = 19  => "TLS_TPOFF32",
+        Copy            = 20  => "COPY",
+        GlobDat         = 21  => "GLOB_DAT",
+        JumpSlot        = 22  => "JUMP_SLOT",
+        Relative        = 23  => "RELATIVE",
+        GOTOff32        = 24  => "GOTOFF32",
+        BasePRel        = 25  => "BASE_PREL",
+        GOTBRel         = 26  => "GOT_BREL",
+        PLT32           = 27  => "PLT32",
+        Call            = 28  => "CALL",
+        Jump24          = 29  => "JUMP24",
+        ThmJump24       = 30  => "THM_JUMP24",
+        BaseAbs         = 31  => "BASE_ABS",
+        Target1         = 38  => "TARGET1",
+        SBRel31         = 39  => "SBREL31",
+        V4BX            = 40  => "V4BX",
+        Target2         = 41  => "TARGET2",
+        PRel31          = 42  => "PREL31",
+        MovwAbsNC       = 43  => "MOVW_ABS_NC",
+        MovtAbs         = 44  => "MOVT_ABS",
+        MovwPRelNC      = 45  => "MOVW_PREL_NC",
+        MovtPRel        = 46  => "MOVT_PREL",
+        ThmMovwAbsNC    = 47  => "THM_MOVW_ABS_NC",
+        ThmMovtAbs      = 48  => "THM_MOVT_ABS",
+        ThmMovwPRelNC   = 49  => "THM_MOVW_PREL_NC",
+        ThmMovtPRel     = 50  => "THM_MOVT_PREL",
+        ThmJump19       = 51  => "THM_JUMP19",
+        ThmJump6        = 52  => "THM_JUMP6",
+        ThmAluPRel11_0  = 53  => "THM_ALU_PREL_11_0",
+        ThmPC12         = 54  => "THM_PC12",
+        Abs32NoI        = 55  => "ABS32_NOI",
+        Rel32NoI        = 56  => "REL32_NOI",
+        TLSGOTDesc      = 90  => "TLS_GOTDESC",
+        TLSCall         = 91  => "TLS_CALL",
+        TLSDescSeq      = 92  => "TLS_DESCSEQ",
+        ThmTLSCall      = 93  => "THM_TLS_CALL",
+        PLT32Abs        = 94  => "PLT32_ABS",
+        GOTAbs          = 95  => "GOT_ABS",
+        GOTPRel         = 96  => "GOT_PREL",
+        GOTBRel12       = 97  => "GOT_BREL12",
+        GOTOff12        = 98  => "GOTOFF12",
+        GOTRelax        = 99  => "GOTRELAX",
+        GNUVTEntry      = 100 => "GNU_VTENTRY",
+        GNUVTInherit    = 101 => "GNU_VTINHERIT",
+        ThmJump11       = 102 => "THM_JUMP11",
+        ThmJump8        = 103 => "THM_JUMP8",
+        TLSGD32         = 104 => "TLS_GD32",
+        TLSLDM32        = 105 => "TLS_LDM32",
+        TLSLDO32        = 106 => "TLS_LDO32",
+        TLSIE32         = 107 => "TLS_IE32",
+        TLSLE32         = 108 => "TLS_LE32",
+        TLSLDO12        = 109 => "TLS_LDO12",
+        TLSLE12         = 110 => "TLS_LE12",
+        TLSIE12GP       = 111 => "TLS_IE12GP",
+        IRelative       = 160 => "IRELATIVE",
+    }
+);
@@ -0,0 +1,97 @@
+//! Aarch64 relocation types.
+
+
+
+relocations!(
+    /// Aarch64 relocation type (`R_AARCH64_*`).
+    Aarch64Relocation, "R_AARCH64_", {
+        None                    = 0    => "NONE",
+        Abs64                   = 257  => "ABS64",
+        Abs32                   = 258  => "ABS32",
+        Abs16                   = 259  => "ABS16",
+        PRel64                  = 260  => "PREL64",
+        PRel32                  = 261  => "PREL32",
+        PRel16                  = 262  => "PREL16",
+        MovwUAbsG0              = 263  => "MOVW_UABS_G0",
+        MovwUAbsG0NC            = 264  => "MOVW_UABS_G0_NC",
+        MovwUAbsG1              = 265  => "MOVW_UABS_G1",
+        MovwUAbsG1NC            = 266  => "MOVW_UABS_G1_NC",
+        MovwUAbsG2              = 267  => "MOVW_UABS_G2",
+        MovwUAbsG2NC            = 268  => "MOVW_UABS_G2_NC",
+        MovwUAbsG3              = 269  => "MOVW_UABS_G3",
+        MovwSAbsG0              = 270  => "MOVW_SABS_G0",
+        MovwSAbsG1              = 271  => "MOVW_SABS_G1",
+        MovwSAbsG2              = 272  => "MOVW_SABS_G2",
+        LdPRelLo19              = 273  => "LD_PREL_LO19",
+        AdrPRelLo21             = 274  => "ADR_PREL_LO21",
+        AdrPRelPgHi21           = 275  => "ADR_PREL_PG_HI21",
+        AdrPRelPgHi21NC         = 276  => "ADR_PREL_PG_HI21_NC",
+        AddAbsLo12NC            = 277  => "ADD_ABS_LO12_NC",
+        LdSt8AbsLo12NC          = 278  => "LDST8_ABS_LO12_NC",
+        TstBr14                 = 279  => "TSTBR14",
+        CondBr19                = 280  => "CONDBR19",
+        Jump26                  = 282  => "JUMP26",
+        Call26                  = 283  => "CALL26",
+        LdSt16AbsLo12NC         = 284  => "LDST16_ABS_LO12_NC",
+        LdSt32AbsLo12NC         = 285  => "LDST32_ABS_LO12_NC",
+        LdSt64AbsLo12NC         = 286  => "LDST64_ABS_LO12_NC",
+        MovwPRelG0              = 287  => "MOVW_PREL_G0",
+        MovwPRelG0NC            = 288  => "MOVW_PREL_G0_NC",
+        MovwPRelG1              = 289  => "MOVW_PREL_G1",
+        MovwPRelG1NC            = 290  => "MOVW_PREL_G1_NC",
+        MovwPRelG2              = 291  => "MOVW_PREL_G2",
+        MovwPRelG2NC            = 292  => "MOVW_PREL_G2_NC",
+        MovwPRelG3              = 293  => "MOVW_PREL_G3",
+        LdSt128AbsLo12NC        = 299  => "LDST128_ABS_LO12_NC",
+        GOTRel64                = 307  => "GOTREL64",
+        GOTRel32                = 308  => "GOTREL32",
+        GOTLdPRel19             = 309  => "GOT_LD_PREL19",
+        Ld64GOTOffLo15          = 310  => "LD64_GOTOFF_LO15",
+        AdrGOTPage              = 311  => "ADR_GOT_PAGE",
+        Ld64GOTLo12NC           = 312  => "LD64_GOT_LO12_NC",
+        Ld64GOTPageLo15         = 313  => "LD64_GOTPAGE_LO15",
+        TLSGDAdrPRel21          = 512  => "TLSGD_ADR_PREL21",
+        TLSGDAdrPage21          = 513  => "TLSGD_ADR_PAGE21",
+        TLSGDAddLo12NC          = 514  => "TLSGD_ADD_LO12_NC",
+        TLSGDMovwG1             = 515  => "TLSGD_MOVW_G1",
+        TLSGDMovwG0NC           = 516  => "TLSGD_MOVW_G0_NC",
+        TLSLDAdrPRel21          = 517  => "TLSLD_ADR_PREL21",
+        TLSLDAdrPage21          = 518  => "TLSLD_ADR_PAGE21",
+        TLSLDAddLo12NC          = 519  => "TLSLD_ADD_LO12_NC",
+        TLSLDAddDTPRelHi12      = 528  => "TLSLD_ADD_DTPREL_HI12",
+        TLSLDAddDTPRelLo12      = 529  => "TLSLD_ADD_DTPREL_LO12",
+        TLSLDAddDTPRelLo12NC    = 530  => "TLSLD_ADD_DTPREL_LO12_NC",
+        TLSIEMovwGOTTPRelG1     = 539  => "TLSIE_MOVW_GOTTPREL_G1",
+        TLSIEMovwGOTTPRelG0NC   = 540  => "TLSIE_MOVW_GOTTPREL_G0_NC",
+        TLSIEAdrGOTTPRelPage21  = 541  => "TLSIE_ADR_GOTTPREL_PAGE21",
+        TLSIELd64GOTTPRelLo12NC = 542  => "TLSIE_LD64_GOTTPREL_LO12_NC",
+        TLSIELdGOTTPRelPRel19   = 543  => "TLSIE_LD_GOTTPREL_PREL19",
+        TLSLEMovwTPRelG2        = 544  => "TLSLE_MOVW_TPREL_G2",
+        TLSLEMovwTPRelG1        = 545  => "TLSLE_MOVW_TPREL_G1",
+        TLSLEMovwTPRelG1NC      = 546  => "TLSLE_MOVW_TPREL_G1_NC",
+        TLSLEMovwTPRelG0        = 547  => "TLSLE_MOVW_TPREL_G0",
+        TLSLEMovwTPRelG0NC      = 548  => "TLSLE_MOVW_TPREL_G0_NC",
+        TLSLEAddTPRelHi12       = 549  => "TLSLE_ADD_TPREL_HI12",
+        TLSLEAddTPRelLo12       = 550  => "TLSLE_ADD_TPREL_LO12",
+        TLSLEAddTPRelLo12NC     = 551  => "TLSLE_ADD_TPREL_LO12_NC",
+        TLSDescLdPRel19         = 560  => "TLSDESC_LD_PREL19",
+        TLSDescAdrPRel21        = 561  => "TLSDESC_ADR_PREL21",
+        TLSDescAdrPage21        = 562  => "TLSDESC_ADR_PAGE21",
+        TLSDescLd64Lo12         = 563  => "TLSDESC_LD64_LO12",
+        TLSDescAddLo12          = 564  => "TLSDESC_ADD_LO12",
+        TLSDescOffG1            = 565  => "TLSDESC_OFF_G1",
+        TLSDescOffG0NC          = 566  => "TLSDESC_OFF_G0_NC",
+        TLSDescLdr              = 567  => "TLSDESC_LDR",
+        TLSDescAdd              = 568  => "TLSDESC_ADD",
+        TLSDescCall             = 569  => "TLSDESC_CALL",
+        Copy                    = 1024 => "COPY",
+        GlobDat                 = 1025 => "GLOB_DAT",
+        JumpSlot                = 1026 => "JUMP_SLOT",
+        Relative                = 1027 => "RELATIVE",
+        TLSDTPMod               = 1028 => "TLS_DTPMOD",
+        TLSDTPRel               = 1029 => "TLS_DTPREL",
+        TLSTPRel                = 1030 => "TLS_TPREL",
+        TLSDesc                 = 1031 => "TLSDESC",
+        IRelative               = 1032 => "IRELATIVE",
+    }
+);
@@ -0,0 +1,50 @@
+//! AMD64 relocation types.
+
+
+
+relocations!(
+    /// AMD64 relocation type (`R_X86_64_*`).
+    AMD64Relocation, "R_X86_64_", {
+        None              = 0  => "NONE",
+        Abs64             = 1  => "64",
+        PC32              = 2  => "PC32",
+        GOT32             = 3  => "GOT32",
+        PLT32             = 4  => "PLT32",
+        Copy              = 5  => "COPY",
+        GlobDat           = 6  => "GLOB_DAT",
+        JumpSlot          = 7  => "JUMP_SLOT",
+        Relative          = 8  => "RELATIVE",
+        GOTPCRel          = 9  => "GOTPCREL",
+        Abs32             = 10 => "32",
+        Abs32S            = 11 => "32S",
+        Abs16             = 12 => "16",
+        PC16              = 13 => "PC16",
+        Abs8              = 14 => "8",
+        PC8               = 15 => "PC8",
+        DTPMod64          = 16 => "DTPMOD64",
+        DTPOff64          = 17 => "DTPOFF64",
+        TPOff64           = 18 => "TPOFF64",
+        TLSGD             = 19 => "TLSGD",
+        TLSLD             = 20 => "TLSLD",
+        DTPOff32          = 21 => "DTPOFF32",
+        GOTTPOff          = 22 => "GOTTPOFF",
+        TPOff32           = 23 => "TPOFF32",
+        PC64              = 24 => "PC64",
+        GOTOff64          = 25 => "GOTOFF64",
+        GOTPC32           = 26 => "GOTPC32",
+        GOT64             = 27 => "GOT64",
+        GOTPCRel64        = 28 => "GOTPCREL64",
+        GOTPC64           = 29 => "GOTPC64",
+        GOTPLT64          = 30 => "GOTPLT64",
+        PLTOff64          = 31 => "PLTOFF64",
+        Size32            = 32 => "SIZE32",
+        Size64            = 33 => "SIZE64",
+        GOTPC32TLSDesc    = 34 => "GOTPC32_TLSDESC",
+        TLSDescCall       = 35 => "TLSDESC_CALL",
+        TLSDesc           = 36 => "TLSDESC",
+        IRelative         = 37 => "IRELATIVE",
+        Relative64        = 38 => "RELATIVE64",
+        GOTPCRelX         = 41 => "GOTPCRELX",
+        RexGOTPCRelX      = 42 => "REX_GOTPCRELX",
+    }
+);
@@ -0,0 +1,196 @@
+//! Relocation types of each Instruction Set Architecture.
+
+
+
+/// Builds a relocation type enum from its table of names and values.
+macro_rules! relocations {
+    ($(#[$meta:meta])* $name:ident, $prefix:literal, { $($variant:ident = $value:literal => $string:literal,)* }) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+        pub enum $name {
+            $($variant,)*
+
+            /// Unknown relocation type.
+            Unknown(u32),
+        }
+
+        impl $name {
+            /// Returns the raw value of the relocation type.
+            pub fn value(&self) -> u32 {
+                match *self {
+                    $(Self::$variant => $value,)*
+                    Self::Unknown(x) => x,
+                }
+            }
+        }
+
+        impl core::convert::From<u32> for $name {
+            fn from(t: u32) -> Self {
+                match t {
+                    $($value => Self::$variant,)*
+                    _ => Self::Unknown(t),
+                }
+            }
+        }
+
+        impl core::fmt::Display for $name {
+            fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                match *self {
+                    $(Self::$variant => write!(f, "{}{}", $prefix, $string),)*
+                    Self::Unknown(x) => write!(f, "{}<unknown {:#X}>", $prefix, x),
+                }
+            }
+        }
+    };
+}
+
+
+
+mod aarch32;
+mod aarch64;
+mod amd64;
+mod riscv;
+mod x86;
+
+
+
+pub use aarch32::Aarch32Relocation;
+pub use aarch64::Aarch64Relocation;
+pub use amd64::AMD64Relocation;
+pub use riscv::RISCVRelocation;
+pub use x86::X86Relocation;
+
+use super::InstructionSet;
+
+
+
+/// Operation of a relocation that patches plain data, such as the contents of debug sections.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum DataOperation {
+    /// Writes `S + A` into the given number of bytes.
+    Set(usize),
+
+    /// Adds `S + A` to the given number of bytes.
+    Add(usize),
+
+    /// Subtracts `S + A` from the given number of bytes.
+    Sub(usize),
+}
+
+
+
+/// Relocation type, decoded according to the target Instruction Set Architecture.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RelocationType {
+    /// Intel 80386 relocation.
+    X86(X86Relocation),
+
+    /// AMD64 relocation.
+    AMD64(AMD64Relocation),
+
+    /// Aarch32 / ARM relocation.
+    Aarch32(Aarch32Relocation),
+
+    /// Aarch64 relocation.
+    Aarch64(Aarch64Relocation),
+
+    /// RISC-V relocation.
+    RISCV(RISCVRelocation),
+
+    /// Relocation of an architecture with no known relocation types.
+    Other(u32),
+}
+
+impl RelocationType {
+    /// Returns the raw value of the relocation type.
+    pub fn value(&self) -> u32 {
+        match *self {
+            Self::X86(r) => r.value(),
+            Self::AMD64(r) => r.value(),
+            Self::Aarch32(r) => r.value(),
+            Self::Aarch64(r) => r.value(),
+            Self::RISCV(r) => r.value(),
+            Self::Other(x) => x,
+        }
+    }
+
+    /// Returns the relative relocation type of the given Instruction Set (`R_*_RELATIVE`),
+    /// implied by the entries of RELR tables.
+    pub fn relative(isa: InstructionSet) -> Self {
+        match isa {
+            InstructionSet::X86 | InstructionSet::IntelMCU => Self::X86( X86Relocation::Relative ),
+            InstructionSet::AMD64 => Self::AMD64( AMD64Relocation::Relative ),
+            InstructionSet::Aarch32 => Self::Aarch32( Aarch32Relocation::Relative ),
+            InstructionSet::Aarch64 => Self::Aarch64( Aarch64Relocation::Relative ),
+            InstructionSet::RISCV => Self::RISCV( RISCVRelocation::Relative ),
+
+            _ => Self::Other(0),
+        }
+    }
+
+    /// Returns the operation of the relocation if it patches plain data.
+    pub(crate) fn data(&self) -> Option<DataOperation> {
+        use DataOperation::*;
+
+        match *self {
+            Self::X86(X86Relocation::Abs32) => Some( Set(4) ),
+            Self::X86(X86Relocation::Abs16) => Some( Set(2) ),
+            Self::X86(X86Relocation::Abs8) => Some( Set(1) ),
+
+            Self::AMD64(AMD64Relocation::Abs64) => Some( Set(8) ),
+            Self::AMD64(AMD64Relocation::Abs32) | Self::AMD64(AMD64Relocation::Abs32S) => Some( Set(4) ),
+            Self::AMD64(AMD64Relocation::Abs16) => Some( Set(2) ),
+            Self::AMD64(AMD64Relocation::Abs8) => Some( Set(1) ),
+
+            Self::Aarch32(Aarch32Relocation::Abs32) => Some( Set(4) ),
+            Self::Aarch32(Aarch32Relocation::Abs16) => Some( Set(2) ),
+            Self::Aarch32(Aarch32Relocation::Abs8) => Some( Set(1) ),
+
+            Self::Aarch64(Aarch64Relocation::Abs64) => Some( Set(8) ),
+            Self::Aarch64(Aarch64Relocation::Abs32) => Some( Set(4) ),
+            Self::Aarch64(Aarch64Relocation::Abs16) => Some( Set(2) ),
+
+            Self::RISCV(RISCVRelocation::Abs64) => Some( Set(8) ),
+            Self::RISCV(RISCVRelocation::Abs32) | Self::RISCV(RISCVRelocation::Set32) => Some( Set(4) ),
+            Self::RISCV(RISCVRelocation::Set16) => Some( Set(2) ),
+            Self::RISCV(RISCVRelocation::Set8) => Some( Set(1) ),
+            Self::RISCV(RISCVRelocation::Add64) => Some( Add(8) ),
+            Self::RISCV(RISCVRelocation::Add32) => Some( Add(4) ),
+            Self::RISCV(RISCVRelocation::Add16) => Some( Add(2) ),
+            Self::RISCV(RISCVRelocation::Add8) => Some( Add(1) ),
+            Self::RISCV(RISCVRelocation::Sub64) => Some( Sub(8) ),
+            Self::RISCV(RISCVRelocation::Sub32) => Some( Sub(4) ),
+            Self::RISCV(RISCVRelocation::Sub16) => Some( Sub(2) ),
+            Self::RISCV(RISCVRelocation::Sub8) => Some( Sub(1) ),
+
+            _ => None,
+        }
+    }
+}
+
+impl core::convert::From<(InstructionSet, u32)> for RelocationType {
+    fn from((isa, t): (InstructionSet, u32)) -> Self {
+        match isa {
+            InstructionSet::X86 | InstructionSet::IntelMCU => Self::X86( X86Relocation::from(t) ),
+            InstructionSet::AMD64 => Self::AMD64( AMD64Relocation::from(t) ),
+            InstructionSet::Aarch32 => Self::Aarch32( Aarch32Relocation::from(t) ),
+            InstructionSet::Aarch64 => Self::Aarch64( Aarch64Relocation::from(t) ),
+            InstructionSet::RISCV => Self::RISCV( RISCVRelocation::from(t) ),
+
+            _ => Self::Other(t),
+        }
+    }
+}
+
+impl core::fmt::Display for RelocationType {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match *self {
+            Self::X86(r) => write!(f, "{}", r),
+            Self::AMD64(r) => write!(f, "{}", r),
+            Self::Aarch32(r) => write!(f, "{}", r),
+            Self::Aarch64(r) => write!(f, "{}", r),
+            Self::RISCV(r) => write!(f, "{}", r),
+            Self::Other(x) => write!(f, "Relocation type {:#X}", x),
+        }
+    }
+}
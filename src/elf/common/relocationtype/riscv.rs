@@ -0,0 +1,66 @@
+//! RISC-V relocation types.
+
+
+
+relocations!(
+    /// RISC-V relocation type (`R_RISCV_*`).
+    RISCVRelocation, "R_RISCV_", {
+        None             = 0  => "NONE",
+        Abs32            = 1  => "32",
+        Abs64            = 2  => "64",
+        Relative         = 3  => "RELATIVE",
+        Copy             = 4  => "COPY",
+        JumpSlot         = 5  => "JUMP_SLOT",
+        TLSDTPMod32      = 6  => "TLS_DTPMOD32",
+        TLSDTPMod64      = 7  => "TLS_DTPMOD64",
+        TLSDTPRel32      = 8  => "TLS_DTPREL32",
+        TLSDTPRel64      = 9  => "TLS_DTPREL64",
+        TLSTPRel32       = 10 => "TLS_TPREL32",
+        TLSTPRel64       = 11 => "TLS_TPREL64",
+        TLSDesc          = 12 => "TLSDESC",
+        Branch           = 16 => "BRANCH",
+        JAL              = 17 => "JAL",
+        Call             = 18 => "CALL",
+        CallPLT          = 19 => "CALL_PLT",
+        GOTHi20          = 20 => "GOT_HI20",
+        TLSGOTHi20       = 21 => "TLS_GOT_HI20",
+        TLSGDHi20        = 22 => "TLS_GD_HI20",
+        PCRelHi20        = 23 => "PCREL_HI20",
+        PCRelLo12I       = 24 => "PCREL_LO12_I",
+        PCRelLo12S       = 25 => "PCREL_LO12_S",
+        Hi20             = 26 => "HI20",
+        Lo12I            = 27 => "LO12_I",
+        Lo12S            = 28 => "LO12_S",
+        TPRelHi20        = 29 => "TPREL_HI20",
+        TPRelLo12I       = 30 => "TPREL_LO12_I",
+        TPRelLo12S       = 31 => "TPREL_LO12_S",
+        TPRelAdd         = 32 => "TPREL_ADD",
+        Add8             = 33 => "ADD8",
+        Add16            = 34 => "ADD16",
+        Add32            = 35 => "ADD32",
+        Add64            = 36 => "ADD64",
+        Sub8             = 37 => "SUB8",
+        Sub16            = 38 => "SUB16",
+        Sub32            = 39 => "SUB32",
+        Sub64            = 40 => "SUB64",
+        GOT32PCRel       = 41 => "GOT32_PCREL",
+        Align            = 43 => "ALIGN",
+        RVCBranch        = 44 => "RVC_BRANCH",
+        RVCJump          = 45 => "RVC_JUMP",
+        Relax            = 51 => "RELAX",
+        Sub6             = 52 => "SUB6",
+        Set6             = 53 => "SET6",
+        Set8             = 54 => "SET8",
+        Set16            = 55 => "SET16",
+        Set32            = 56 => "SET32",
+        PCRel32          = 57 => "32_PCREL",
+        IRelative        = 58 => "IRELATIVE",
+        PLT32            = 59 => "PLT32",
+        SetULEB128       = 60 => "SET_ULEB128",
+        SubULEB128       = 61 => "SUB_ULEB128",
+        TLSDescHi20      = 62 => "TLSDESC_HI20",
+        TLSDescLoadLo12  = 63 => "TLSDESC_LOAD_LO12",
+        TLSDescAddLo12   = 64 => "TLSDESC_ADD_LO12",
+        TLSDescCall      = 65 => "TLSDESC_CALL",
+    }
+);
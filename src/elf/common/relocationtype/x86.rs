@@ -0,0 +1,51 @@
+//! Intel 80386 relocation types.
+
+
+
+relocations!(
+    /// Intel 80386 relocation type (`R_386_*`).
+    X86Relocation, "R_386_", {
+        None         = 0  => "NONE",
+        Abs32        = 1  => "32",
+        PC32         = 2  => "PC32",
+        GOT32        = 3  => "GOT32",
+        PLT32        = 4  => "PLT32",
+        Copy         = 5  => "COPY",
+        GlobDat      = 6  => "GLOB_DAT",
+        JumpSlot     = 7  => "JUMP_SLOT",
+        Relative     = 8  => "RELATIVE",
+        GOTOff       = 9  => "GOTOFF",
+        GOTPC        = 10 => "GOTPC",
+        Abs32PLT     = 11 => "32PLT",
+        TLSTPOff     = 14 => "TLS_TPOFF",
+        TLSIE        = 15 => "TLS_IE",
+        TLSGOTIE     = 16 => "TLS_GOTIE",
+        TLSLE        = 17 => "TLS_LE",
+        TLSGD        = 18 => "TLS_GD",
+        TLSLDM       = 19 => "TLS_LDM",
+        Abs16        = 20 => "16",
+        PC16         = 21 => "PC16",
+        Abs8         = 22 => "8",
+        PC8          = 23 => "PC8",
+        TLSGD32      = 24 => "TLS_GD_32",
+        TLSGDPush    = 25 => "TLS_GD_PUSH",
+        TLSGDCall    = 26 => "TLS_GD_CALL",
+        TLSGDPop     = 27 => "TLS_GD_POP",
+        TLSLDM32     = 28 => "TLS_LDM_32",
+        TLSLDMPush   = 29 => "TLS_LDM_PUSH",
+        TLSLDMCall   = 30 => "TLS_LDM_CALL",
+        TLSLDMPop    = 31 => "TLS_LDM_POP",
+        TLSLDO32     = 32 => "TLS_LDO_32",
+        TLSIE32      = 33 => "TLS_IE_32",
+        TLSLE32      = 34 => "TLS_LE_32",
+        TLSDTPMod32  = 35 => "TLS_DTPMOD32",
+        TLSDTPOff32  = 36 => "TLS_DTPOFF32",
+        TLSTPOff32   = 37 => "TLS_TPOFF32",
+        Size32       = 38 => "SIZE32",
+        TLSGOTDesc   = 39 => "TLS_GOTDESC",
+        TLSDescCall  = 40 => "TLS_DESC_CALL",
+        TLSDesc      = 41 => "TLS_DESC",
+        IRelative    = 42 => "IRELATIVE",
+        GOT32X       = 43 => "GOT32X",
+    }
+);
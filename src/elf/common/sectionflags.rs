@@ -2,11 +2,19 @@
 
 
 
+use super::InstructionSet;
 
 
-pub struct SectionFlags(pub(crate) u64);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SectionFlags(pub(crate) u64, pub(crate) InstructionSet);
 
 impl SectionFlags {
+    /// Returns the raw value of the flags.
+    pub fn value(&self) -> u64 {
+        self.0
+    }
+
     /// Returns `true` if this section is writable.
     pub fn write(&self) -> bool {
         (self.0 & 0x1) != 0
@@ -56,21 +64,48 @@ impl SectionFlags {
     pub fn tls(&self) -> bool {
         (self.0 & 0x400) != 0
     }
+
+    /// Returns `true` if the contents of this section are compressed.
+    pub fn compressed(&self) -> bool {
+        (self.0 & 0x800) != 0
+    }
+
+    /// Returns `true` if this section must be kept by the linker's garbage collection.
+    pub fn retain(&self) -> bool {
+        (self.0 & 0x200000) != 0
+    }
+
+    /// Returns `true` if this section is excluded from executable and shared objects.
+    pub fn exclude(&self) -> bool {
+        (self.0 & 0x80000000) != 0
+    }
+
+    /// Returns `true` if this AMD64 section can hold more than 2 GiB.
+    pub fn large(&self) -> bool {
+        (self.1 == InstructionSet::AMD64) && ((self.0 & 0x10000000) != 0)
+    }
+
+    /// Returns `true` if this ARM section contains only executable code, which cannot be read.
+    pub fn purecode(&self) -> bool {
+        (self.1 == InstructionSet::Aarch32) && ((self.0 & 0x20000000) != 0)
+    }
 }
 
 impl core::fmt::Display for SectionFlags {
     fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         // Contant strings.
-        const STRINGS: [&'static str; 10] = [
+        const STRINGS: [&str; 15] = [
             "Write", "Alloc", "Exec", "Merge", "Strings",
             "InfoLink", "LinkOrder", "OS-Non conforming",
-            "Group", "TLS",
+            "Group", "TLS", "Compressed", "Retain",
+            "Exclude", "Large", "Pure code",
         ];
 
         let flags = [
             self.write(), self.alloc(), self.exec(), self.merge(), self.strings(),
             self.infolink(), self.linkorder(), self.nonconforming(),
-            self.group(), self.tls(),
+            self.group(), self.tls(), self.compressed(), self.retain(),
+            self.exclude(), self.large(), self.purecode(),
         ];
 
         let string = flags.iter()
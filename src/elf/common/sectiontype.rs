@@ -2,6 +2,10 @@
 
 
 
+use super::InstructionSet;
+
+
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum SectionType {
     /// Null section type, used also for unknown.
@@ -55,11 +59,118 @@ pub enum SectionType {
     /// Extended section indices.
     SymbolTableX,
 
-    /// Number of defined types.
-    Num,
+    /// Relative relocation entries in the compact RELR format.
+    RelativeRelocation,
+
+    /// Android packed relocation entries with no addends.
+    AndroidRelocation,
+
+    /// Android packed relocation entries with addends.
+    AndroidRelocationA,
+
+    /// Android relative relocation entries in the compact RELR format.
+    AndroidRelativeRelocation,
+
+    /// LLVM address significance table.
+    LLVMAddressSignificance,
+
+    /// LLVM call graph profile.
+    LLVMCallGraphProfile,
+
+    /// GNU object attributes.
+    GNUAttributes,
+
+    /// GNU symbol hash table.
+    GNUHash,
+
+    /// GNU version definitions.
+    GNUVersionDefinition,
+
+    /// GNU version requirements.
+    GNUVersionNeed,
+
+    /// GNU symbol versions.
+    GNUVersionSymbol,
+
+    /// ARM exception index table.
+    ARMExceptionIndex,
+
+    /// ARM build attributes.
+    ARMAttributes,
+
+    /// RISC-V build attributes.
+    RISCVAttributes,
+
+    /// AMD64 unwind information.
+    AMD64Unwind,
 
     /// OS Specific.
     OperatingSystem(u32),
+
+    /// Processor specific.
+    Processor(u32),
+
+    /// Unknown section type.
+    Unknown(u32),
+}
+
+impl SectionType {
+    /// Builds the section type of a file for the given Instruction Set,
+    /// which disambiguates the processor specific types.
+    pub fn new(t: u32, isa: InstructionSet) -> SectionType {
+        use SectionType::*;
+
+        match (t, isa) {
+            (0x70000001, InstructionSet::Aarch32) => ARMExceptionIndex,
+            (0x70000003, InstructionSet::Aarch32) => ARMAttributes,
+            (0x70000003, InstructionSet::RISCV) => RISCVAttributes,
+            (0x70000001, InstructionSet::AMD64) => AMD64Unwind,
+
+            _ => SectionType::from(t),
+        }
+    }
+
+    /// Returns the raw value of the section type.
+    pub fn value(&self) -> u32 {
+        use SectionType::*;
+
+        match *self {
+            Null          => 0x00,
+            ProgramData   => 0x01,
+            SymbolTable   => 0x02,
+            StringTable   => 0x03,
+            RelocationA   => 0x04,
+            Hash          => 0x05,
+            Dynamic       => 0x06,
+            Notes         => 0x07,
+            Bss           => 0x08,
+            Relocation    => 0x09,
+            SharedLib     => 0x0A,
+            DynamicSymbol => 0x0B,
+            Init          => 0x0E,
+            Fini          => 0x0F,
+            PreInit       => 0x10,
+            Group         => 0x11,
+            SymbolTableX  => 0x12,
+            RelativeRelocation => 0x13,
+
+            AndroidRelocation         => 0x60000001,
+            AndroidRelocationA        => 0x60000002,
+            LLVMAddressSignificance   => 0x6FFF4C03,
+            LLVMCallGraphProfile      => 0x6FFF4C09,
+            AndroidRelativeRelocation => 0x6FFFFF00,
+            GNUAttributes             => 0x6FFFFFF5,
+            GNUHash                   => 0x6FFFFFF6,
+            GNUVersionDefinition      => 0x6FFFFFFD,
+            GNUVersionNeed            => 0x6FFFFFFE,
+            GNUVersionSymbol          => 0x6FFFFFFF,
+
+            ARMExceptionIndex | AMD64Unwind => 0x70000001,
+            ARMAttributes | RISCVAttributes => 0x70000003,
+
+            OperatingSystem(x) | Processor(x) | Unknown(x) => x,
+        }
+    }
 }
 
 impl core::convert::From<u32> for SectionType {
@@ -84,10 +195,23 @@ impl core::convert::From<u32> for SectionType {
             0x10 => PreInit,
             0x11 => Group,
             0x12 => SymbolTableX,
-            0x13 => Num,
+            0x13 => RelativeRelocation,
+
+            0x60000001 => AndroidRelocation,
+            0x60000002 => AndroidRelocationA,
+            0x6FFF4C03 => LLVMAddressSignificance,
+            0x6FFF4C09 => LLVMCallGraphProfile,
+            0x6FFFFF00 => AndroidRelativeRelocation,
+            0x6FFFFFF5 => GNUAttributes,
+            0x6FFFFFF6 => GNUHash,
+            0x6FFFFFFD => GNUVersionDefinition,
+            0x6FFFFFFE => GNUVersionNeed,
+            0x6FFFFFFF => GNUVersionSymbol,
+
             x => match x {
+                0x70000000..=0x7FFFFFFF => Processor(x),
                 0x60000000..=0xFFFFFFFF => OperatingSystem(x),
-                _ => Null,
+                _ => Unknown(x),
             },
         }
     }
@@ -98,7 +222,7 @@ impl core::fmt::Display for SectionType {
         use SectionType::*;
 
         let arg = match *self {
-            Null          => String::from("Null/Unused/Unknown"),
+            Null          => String::from("Null/Unused"),
             ProgramData   => String::from("Program data"),
             SymbolTable   => String::from("Symbol table"),
             StringTable   => String::from("String table"),
@@ -115,8 +239,24 @@ impl core::fmt::Display for SectionType {
             PreInit       => String::from("Array of pre-contructors"),
             Group         => String::from("Section group"),
             SymbolTableX  => String::from("Extended section indices"),
-            Num           => String::from("Number of defined types"),
+            RelativeRelocation => String::from("Relative relocation entries (RELR)"),
+            AndroidRelocation  => String::from("Android packed relocation entries with no addends"),
+            AndroidRelocationA => String::from("Android packed relocation entries with addend"),
+            AndroidRelativeRelocation => String::from("Android relative relocation entries (RELR)"),
+            LLVMAddressSignificance   => String::from("LLVM address significance table"),
+            LLVMCallGraphProfile      => String::from("LLVM call graph profile"),
+            GNUAttributes        => String::from("GNU object attributes"),
+            GNUHash              => String::from("GNU symbol hash table"),
+            GNUVersionDefinition => String::from("GNU version definitions"),
+            GNUVersionNeed       => String::from("GNU version requirements"),
+            GNUVersionSymbol     => String::from("GNU symbol versions"),
+            ARMExceptionIndex => String::from("ARM exception index table"),
+            ARMAttributes     => String::from("ARM build attributes"),
+            RISCVAttributes   => String::from("RISC-V build attributes"),
+            AMD64Unwind       => String::from("AMD64 unwind information"),
             OperatingSystem(x) => format!("OS Specific ({:#X})", x),
+            Processor(x) => format!("Processor Specific ({:#X})", x),
+            Unknown(x) => format!("Unknown ({:#X})", x),
         };
 
         write!(f, "{}", arg)
@@ -0,0 +1,48 @@
+//! Program Header flags.
+
+
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SegmentFlags(pub(crate) u32);
+
+impl SegmentFlags {
+    /// Returns `true` if this segment is executable.
+    pub fn exec(&self) -> bool {
+        (self.0 & 0x1) != 0
+    }
+
+    /// Returns `true` if this segment is writable.
+    pub fn write(&self) -> bool {
+        (self.0 & 0x2) != 0
+    }
+
+    /// Returns `true` if this segment is readable.
+    pub fn read(&self) -> bool {
+        (self.0 & 0x4) != 0
+    }
+}
+
+impl core::fmt::Display for SegmentFlags {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        // Contant strings.
+        const STRINGS: [&str; 3] = [
+            "Read", "Write", "Exec",
+        ];
+
+        let flags = [
+            self.read(), self.write(), self.exec(),
+        ];
+
+        let string = flags.iter()
+            .zip(STRINGS.iter())
+            .filter(|(f, _)| **f)
+            .map(|(_, s)| *s)
+            .collect::<Vec<_>>();
+
+        if string.is_empty() {
+            f.write_str("----")
+        } else{
+            f.write_str(&string.join(" + "))
+        }
+    }
+}
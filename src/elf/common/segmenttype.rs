@@ -0,0 +1,129 @@
+//! Segment Type as defined by the ELF format.
+
+
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SegmentType {
+    /// Null segment type, unused entry.
+    Null,
+
+    /// Loadable segment.
+    Load,
+
+    /// Dynamic linking information.
+    Dynamic,
+
+    /// Path to the program interpreter.
+    Interpreter,
+
+    /// Auxiliary information.
+    Note,
+
+    /// Reserved.
+    SharedLib,
+
+    /// Location of the Program Header Table itself.
+    ProgramHeader,
+
+    /// Thread Local Storage template.
+    TLS,
+
+    /// GNU exception handling frame header.
+    GNUEHFrame,
+
+    /// GNU stack executability.
+    GNUStack,
+
+    /// GNU read-only after relocation.
+    GNURelRO,
+
+    /// GNU program properties.
+    GNUProperty,
+
+    /// OS Specific.
+    OperatingSystem(u32),
+
+    /// Processor Specific.
+    Processor(u32),
+
+    /// Unknown segment type.
+    Unknown(u32),
+}
+
+impl SegmentType {
+    /// Returns the raw value of the segment type.
+    pub fn value(&self) -> u32 {
+        use SegmentType::*;
+
+        match *self {
+            Null          => 0x00,
+            Load          => 0x01,
+            Dynamic       => 0x02,
+            Interpreter   => 0x03,
+            Note          => 0x04,
+            SharedLib     => 0x05,
+            ProgramHeader => 0x06,
+            TLS           => 0x07,
+
+            GNUEHFrame  => 0x6474E550,
+            GNUStack    => 0x6474E551,
+            GNURelRO    => 0x6474E552,
+            GNUProperty => 0x6474E553,
+
+            OperatingSystem(x) | Processor(x) | Unknown(x) => x,
+        }
+    }
+}
+
+impl core::convert::From<u32> for SegmentType {
+    fn from(t: u32) -> SegmentType {
+        use SegmentType::*;
+
+        match t {
+            0x00 => Null,
+            0x01 => Load,
+            0x02 => Dynamic,
+            0x03 => Interpreter,
+            0x04 => Note,
+            0x05 => SharedLib,
+            0x06 => ProgramHeader,
+            0x07 => TLS,
+
+            0x6474E550 => GNUEHFrame,
+            0x6474E551 => GNUStack,
+            0x6474E552 => GNURelRO,
+            0x6474E553 => GNUProperty,
+
+            0x60000000..=0x6FFFFFFF => OperatingSystem(t),
+            0x70000000..=0x7FFFFFFF => Processor(t),
+
+            _ => Unknown(t),
+        }
+    }
+}
+
+impl core::fmt::Display for SegmentType {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        use SegmentType::*;
+
+        let arg = match *self {
+            Null          => String::from("Null/Unused"),
+            Load          => String::from("Loadable segment"),
+            Dynamic       => String::from("Dynamic linking information"),
+            Interpreter   => String::from("Program interpreter"),
+            Note          => String::from("Notes"),
+            SharedLib     => String::from("RESERVED"),
+            ProgramHeader => String::from("Program Header Table"),
+            TLS           => String::from("Thread Local Storage"),
+            GNUEHFrame    => String::from("GNU EH frame header"),
+            GNUStack      => String::from("GNU stack"),
+            GNURelRO      => String::from("GNU read-only after relocation"),
+            GNUProperty   => String::from("GNU properties"),
+            OperatingSystem(x) => format!("OS Specific ({:#X})", x),
+            Processor(x)       => format!("Processor Specific ({:#X})", x),
+            Unknown(x)         => format!("Unknown ({:#X})", x),
+        };
+
+        write!(f, "{}", arg)
+    }
+}
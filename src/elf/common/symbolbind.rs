@@ -12,6 +12,12 @@ pub enum SymbolBind {
 	/// Weak bind.
 	Weak,
 
+	/// GNU unique bind: one definition per process.
+	GNUUnique,
+
+	/// OS specific.
+	OperatingSystem(u8),
+
 	/// Processor specific.
 	Processor(u8),
 
@@ -25,6 +31,10 @@ impl core::convert::From<u8> for SymbolBind {
             1 => SymbolBind::Global,
             2 => SymbolBind::Weak,
 
+            10 => SymbolBind::GNUUnique,
+
+            11..=12 => SymbolBind::OperatingSystem(u),
+
             13..=15 => SymbolBind::Processor(u),
 
             _ => SymbolBind::None,
@@ -39,6 +49,10 @@ impl core::fmt::Display for SymbolBind {
             SymbolBind::Global => String::from("Global"),
             SymbolBind::Weak  => String::from("Weak"),
 
+            SymbolBind::GNUUnique => String::from("GNU unique"),
+
+            SymbolBind::OperatingSystem(u) => format!("OS {}", u),
+
             SymbolBind::Processor(u) => format!("Processor {}", u),
 
             SymbolBind::None => String::from("No binding"),
@@ -0,0 +1,69 @@
+//! Section a symbol is defined relative to.
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SymbolSection {
+    /// Undefined symbol (`SHN_UNDEF`).
+    Undefined,
+
+    /// Absolute value, not affected by relocation (`SHN_ABS`).
+    Absolute,
+
+    /// Common block not yet allocated (`SHN_COMMON`).
+    Common,
+
+    /// Index of the section the symbol is defined in.
+    Index(usize),
+
+    /// Index escaped to the extended section index table and not yet resolved (`SHN_XINDEX`).
+    Extended,
+
+    /// Processor or OS specific reserved index.
+    Reserved(u16),
+}
+
+impl SymbolSection {
+    /// Returns the raw `st_shndx` value of the section.
+    pub fn index(&self) -> usize {
+        match *self {
+            SymbolSection::Undefined => 0,
+            SymbolSection::Absolute  => 0xFFF1,
+            SymbolSection::Common    => 0xFFF2,
+            SymbolSection::Extended  => 0xFFFF,
+
+            SymbolSection::Index(i) => i,
+            SymbolSection::Reserved(u) => u as usize,
+        }
+    }
+}
+
+impl core::convert::From<u16> for SymbolSection {
+    fn from(u: u16) -> Self {
+        match u {
+            0x0000 => SymbolSection::Undefined,
+            0xFFF1 => SymbolSection::Absolute,
+            0xFFF2 => SymbolSection::Common,
+            0xFFFF => SymbolSection::Extended,
+
+            0xFF00..=0xFFFE => SymbolSection::Reserved(u),
+
+            _ => SymbolSection::Index(u as usize),
+        }
+    }
+}
+
+impl core::fmt::Display for SymbolSection {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let s = match *self {
+            SymbolSection::Undefined => String::from("Undefined"),
+            SymbolSection::Absolute  => String::from("Absolute"),
+            SymbolSection::Common    => String::from("Common"),
+            SymbolSection::Extended  => String::from("Extended"),
+
+            SymbolSection::Index(i) => format!("{}", i),
+            SymbolSection::Reserved(u) => format!("Reserved {:#X}", u),
+        };
+
+        write!(f, "{}", s)
+    }
+}
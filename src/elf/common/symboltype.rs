@@ -18,6 +18,18 @@ pub enum SymbolType {
     /// File.
     File,
 
+    /// Uninitialized common block.
+    Common,
+
+    /// Thread Local Storage entity.
+    TLS,
+
+    /// GNU indirect function, resolved at load time.
+    GNUIndirectFunction,
+
+    /// OS specific.
+    OperatingSystem(u8),
+
     /// Processor specific.
     Processor(u8),
 }
@@ -30,7 +42,12 @@ impl core::convert::From<u8> for SymbolType {
             2 => SymbolType::Function,
             3 => SymbolType::Section,
             4 => SymbolType::File,
+            5 => SymbolType::Common,
+            6 => SymbolType::TLS,
 
+            10 => SymbolType::GNUIndirectFunction,
+
+            11..=12 => SymbolType::OperatingSystem(u),
             13..=15 => SymbolType::Processor(u),
 
             _ => SymbolType::None,
@@ -45,6 +62,12 @@ impl core::fmt::Display for SymbolType {
             SymbolType::Function => String::from("Function"),
             SymbolType::Section  => String::from("Section"),
             SymbolType::File     => String::from("File"),
+            SymbolType::Common   => String::from("Common"),
+            SymbolType::TLS      => String::from("TLS"),
+
+            SymbolType::GNUIndirectFunction => String::from("GNU indirect function"),
+
+            SymbolType::OperatingSystem(u) => format!("OS {}", u),
 
             SymbolType::Processor(u) => format!("Processor {}", u),
 
@@ -0,0 +1,42 @@
+//! Symbol visibility.
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SymbolVisibility {
+    /// Visibility given by the binding of the symbol.
+    Default,
+
+    /// Processor specific hidden visibility.
+    Internal,
+
+    /// Not visible to other components.
+    Hidden,
+
+    /// Visible to other components, but not preemptable.
+    Protected,
+}
+
+impl core::convert::From<u8> for SymbolVisibility {
+    fn from(u: u8) -> Self {
+        match u & 0x3 {
+            1 => SymbolVisibility::Internal,
+            2 => SymbolVisibility::Hidden,
+            3 => SymbolVisibility::Protected,
+
+            _ => SymbolVisibility::Default,
+        }
+    }
+}
+
+impl core::fmt::Display for SymbolVisibility {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let s = match *self {
+            SymbolVisibility::Default   => "Default",
+            SymbolVisibility::Internal  => "Internal",
+            SymbolVisibility::Hidden    => "Hidden",
+            SymbolVisibility::Protected => "Protected",
+        };
+
+        write!(f, "{}", s)
+    }
+}
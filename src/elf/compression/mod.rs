@@ -0,0 +1,217 @@
+//! Compressed section parsing and decompression.
+
+
+
+use crate::{
+    elf::{
+        common::{
+            read::{
+                self,
+                big32, little32,
+                big64, little64,
+            },
+
+            CompressionType, Endian,
+        },
+
+        ELFError,
+    },
+};
+
+
+
+/// Header of the contents of a compressed section.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CompressionHeader {
+    /// Compression algorithm.
+    pub ctype: CompressionType,
+
+    /// Size of the decompressed contents.
+    pub size: usize,
+
+    /// Alignment of the decompressed contents.
+    pub align: usize,
+
+    /// Size of the header, which precedes the compressed data.
+    pub header: usize,
+}
+
+impl CompressionHeader {
+    /// Magic prefix of the legacy `.zdebug_*` sections.
+    const ZLIB: &'static [u8] = b"ZLIB";
+
+    /// Parses the `Elf32_Chdr` or `Elf64_Chdr` header of a `SHF_COMPRESSED` section.
+    pub(crate) fn parse(data: &[u8], endian: Endian, word: usize) -> Result<Self, ELFError> {
+        // Get the read functions.
+        let read32: fn(&[u8]) -> u32 = match endian {
+            Endian::Little => little32,
+            Endian::Big => big32,
+        };
+
+        let read64: fn(&[u8]) -> u64 = match endian {
+            Endian::Little => little64,
+            Endian::Big => big64,
+        };
+
+        match word {
+            4 => {
+                let header = read::slice(data, 0, 12)?;
+
+                Ok( Self {
+                    ctype: CompressionType::from( read32( &header[0..4] ) ),
+                    size: read32( &header[4..8] ) as usize,
+                    align: read32( &header[8..12] ) as usize,
+                    header: 12,
+                })
+            },
+
+            _ => {
+                let header = read::slice(data, 0, 24)?;
+
+                Ok( Self {
+                    ctype: CompressionType::from( read32( &header[0..4] ) ),
+                    size: read64( &header[8..16] ) as usize,
+                    align: read64( &header[16..24] ) as usize,
+                    header: 24,
+                })
+            },
+        }
+    }
+
+    /// Parses the header of a legacy `.zdebug_*` section: the "ZLIB" magic followed
+    /// by the big endian 64 bit decompressed size. Returns `None` if the magic is missing.
+    pub(crate) fn zdebug(data: &[u8]) -> Option<Self> {
+        let header = read::slice(data, 0, 12).ok()?;
+
+        if &header[0..4] != Self::ZLIB {
+            return None;
+        }
+
+        Some( Self {
+            ctype: CompressionType::Zlib,
+            size: big64( &header[4..12] ) as usize,
+            align: 1,
+            header: 12,
+        })
+    }
+
+    /// Decompresses the contents of a section, including this header.
+    /// The offset is the file offset of the section, used for errors.
+    #[cfg_attr(not(any(feature = "zlib", feature = "zstd")), allow(unused_variables))]
+    pub fn decompress(&self, data: &[u8], offset: usize) -> Result<Vec<u8>, ELFError> {
+        // Check the header is contained in the data.
+        if data.len() < self.header {
+            return Err( ELFError::Truncated { offset, expected: self.header, found: data.len() } );
+        }
+
+        let decompressed: Result<Option<Vec<u8>>, ELFError> = match self.ctype {
+            #[cfg(feature = "zlib")]
+            CompressionType::Zlib => Ok( miniz_oxide::inflate::decompress_to_vec_zlib_with_limit(&data[self.header..], self.size).ok() ),
+
+            #[cfg(feature = "zstd")]
+            CompressionType::Zstd => {
+                use std::io::Read;
+
+                // Limit the output to detect contents larger than declared.
+                let mut decompressed = Vec::new();
+
+                match ruzstd::StreamingDecoder::new(&data[self.header..]) {
+                    Ok(decoder) => Ok( decoder.take((self.size as u64).saturating_add(1)).read_to_end(&mut decompressed).ok().map(|_| decompressed) ),
+                    _ => Ok( None ),
+                }
+            },
+
+            ctype => Err( ELFError::UnsupportedCompression { offset, ctype } ),
+        };
+
+        match decompressed? {
+            Some(decompressed) if decompressed.len() == self.size => Ok( decompressed ),
+            _ => Err( ELFError::BadCompressedData { offset, size: self.size } ),
+        }
+    }
+}
+
+impl core::fmt::Display for CompressionHeader {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "{} compressed, {} B decompressed ({} B aligned)", self.ctype, self.size, self.align)
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Contents of the compressed sections.
+    #[cfg(any(feature = "zlib", feature = "zstd"))]
+    const TEXT: &[u8] = b"hello hello hello hello\n";
+
+    /// Builds the contents of a 64-bit little endian compressed section.
+    fn section(ctype: u32, size: u64, data: &[u8]) -> Vec<u8> {
+        let mut section = Vec::new();
+
+        section.extend_from_slice(&ctype.to_le_bytes());
+        section.extend_from_slice(&0u32.to_le_bytes());
+        section.extend_from_slice(&size.to_le_bytes());
+        section.extend_from_slice(&1u64.to_le_bytes());
+        section.extend_from_slice(data);
+
+        section
+    }
+
+    #[test]
+    fn header() {
+        let data = section(1, 24, &[]);
+
+        let header = CompressionHeader::parse(&data, Endian::Little, 8).unwrap();
+
+        assert_eq!(header, CompressionHeader { ctype: CompressionType::Zlib, size: 24, align: 1, header: 24 });
+        assert!(CompressionHeader::parse(&data[..20], Endian::Little, 8).is_err());
+    }
+
+    #[test]
+    fn zdebug() {
+        let header = CompressionHeader::zdebug(b"ZLIB\0\0\0\0\0\0\x01\x00").unwrap();
+
+        assert_eq!((header.ctype, header.size, header.header), (CompressionType::Zlib, 0x100, 12));
+        assert!(CompressionHeader::zdebug(b"ZSTD\0\0\0\0\0\0\x01\x00").is_none());
+    }
+
+    #[cfg(feature = "zlib")]
+    #[test]
+    fn zlib() {
+        const DATA: [u8; 17] = [120, 156, 203, 72, 205, 201, 201, 87, 200, 64, 39, 185, 0, 112, 190, 8, 187];
+
+        let data = section(1, TEXT.len() as u64, &DATA);
+        let header = CompressionHeader::parse(&data, Endian::Little, 8).unwrap();
+
+        assert_eq!(header.decompress(&data, 0x100).unwrap(), TEXT);
+
+        // Declared sizes that do not match the contents.
+        for size in [10, 100, u64::MAX] {
+            let data = section(1, size, &DATA);
+            let header = CompressionHeader::parse(&data, Endian::Little, 8).unwrap();
+
+            assert!(matches!(header.decompress(&data, 0x100), Err(ELFError::BadCompressedData { offset: 0x100, .. })));
+        }
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn zstd() {
+        const DATA: [u8; 26] = [40, 181, 47, 253, 4, 104, 109, 0, 0, 56, 104, 101, 108, 108, 111, 32, 10, 1, 0, 153, 75, 17, 168, 124, 46, 168];
+
+        let data = section(2, TEXT.len() as u64, &DATA);
+        let header = CompressionHeader::parse(&data, Endian::Little, 8).unwrap();
+
+        assert_eq!(header.decompress(&data, 0x100).unwrap(), TEXT);
+
+        // Declared sizes that do not match the contents.
+        for size in [10, 100, u64::MAX] {
+            let data = section(2, size, &DATA);
+            let header = CompressionHeader::parse(&data, Endian::Little, 8).unwrap();
+
+            assert!(matches!(header.decompress(&data, 0x100), Err(ELFError::BadCompressedData { offset: 0x100, .. })));
+        }
+    }
+}
@@ -0,0 +1,159 @@
+//! Core dump inspection.
+
+
+
+use crate::{
+    elf::{
+        common::{
+            SegmentType,
+        },
+
+        AuxEntry, ELFError, ELFFile, MappedFile, NoteData,
+        ProcessInfo, ProcessStatus, SignalInfo,
+    },
+};
+
+
+
+/// Contains the process state recorded in the notes of a core dump.
+pub struct ELFCore {
+    /// Status and registers of each thread, starting with the one that caused the dump.
+    pub threads: Vec<ProcessStatus>,
+
+    /// Information of the dumped process.
+    pub process: Option<ProcessInfo>,
+
+    /// Auxiliary vector of the process.
+    pub auxv: Vec<AuxEntry>,
+
+    /// Signal that caused the dump.
+    pub signal: Option<SignalInfo>,
+
+    /// Files mapped in the address space of the process.
+    pub files: Vec<MappedFile>,
+}
+
+impl ELFCore {
+    /// Collects the core dump notes of all the note segments of the given file.
+    pub fn parse(file: &ELFFile) -> Result<Self, ELFError> {
+        let mut core = Self {
+            threads: Vec::new(),
+            process: None,
+            auxv: Vec::new(),
+            signal: None,
+            files: Vec::new(),
+        };
+
+        for segment in file.segments()? {
+            let segment = segment?;
+
+            if segment.stype() != SegmentType::Note {
+                continue;
+            }
+
+            for note in file.segment_notes(segment.as_ref())? {
+                match note?.data()? {
+                    NoteData::ProcessStatus(status) => core.threads.push( *status ),
+                    NoteData::ProcessInfo(info) => core.process = Some( info ),
+                    NoteData::AuxVector(auxv) => core.auxv = auxv,
+                    NoteData::SignalInfo(info) => core.signal = Some( info ),
+                    NoteData::MappedFiles(files) => core.files = files,
+
+                    _ => (),
+                }
+            }
+        }
+
+        Ok( core )
+    }
+}
+
+impl core::fmt::Display for ELFCore {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        // Build the output string.
+        let mut string = String::from("ELF Core Dump\n");
+
+        // Add process information.
+        if let Some(process) = &self.process {
+            string += &format!("|- {}\n", process);
+        }
+
+        if let Some(signal) = &self.signal {
+            string += &format!("|- {}\n", signal);
+        }
+
+        // Add all the threads.
+        string += "|- Threads\n";
+
+        for thread in self.threads.iter() {
+            string += &format!("|  |- {}\n", thread);
+        }
+
+        // Add the auxiliary vector.
+        string += "|- Auxiliary vector\n";
+
+        for entry in self.auxv.iter() {
+            string += &format!("|  |- {}\n", entry);
+        }
+
+        // Add the mapped files.
+        string += "|- Mapped files\n";
+
+        for file in self.files.iter() {
+            string += &format!("   |- {}\n", file);
+        }
+
+        string += "\n";
+
+        f.write_str(&string)
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use crate::elf::{
+        fixture::{
+            Image, Segment,
+        },
+
+        ELFContent,
+    };
+
+    /// Builds a 64-bit little endian core dump with a single note segment holding a `CORE` note.
+    fn core(ntype: u32, desc: &[u8]) -> Vec<u8> {
+        let mut note = Vec::new();
+
+        note.extend_from_slice(&5u32.to_le_bytes());
+        note.extend_from_slice(&(desc.len() as u32).to_le_bytes());
+        note.extend_from_slice(&ntype.to_le_bytes());
+        note.extend_from_slice(b"CORE\0\0\0\0");
+        note.extend_from_slice(desc);
+
+        Image::new(4, 0x3E)
+            .segment( Segment { ptype: 4, contents: &note, align: 4, ..Default::default() } )
+            .build()
+    }
+
+    #[test]
+    fn auxiliary_vector() {
+        let desc = [6u64, 0x1000, 0, 0].iter().flat_map(|w| w.to_le_bytes()).collect::<Vec<_>>();
+
+        let elf = ELFContent::parse( core(6, &desc) ).unwrap();
+        let core = elf.coredump.unwrap();
+
+        assert!(elf.errors.is_empty());
+        assert_eq!(core.auxv.len(), 1);
+        assert_eq!((core.auxv[0].atype, core.auxv[0].value), (6, 0x1000));
+    }
+
+    #[test]
+    fn malformed_process_status() {
+        // A thread status note too short for the registers.
+        let elf = ELFContent::parse( core(1, &[0; 4]) ).unwrap();
+
+        assert!(elf.coredump.is_none());
+        assert_eq!(elf.errors.len(), 1);
+        assert_eq!(elf.segments.len(), 1);
+    }
+}
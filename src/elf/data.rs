@@ -0,0 +1,44 @@
+//! Storage of the raw data of an ELF file.
+
+
+
+/// Raw data of an ELF file, either owned or memory mapped.
+pub enum ELFData {
+    /// Data read into memory.
+    Owned( Vec<u8> ),
+
+    /// Data mapped from a file.
+    #[cfg(feature = "mmap")]
+    Mapped( memmap2::Mmap ),
+}
+
+impl ELFData {
+    /// Maps the given file into memory.
+    ///
+    /// # Safety
+    /// The file must not be modified or truncated while it is mapped, as the
+    /// mapping would change under the borrowed views of its contents.
+    #[cfg(feature = "mmap")]
+    pub unsafe fn map(file: &std::fs::File) -> std::io::Result<Self> {
+        Ok( Self::Mapped( memmap2::Mmap::map(file)? ) )
+    }
+}
+
+impl core::ops::Deref for ELFData {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match self {
+            Self::Owned(data) => data,
+
+            #[cfg(feature = "mmap")]
+            Self::Mapped(map) => map,
+        }
+    }
+}
+
+impl core::convert::From<Vec<u8>> for ELFData {
+    fn from(data: Vec<u8>) -> Self {
+        Self::Owned(data)
+    }
+}
@@ -0,0 +1,66 @@
+//! Demangling of symbol names: Itanium C++ ABI and Rust legacy and v0 manglings.
+//! Enabled by the `demangle` feature.
+
+
+
+/// Demangles a symbol name, or returns `None` if it is not a mangled name (or the
+/// `demangle` feature is disabled). When `hash` is `false`, the hashes of Rust legacy
+/// names and the crate disambiguators of Rust v0 names are stripped.
+#[cfg_attr(not(feature = "demangle"), allow(unused_variables))]
+pub fn demangle(name: &str, hash: bool) -> Option<String> {
+    #[cfg(feature = "demangle")]
+    {
+        // Rust legacy names are also valid C++ names, so they are checked first.
+        if let Ok(demangled) = rustc_demangle::try_demangle(name) {
+            return match hash {
+                true => Some( format!("{}", demangled) ),
+                false => Some( format!("{:#}", demangled) ),
+            };
+        }
+
+        if let Ok(symbol) = cpp_demangle::Symbol::new(name) {
+            return symbol.demangle( &cpp_demangle::DemangleOptions::default() ).ok();
+        }
+    }
+
+    None
+}
+
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "demangle")]
+    fn rust() {
+        // Legacy mangling.
+        let legacy = "_ZN4core3fmt5write17h0123456789abcdefE";
+
+        assert_eq!(demangle(legacy, true).as_deref(), Some("core::fmt::write::h0123456789abcdef"));
+        assert_eq!(demangle(legacy, false).as_deref(), Some("core::fmt::write"));
+
+        // v0 mangling.
+        let v0 = "_RNvCs1234_7mycrate3foo";
+
+        assert_eq!(demangle(v0, true).as_deref(), Some("mycrate[3c1c0]::foo"));
+        assert_eq!(demangle(v0, false).as_deref(), Some("mycrate::foo"));
+    }
+
+    #[test]
+    #[cfg(feature = "demangle")]
+    fn cpp() {
+        assert_eq!(demangle("_Z3fooi", true).as_deref(), Some("foo(int)"));
+        assert_eq!(demangle("_ZN2ns5ClassC2ERKS0_", false).as_deref(), Some("ns::Class::Class(ns::Class const&)"));
+    }
+
+    #[test]
+    fn unmangled() {
+        assert_eq!(demangle("main", true), None);
+        assert_eq!(demangle("_Zfoo", true), None);
+
+        #[cfg(not(feature = "demangle"))]
+        assert_eq!(demangle("_Z3fooi", true), None);
+    }
+}
@@ -0,0 +1,120 @@
+//! ELF Dynamic section parsing and interaction.
+
+
+
+mod x32;
+mod x64;
+
+
+
+use crate::{
+    elf::{
+        common::{
+            DynamicTag,
+        },
+
+        traits::{
+            Rename,
+        },
+    },
+};
+
+
+
+/// Contains an entry of the dynamic section.
+pub struct ELFDynamic<T> {
+    /// Index of the entry in the dynamic section.
+    index: usize,
+
+    /// Tag of the entry.
+    tag: DynamicTag,
+
+    /// Value of the entry.
+    value: T,
+
+    /// String referenced by string-valued entries.
+    string: String,
+}
+
+impl<T: Copy + Into<u64>> Rename for ELFDynamic<T> {
+    fn strndx(&self) -> usize {
+        // Only string-valued entries reference the string table.
+        match self.tag.string() {
+            true => self.value.into() as usize,
+            false => 0,
+        }
+    }
+
+    fn setname(&mut self, name: String) {
+        self.string = name;
+    }
+}
+
+
+
+#[cfg(test)]
+mod test {
+    use crate::elf::{
+        common::DynamicTag,
+        traits::DynamicEntry,
+        ELFContent, ELFError,
+    };
+
+    /// Shared library linked with `-z now`. Built by `tests/fixtures/build.sh`.
+    const LIBRARY: &[u8] = include_bytes!("../../../tests/fixtures/libsample.so");
+
+    /// Returns the entry of the given tag.
+    fn entry(elf: &ELFContent, tag: DynamicTag) -> &dyn DynamicEntry {
+        elf.dynamic.iter().find(|e| e.tag() == tag).unwrap().as_ref()
+    }
+
+    #[test]
+    fn entries() {
+        let elf = ELFContent::parse(LIBRARY.to_vec()).unwrap();
+
+        // The terminator is not included.
+        assert_eq!(elf.dynamic.len(), 29);
+        assert!(elf.errors.is_empty());
+
+        assert_eq!(entry(&elf, DynamicTag::Needed).string(), Some("libc.so.6"));
+        assert_eq!(entry(&elf, DynamicTag::SOName).string(), Some("libsample.so.1"));
+        assert_eq!(entry(&elf, DynamicTag::VersionDefinitionNum).value(), 3);
+        assert_eq!(entry(&elf, DynamicTag::StringTable).string(), None);
+    }
+
+    #[test]
+    fn flags() {
+        let elf = ELFContent::parse(LIBRARY.to_vec()).unwrap();
+
+        let flags = entry(&elf, DynamicTag::Flags).flags().unwrap();
+        assert!(flags.bindnow() && !flags.textrel() && !flags.symbolic());
+        assert_eq!(flags.to_string(), "Bind now");
+
+        let flags = entry(&elf, DynamicTag::Flags1).flags1().unwrap();
+        assert!(flags.now() && !flags.pie() && !flags.nodelete());
+        assert_eq!(flags.to_string(), "Now");
+
+        // Only the flags entries are decoded.
+        assert!(entry(&elf, DynamicTag::Needed).flags().is_none());
+        assert!(entry(&elf, DynamicTag::Flags).flags1().is_none());
+        assert!(entry(&elf, DynamicTag::Flags1).to_string().contains("|- Value      : Now\n"));
+    }
+
+    #[test]
+    fn malformed() {
+        let mut raw = LIBRARY.to_vec();
+        let elf = ELFContent::parse(raw.clone()).unwrap();
+
+        // Link the dynamic section to a section which does not exist.
+        let shoff = u64::from_le_bytes( raw[0x28..0x30].try_into().unwrap() ) as usize;
+        let index = elf.find_section(".dynamic").unwrap();
+        let link = shoff + (index * 64) + 0x28;
+        raw[link..link+4].copy_from_slice(&0xFFu32.to_le_bytes());
+
+        let elf = ELFContent::parse(raw).unwrap();
+
+        assert!(elf.dynamic.is_empty());
+        assert!(matches!(elf.errors[..], [ELFError::BadSectionIndex { index: 0xFF, .. }]));
+        assert!(!elf.verdef.is_empty());
+    }
+}
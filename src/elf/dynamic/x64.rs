@@ -0,0 +1,111 @@
+//! 64-bit implementation of an ELF Dynamic entry.
+
+
+
+use byteorder::{
+    BigEndian, LittleEndian,
+    ByteOrder,
+};
+
+use crate::{
+    elf::{
+        common::{
+            DynamicTag, Endian,
+        },
+
+        traits::{
+            DynamicEntry,
+        },
+
+        ELFError,
+    },
+};
+
+use super::ELFDynamic;
+
+
+
+impl ELFDynamic<u64> {
+    /// Size of a 64-bit dynamic entry.
+    const SIZE: usize = 16;
+
+    /// Internal read function.
+    fn read<B: ByteOrder>(data: &[u8]) -> u64 {
+        B::read_u64( data )
+    }
+}
+
+impl DynamicEntry for ELFDynamic<u64> {
+    fn parse(chunk: &[u8], endian: Endian, index: usize) -> Result<Self, ELFError> {
+        // Check the entry is complete.
+        if chunk.len() < Self::SIZE {
+            return Err( ELFError::Truncated { offset: 0, expected: Self::SIZE, found: chunk.len() } );
+        }
+
+        // Get the read function.
+        let read: fn(&[u8]) -> u64 = match endian {
+            Endian::Little => Self::read::<LittleEndian>,
+            Endian::Big    => Self::read::<BigEndian>,
+        };
+
+        let s = core::mem::size_of::<u64>();
+
+        // Get the tag.
+        let tag = DynamicTag::from( read( &chunk[0..s] ) );
+
+        // Get the value.
+        let value = read( &chunk[s..2*s] );
+
+        Ok( Self {
+            index,
+            tag,
+            value,
+            string: String::new(),
+        })
+    }
+
+    fn entrysize() -> usize {
+        Self::SIZE
+    }
+
+    fn index(&self) -> usize {
+        self.index
+    }
+
+    fn tag(&self) -> DynamicTag {
+        self.tag
+    }
+
+    fn value(&self) -> u64 {
+        self.value
+    }
+
+    fn string(&self) -> Option<&str> {
+        match self.tag.string() {
+            true => Some( &self.string ),
+            false => None,
+        }
+    }
+}
+
+impl core::fmt::Display for ELFDynamic<u64> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        // Build the output string.
+        let mut string = String::from("ELF Dynamic Entry\n");
+
+        // Add entry information.
+        string += &format!("|- Entry index: {}\n", self.index);
+        string += &format!("|- Tag        : {}\n", self.tag);
+
+        match (self.flags(), self.flags1()) {
+            (Some(flags), _) => string += &format!("|- Value      : {}\n\n", flags),
+            (_, Some(flags)) => string += &format!("|- Value      : {}\n\n", flags),
+            _ => match self.tag.string() {
+                true => string += &format!("|- Value      : {}\n\n", self.string),
+                false => string += &format!("|- Value      : 0x{:016X}\n\n", self.value),
+            },
+        }
+
+        f.write_str(&string)
+    }
+}
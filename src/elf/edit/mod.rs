@@ -0,0 +1,421 @@
+//! Section level editing of ELF files.
+
+
+
+use crate::{
+    elf::{
+        common::{
+            SectionFlags, SectionType, SymbolSection,
+        },
+
+        traits::{
+            SectionHeader,
+        },
+
+        ELFContent, ELFError, ELFSection, ELFSymbolIndex,
+    },
+};
+
+
+
+impl ELFContent {
+    /// Returns the index of the first section with the given name.
+    pub fn find_section(&self, name: &str) -> Option<usize> {
+        self.sections.iter().position(|s| s.name() == name)
+    }
+
+    /// Adds a section with the given name, type, flags and contents at the end of the Section Header
+    /// Table and returns its index. The contents are placed in the file when it is written.
+    pub fn add_section(&mut self, name: &str, stype: SectionType, flags: u64, data: Vec<u8>) -> usize {
+        // Files without sections start with the null section.
+        if self.sections.is_empty() {
+            let null = self.header(0, SectionType::Null, 0);
+            self.sections.push( null );
+        }
+
+        let index = self.sections.len();
+
+        let mut section = self.header(index, stype, flags);
+        section.setname( String::from(name) );
+
+        self.sections.push( section );
+        self.edits.insert(index, data);
+
+        index
+    }
+
+    /// Removes a section, renumbering the following sections and updating the references to them:
+    /// section links and information, symbol sections, section group members and the index of the
+    /// String Section. References to the removed section are cleared, and the symbols defined
+    /// in it become undefined, so the symbol indices used by relocations do not change.
+    pub fn remove_section(&mut self, index: usize) -> Result<(), ELFError> {
+        self.editable(index)?;
+
+        // Get the new index of a section, or `None` for the removed section.
+        let renumber = move |i: usize| match i {
+            i if i == index => None,
+            i if i > index => Some( i - 1 ),
+            i => Some( i ),
+        };
+
+        self.sections.remove(index);
+
+        // Update the section headers.
+        for (i, section) in self.sections.iter_mut().enumerate() {
+            section.setindex(i);
+
+            if section.link() != 0 {
+                section.setlink( renumber( section.link() ).unwrap_or(0) );
+            }
+
+            // The information field only references a section in relocation tables and with the info link flag.
+            let infolink = section.flags().infolink() || matches!(section.stype(), SectionType::Relocation | SectionType::RelocationA);
+
+            if infolink && (section.info() != 0) {
+                section.setinfo( renumber( section.info() ).unwrap_or(0) );
+            }
+        }
+
+        // Update the edited contents and the index of the String Section.
+        self.edits = self.edits.drain()
+            .filter_map(|(i, data)| Some( (renumber(i)?, data) ))
+            .collect();
+
+        self.header.setshstrndx( renumber( self.header.shstrndx() ).unwrap_or(0) );
+
+        // Update the symbol tables and the sections of their symbols.
+        self.symbols.retain(|t| t.section != index);
+
+        for table in self.symbols.iter_mut() {
+            table.section = renumber(table.section).unwrap_or(0);
+            table.strtab = renumber(table.strtab).unwrap_or(0);
+
+            for symbol in table.symbols.iter_mut() {
+                if let SymbolSection::Index(i) = symbol.section() {
+                    match renumber(i) {
+                        None => symbol.setsection( SymbolSection::Undefined ),
+
+                        Some(n) if n != i => {
+                            symbol.setsection( SymbolSection::Index(n) );
+
+                            if let Some(section) = self.sections.get(n) {
+                                symbol.setsectionname( String::from( section.name() ) );
+                            }
+                        },
+
+                        _ => (),
+                    }
+                }
+            }
+        }
+
+        // Update the relocation tables, section groups and build attribute sections.
+        self.relocations.retain(|t| t.section != index);
+
+        for table in self.relocations.iter_mut() {
+            table.section = renumber(table.section).unwrap_or(0);
+            table.symtab = renumber(table.symtab).unwrap_or(0);
+            table.target = renumber(table.target).unwrap_or(0);
+        }
+
+        self.groups.retain(|g| g.section != index);
+
+        for group in self.groups.iter_mut() {
+            group.section = renumber(group.section).unwrap_or(0);
+            group.members = group.members.iter().filter_map(|m| renumber(*m)).collect();
+        }
+
+        self.attributes.retain(|a| a.section != index);
+
+        for attributes in self.attributes.iter_mut() {
+            attributes.section = renumber(attributes.section).unwrap_or(0);
+        }
+
+        // Index the remaining symbols.
+        self.symindex = ELFSymbolIndex::new(&self.symbols, &self.sections);
+
+        Ok(())
+    }
+
+    /// Renames a section. New names are added to the section name string table when the file is written.
+    pub fn rename_section(&mut self, index: usize, name: &str) -> Result<(), ELFError> {
+        self.editable(index)?.setname( String::from(name) );
+
+        // Rename the section of the symbols defined in it.
+        for symbol in self.symbols.iter_mut().flat_map(|t| t.symbols.iter_mut()) {
+            if symbol.section() == SymbolSection::Index(index) {
+                symbol.setsectionname( String::from(name) );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Sets the raw flags (`sh_flags`) of a section.
+    pub fn set_section_flags(&mut self, index: usize, flags: u64) -> Result<(), ELFError> {
+        let isa = self.header.isa();
+
+        self.editable(index)?.setflags( SectionFlags(flags, isa) );
+
+        Ok(())
+    }
+
+    /// Sets the address alignment of a section.
+    pub fn set_section_align(&mut self, index: usize, align: usize) -> Result<(), ELFError> {
+        self.editable(index)?.setalign(align);
+
+        Ok(())
+    }
+
+    /// Replaces the contents of a section, which may have a different size. The parsed symbols,
+    /// relocations and other tables are not updated, and replaced symbol tables and section groups
+    /// are written as given instead of being rebuilt.
+    pub fn update_section(&mut self, index: usize, data: Vec<u8>) -> Result<(), ELFError> {
+        let section = self.editable(index)?;
+
+        // Sections with no data in the file image.
+        if section.stype() == SectionType::Bss {
+            return Err( ELFError::BadSectionType { offset: section.phys().0, index } );
+        }
+
+        self.edits.insert(index, data);
+
+        Ok(())
+    }
+
+    /// Returns the section with the given index, which cannot be the null section.
+    fn editable(&mut self, index: usize) -> Result<&mut Box<dyn SectionHeader>, ELFError> {
+        let (offset, count) = (self.header.shtoffset(), self.sections.len());
+
+        match self.sections.get_mut(index) {
+            Some(section) if index != 0 => Ok( section ),
+            _ => Err( ELFError::BadSectionIndex { offset, index, count } ),
+        }
+    }
+
+    /// Creates the header of a new section of the word size of this file.
+    fn header(&self, index: usize, stype: SectionType, flags: u64) -> Box<dyn SectionHeader> {
+        let flags = SectionFlags(flags, self.header.isa());
+
+        match self.raw[0x04] {
+            2 => Box::new( ELFSection::<u64>::new(index, stype, flags) ),
+            _ => Box::new( ELFSection::<u32>::new(index, stype, flags) ),
+        }
+    }
+}
+
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::elf::{
+        fixture::{
+            Image, Section, Segment,
+        },
+
+        traits::Symbol,
+    };
+
+    /// 64-bit little endian relocatable object with a section group and relocations.
+    const OBJECT: &[u8] = include_bytes!("../../../tests/fixtures/elf64le.o");
+
+    /// Returns the symbol with the given name of the first symbol table.
+    fn symbol<'a>(elf: &'a ELFContent, name: &str) -> &'a dyn Symbol {
+        elf.symbols[0].symbols.iter().find(|s| s.name() == name).unwrap().as_ref()
+    }
+
+    /// Writes and parses again an edited file.
+    fn reparse(elf: &ELFContent) -> ELFContent {
+        ELFContent::parse( elf.write().unwrap() ).unwrap()
+    }
+
+    #[test]
+    fn remove_section() {
+        let mut elf = ELFContent::parse(OBJECT.to_vec()).unwrap();
+
+        // Remove `.bss`, which precedes the group member, the symbol table and the string tables.
+        elf.remove_section( elf.find_section(".bss").unwrap() ).unwrap();
+
+        let elf = reparse(&elf);
+        let (text, member, symtab) = (elf.find_section(".text").unwrap(), elf.find_section(".text.f").unwrap(), elf.find_section(".symtab").unwrap());
+
+        assert_eq!((member, symtab), (6, 8));
+        assert_eq!(elf.sections.len(), 11);
+        assert_eq!(elf.header.shstrndx(), elf.find_section(".shstrtab").unwrap());
+
+        // Links and information of the following sections.
+        let rela = &elf.sections[ elf.find_section(".rela.text").unwrap() ];
+
+        assert_eq!((rela.link(), rela.info()), (symtab, text));
+        assert_eq!(elf.sections[symtab].link(), elf.find_section(".strtab").unwrap());
+
+        // Group members and symbol sections.
+        assert_eq!(elf.groups[0].members, [member]);
+        assert_eq!(symbol(&elf, "f").section(), SymbolSection::Index(member));
+        assert_eq!(symbol(&elf, "main").section(), SymbolSection::Index(text));
+    }
+
+    #[test]
+    fn remove_referenced_section() {
+        let mut elf = ELFContent::parse(OBJECT.to_vec()).unwrap();
+
+        // The relocations of `.data` no longer target a section and its symbols become undefined.
+        elf.remove_section( elf.find_section(".data").unwrap() ).unwrap();
+
+        let elf = reparse(&elf);
+
+        assert_eq!(elf.sections[ elf.find_section(".rela.data").unwrap() ].info(), 0);
+        assert_eq!(symbol(&elf, "ptr").section(), SymbolSection::Undefined);
+        assert_eq!(symbol(&elf, "f").section(), SymbolSection::Index( elf.find_section(".text.f").unwrap() ));
+    }
+
+    #[test]
+    fn add_and_rename_section() {
+        let mut elf = ELFContent::parse(OBJECT.to_vec()).unwrap();
+
+        let index = elf.add_section(".comment", SectionType::ProgramData, 0, Vec::from(&b"\0moria\0"[..]));
+        elf.rename_section( elf.find_section(".text.f").unwrap(), ".text.g" ).unwrap();
+
+        let elf = reparse(&elf);
+        let section = &elf.sections[index];
+
+        assert_eq!(section.name(), ".comment");
+        assert_eq!(elf.section_data(section.as_ref()).unwrap(), b"\0moria\0");
+
+        // The renamed section keeps its index and contents.
+        assert_eq!(elf.find_section(".text.g"), Some(7));
+        assert_eq!(elf.find_section(".text.f"), None);
+        assert_eq!(elf.section_data(elf.sections[7].as_ref()).unwrap(), [0xC3]);
+        assert_eq!(elf.groups[0].members, [7]);
+        assert!(elf.errors.is_empty());
+    }
+
+    #[test]
+    fn update_section() {
+        let mut elf = ELFContent::parse(OBJECT.to_vec()).unwrap();
+        let text = elf.find_section(".text").unwrap();
+
+        // Grow `.text`, which moves the sections after it.
+        elf.update_section(text, vec![0x90; 0x40]).unwrap();
+
+        let elf = reparse(&elf);
+
+        assert_eq!(elf.section_data(elf.sections[text].as_ref()).unwrap(), [0x90; 0x40]);
+        assert_eq!(elf.section_data(elf.sections[7].as_ref()).unwrap(), [0xC3]);
+        assert_eq!(symbol(&elf, "main").section(), SymbolSection::Index(text));
+        assert_eq!(elf.relocations.len(), 2);
+        assert!(elf.errors.is_empty());
+    }
+
+    #[test]
+    fn update_dwarf() {
+        let mut elf = ELFContent::parse(OBJECT.to_vec()).unwrap();
+        let index = elf.find_section(".debug_str").unwrap();
+
+        // Replace the compressed strings with uncompressed ones.
+        elf.set_section_flags(index, 0x30).unwrap();
+        elf.update_section(index, Vec::from(&b"edited\0"[..])).unwrap();
+
+        let dwarf = elf.dwarf().unwrap();
+        let dwarf = dwarf.borrow(|section| gimli::EndianSlice::new(section, gimli::RunTimeEndian::Little));
+
+        assert_eq!(dwarf.debug_str.get_str( gimli::DebugStrOffset(0) ).unwrap().slice(), b"edited");
+    }
+
+    #[test]
+    fn update_relocated_section() {
+        use gimli::Section;
+
+        let mut elf = ELFContent::parse(include_bytes!("../../../tests/fixtures/debug64.o").to_vec()).unwrap();
+        let index = elf.find_section(".debug_info").unwrap();
+
+        // The relocations of the original contents are not applied to smaller or equally sized replacements.
+        for data in [Vec::new(), vec![0xAA; elf.sections[index].phys().1]] {
+            elf.update_section(index, data.clone()).unwrap();
+
+            let dwarf = elf.dwarf().unwrap();
+            let dwarf = dwarf.borrow(|section| gimli::EndianSlice::new(section, gimli::RunTimeEndian::Little));
+
+            assert_eq!(dwarf.debug_info.reader().slice(), data);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "zlib")]
+    fn update_compressed_section() {
+        let mut elf = ELFContent::parse(OBJECT.to_vec()).unwrap();
+        let index = elf.find_section(".debug_str").unwrap();
+
+        // Compressed `"edited\0"`.
+        let stream = [120, 218, 75, 77, 201, 44, 73, 77, 97, 0, 0, 10, 246, 2, 112];
+
+        // Replace the contents with a new compression header.
+        let mut data = vec![0u8; 24];
+        data[0..4].copy_from_slice(&1u32.to_le_bytes());
+        data[8..16].copy_from_slice(&7u64.to_le_bytes());
+        data[16..24].copy_from_slice(&1u64.to_le_bytes());
+        data.extend_from_slice(&stream);
+
+        elf.update_section(index, data).unwrap();
+
+        assert_eq!(elf.compression(elf.sections[index].as_ref()).unwrap().map(|h| h.size), Some(7));
+        assert_eq!(elf.section_contents(elf.sections[index].as_ref()).unwrap().as_ref(), b"edited\0");
+
+        let dwarf = elf.dwarf().unwrap();
+        let dwarf = dwarf.borrow(|section| gimli::EndianSlice::new(section, gimli::RunTimeEndian::Little));
+
+        assert_eq!(dwarf.debug_str.get_str( gimli::DebugStrOffset(0) ).unwrap().slice(), b"edited");
+
+        // Legacy compressed sections are recognised by their current name.
+        let mut data = Vec::from(&b"ZLIB\0\0\0\0\0\0\0\x07"[..]);
+        data.extend_from_slice(&stream);
+
+        elf.set_section_flags(index, 0x30).unwrap();
+        elf.update_section(index, data).unwrap();
+        elf.rename_section(index, ".zdebug_str").unwrap();
+
+        assert_eq!(elf.section_contents(elf.sections[index].as_ref()).unwrap().as_ref(), b"edited\0");
+
+        elf.rename_section(index, ".debug_str").unwrap();
+
+        assert!(elf.compression(elf.sections[index].as_ref()).unwrap().is_none());
+    }
+
+    #[test]
+    fn bad_alignment() {
+        for align in [3, 1 << 40] {
+            let mut elf = ELFContent::parse(OBJECT.to_vec()).unwrap();
+            let index = elf.find_section(".data").unwrap();
+
+            elf.set_section_align(index, align).unwrap();
+            elf.add_section(".comment", SectionType::ProgramData, 0, Vec::new());
+
+            assert_eq!(elf.write(), Err( ELFError::BadAlignment { offset: 0x50, index, align } ));
+        }
+    }
+
+    #[test]
+    fn unmovable_section() {
+        // 64-bit little endian executable with a loadable segment mapping `.text`.
+        let raw = Image::new(2, 0)
+            .segment( Segment { ptype: 1, vaddr: 0x1078, contents: &[0x90; 8], memsize: 8, ..Default::default() } )
+            .section( Section { name: ".text", stype: 1, flags: 6, segment: Some(0), ..Default::default() } )
+            .build();
+
+        let mut elf = ELFContent::parse(raw).unwrap();
+
+        // Mapped sections cannot grow.
+        elf.update_section(1, vec![0xCC; 16]).unwrap();
+        assert_eq!(elf.write(), Err( ELFError::UnmovableSection { offset: 0x78, index: 1 } ));
+
+        // But they can shrink in place.
+        elf.update_section(1, vec![0xCC; 4]).unwrap();
+
+        let elf = reparse(&elf);
+
+        assert_eq!(elf.sections[1].phys(), (0x78, 4));
+        assert_eq!(elf.section_data(elf.sections[1].as_ref()).unwrap(), [0xCC; 4]);
+    }
+}
@@ -2,6 +2,16 @@
 
 
 
+use crate::{
+    elf::{
+        common::{
+            CompressionType,
+        },
+    },
+};
+
+
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum ELFError {
     /// The ELF magic number was wrong.
@@ -9,6 +19,240 @@ pub enum ELFError {
 
     /// The pointer width flag indicates an unknown value.
     BadPointerWidth( u8 ),
+
+    /// The ELF version flag indicates an unknown version.
+    BadVersion {
+        /// File offset of the version flag.
+        offset: usize,
+
+        /// Version found.
+        version: u8,
+    },
+
+    /// The endianness flag indicates an unknown value.
+    BadEndian {
+        /// File offset of the endianness flag.
+        offset: usize,
+
+        /// Value found.
+        value: u8,
+    },
+
+    /// A header or entry is shorter than its format requires.
+    Truncated {
+        /// File offset of the header or entry.
+        offset: usize,
+
+        /// Number of bytes required.
+        expected: usize,
+
+        /// Number of bytes available.
+        found: usize,
+    },
+
+    /// A table or section extends past the end of the file.
+    OutOfBounds {
+        /// File offset of the table or section.
+        offset: usize,
+
+        /// Size of the table or section.
+        size: usize,
+
+        /// Size of the file.
+        filesize: usize,
+    },
+
+    /// The section header string table index does not reference a valid section.
+    BadStringTableIndex {
+        /// File offset of the index.
+        offset: usize,
+
+        /// Index found.
+        index: usize,
+
+        /// Number of sections in the file.
+        count: usize,
+    },
+
+    /// A section index does not reference a valid section.
+    BadSectionIndex {
+        /// File offset of the index or of the table it indexes.
+        offset: usize,
+
+        /// Index found.
+        index: usize,
+
+        /// Number of sections in the file.
+        count: usize,
+    },
+
+    /// A symbol index does not reference a valid symbol.
+    BadSymbolIndex {
+        /// File offset of the entry containing the index.
+        offset: usize,
+
+        /// Index found.
+        index: usize,
+
+        /// Number of symbols in the symbol table.
+        count: usize,
+    },
+
+    /// A section does not have the type required by the operation.
+    BadSectionType {
+        /// File offset of the section.
+        offset: usize,
+
+        /// Index of the section.
+        index: usize,
+    },
+
+    /// A string offset points past the end of its string table.
+    StringOutOfBounds {
+        /// File offset of the string table.
+        offset: usize,
+
+        /// Offset of the string inside the table.
+        strndx: usize,
+
+        /// Size of the string table.
+        size: usize,
+    },
+
+    /// A string is not valid UTF-8.
+    NonUTF8String {
+        /// File offset of the string table.
+        offset: usize,
+
+        /// Offset of the string inside the table.
+        strndx: usize,
+    },
+
+    /// A table declares an entry size different from the one of its format.
+    BadEntrySize {
+        /// File offset of the entry size declaration or of the table.
+        offset: usize,
+
+        /// Entry size required by the format.
+        expected: usize,
+
+        /// Entry size found.
+        found: usize,
+    },
+
+    /// A section is compressed with an algorithm that is unknown or not enabled.
+    UnsupportedCompression {
+        /// File offset of the compressed section.
+        offset: usize,
+
+        /// Compression algorithm of the section.
+        ctype: CompressionType,
+    },
+
+    /// A packed relocation table (Android APS2) is malformed.
+    BadPackedRelocations {
+        /// File offset of the relocation table.
+        offset: usize,
+    },
+
+    /// A build attribute section is malformed.
+    BadAttributes {
+        /// File offset of the malformed subsection or attribute group.
+        offset: usize,
+    },
+
+    /// A virtual address range is not mapped by any loadable segment or allocated section.
+    UnmappedAddress {
+        /// First unmapped virtual address.
+        address: usize,
+
+        /// Size of the unmapped range, up to the next mapped address or the end of the request.
+        size: usize,
+    },
+
+    /// A section grew past its place in the file image, but is mapped by a loadable
+    /// segment and cannot be moved without relinking the file.
+    UnmovableSection {
+        /// File offset of the section.
+        offset: usize,
+
+        /// Index of the section.
+        index: usize,
+    },
+
+    /// A section to be placed in the file has an alignment that is not a power of two or
+    /// is larger than the file.
+    BadAlignment {
+        /// File offset of the section.
+        offset: usize,
+
+        /// Index of the section.
+        index: usize,
+
+        /// Alignment of the section (`sh_addralign`).
+        align: usize,
+    },
+
+    /// The contents of a compressed section cannot be decompressed to the declared size.
+    BadCompressedData {
+        /// File offset of the compressed section.
+        offset: usize,
+
+        /// Decompressed size declared by the compression header.
+        size: usize,
+    },
+}
+
+impl ELFError {
+    /// Returns the file offset of the offending data, or the virtual address of unmapped memory.
+    pub fn offset(&self) -> usize {
+        use ELFError::*;
+
+        match *self {
+            BadMagic(_) => 0x00,
+            BadPointerWidth(_) => 0x04,
+
+            BadVersion { offset, .. } | BadEndian { offset, .. } |
+            Truncated { offset, .. } | OutOfBounds { offset, .. } |
+            BadStringTableIndex { offset, .. } | BadSectionIndex { offset, .. } |
+            BadSymbolIndex { offset, .. } | BadSectionType { offset, .. } |
+            UnmovableSection { offset, .. } | BadAlignment { offset, .. } |
+            StringOutOfBounds { offset, .. } | NonUTF8String { offset, .. } |
+            BadEntrySize { offset, .. } | UnsupportedCompression { offset, .. } |
+            BadCompressedData { offset, .. } | BadPackedRelocations { offset } |
+            BadAttributes { offset } => offset,
+
+            UnmappedAddress { address, .. } => address,
+        }
+    }
+
+    /// Moves the file offset of this error by the given base offset.
+    /// Used to convert offsets relative to a table into file offsets.
+    pub(crate) fn shift(self, base: usize) -> Self {
+        use ELFError::*;
+
+        match self {
+            BadVersion { offset, version } => BadVersion { offset: base + offset, version },
+            BadEndian { offset, value } => BadEndian { offset: base + offset, value },
+            Truncated { offset, expected, found } => Truncated { offset: base + offset, expected, found },
+            OutOfBounds { offset, size, filesize } => OutOfBounds { offset: base + offset, size, filesize },
+            BadStringTableIndex { offset, index, count } => BadStringTableIndex { offset: base + offset, index, count },
+            BadSectionIndex { offset, index, count } => BadSectionIndex { offset: base + offset, index, count },
+            BadSymbolIndex { offset, index, count } => BadSymbolIndex { offset: base + offset, index, count },
+            BadSectionType { offset, index } => BadSectionType { offset: base + offset, index },
+            UnmovableSection { offset, index } => UnmovableSection { offset: base + offset, index },
+            BadAlignment { offset, index, align } => BadAlignment { offset: base + offset, index, align },
+            NonUTF8String { offset, strndx } => NonUTF8String { offset: base + offset, strndx },
+            StringOutOfBounds { offset, strndx, size } => StringOutOfBounds { offset: base + offset, strndx, size },
+            BadEntrySize { offset, expected, found } => BadEntrySize { offset: base + offset, expected, found },
+            UnsupportedCompression { offset, ctype } => UnsupportedCompression { offset: base + offset, ctype },
+            BadCompressedData { offset, size } => BadCompressedData { offset: base + offset, size },
+            BadPackedRelocations { offset } => BadPackedRelocations { offset: base + offset },
+            BadAttributes { offset } => BadAttributes { offset: base + offset },
+
+            other => other,
+        }
+    }
 }
 
 impl core::fmt::Display for ELFError {
@@ -16,6 +260,24 @@ impl core::fmt::Display for ELFError {
         let string = match *self {
             Self::BadMagic( [a, b, c, d] ) => format!("Bad ELF magic number. Expected [0x7F, 0x45, 0x4C, 0x46], found [0x{:02X}, 0x{:02X}, 0x{:02X}, 0x{:02X}]", a, b, c, d),
             Self::BadPointerWidth( w ) => format!("bad pointer width flag. Expected 1 or 2, found {}", w),
+            Self::BadVersion { offset, version } => format!("bad ELF version at offset {:#X}. Expected 1, found {}", offset, version),
+            Self::BadEndian { offset, value } => format!("bad endianness flag at offset {:#X}. Expected 1 or 2, found {}", offset, value),
+            Self::Truncated { offset, expected, found } => format!("truncated entry at offset {:#X}. Expected {} bytes, found {}", offset, expected, found),
+            Self::OutOfBounds { offset, size, filesize } => format!("table at offset {:#X} with size {} extends past the end of the file ({} bytes)", offset, size, filesize),
+            Self::BadStringTableIndex { offset, index, count } => format!("bad section name string table index at offset {:#X}. Found {}, but there are {} sections", offset, index, count),
+            Self::BadSectionIndex { offset, index, count } => format!("bad section index at offset {:#X}. Found {}, but there are {} sections", offset, index, count),
+            Self::BadSymbolIndex { offset, index, count } => format!("bad symbol index at offset {:#X}. Found {}, but there are {} symbols", offset, index, count),
+            Self::BadSectionType { offset, index } => format!("section {} at offset {:#X} does not have the required type", index, offset),
+            Self::UnmovableSection { offset, index } => format!("section {} at offset {:#X} is mapped by a loadable segment and cannot grow", index, offset),
+            Self::BadAlignment { offset, index, align } => format!("section {} at offset {:#X} cannot be placed with an alignment of {}", index, offset, align),
+            Self::NonUTF8String { offset, strndx } => format!("string at offset {} of the string table at offset {:#X} is not valid UTF-8", strndx, offset),
+            Self::StringOutOfBounds { offset, strndx, size } => format!("string offset {} is past the end of the string table at offset {:#X} ({} bytes)", strndx, offset, size),
+            Self::BadEntrySize { offset, expected, found } => format!("bad entry size at offset {:#X}. Expected {}, found {}", offset, expected, found),
+            Self::UnsupportedCompression { offset, ctype } => format!("section at offset {:#X} is compressed with an unsupported algorithm: {}", offset, ctype),
+            Self::BadPackedRelocations { offset } => format!("malformed packed relocation table at offset {:#X}", offset),
+            Self::BadAttributes { offset } => format!("malformed build attribute section at offset {:#X}", offset),
+            Self::UnmappedAddress { address, size } => format!("virtual address range {:#X}..{:#X} is not mapped", address, address.saturating_add(size)),
+            Self::BadCompressedData { offset, size } => format!("compressed section at offset {:#X} does not decompress to {} bytes", offset, size),
         };
 
         f.write_str(&string)
@@ -23,3 +285,30 @@ impl core::fmt::Display for ELFError {
 }
 
 impl std::error::Error for ELFError {}
+
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn offset() {
+        assert_eq!(ELFError::BadMagic([0; 4]).offset(), 0x00);
+        assert_eq!(ELFError::BadPointerWidth(3).offset(), 0x04);
+        assert_eq!(ELFError::BadEndian { offset: 0x05, value: 3 }.offset(), 0x05);
+        assert_eq!(ELFError::StringOutOfBounds { offset: 0x200, strndx: 0x10, size: 0x08 }.offset(), 0x200);
+        assert_eq!(ELFError::UnmappedAddress { address: 0x4000, size: 0x10 }.offset(), 0x4000);
+    }
+
+    #[test]
+    fn shift() {
+        assert_eq!(ELFError::Truncated { offset: 0x08, expected: 0x10, found: 0x04 }.shift(0x100), ELFError::Truncated { offset: 0x108, expected: 0x10, found: 0x04 });
+        assert_eq!(ELFError::BadEntrySize { offset: 0, expected: 0x18, found: 0x10 }.shift(0x40), ELFError::BadEntrySize { offset: 0x40, expected: 0x18, found: 0x10 });
+
+        // Errors at fixed offsets or at virtual addresses are not moved.
+        assert_eq!(ELFError::BadMagic([0; 4]).shift(0x100), ELFError::BadMagic([0; 4]));
+        assert_eq!(ELFError::BadPointerWidth(3).shift(0x100).offset(), 0x04);
+        assert_eq!(ELFError::UnmappedAddress { address: 0x4000, size: 0x10 }.shift(0x100).offset(), 0x4000);
+    }
+}
@@ -7,10 +7,16 @@ mod x64;
 
 
 
+use byteorder::{
+    BigEndian, LittleEndian,
+    ByteOrder,
+};
+
 use crate::{
     elf::{
         common::{
             read::{
+                self,
                 big16, little16,
                 big32, little32,
             },
@@ -18,6 +24,8 @@ use crate::{
             ArchFlags, Endian, FileType,
             InstructionSet, OperatingSystem,
         },
+
+        ELFError,
     },
 };
 
@@ -50,13 +58,24 @@ pub struct ELFHeader<T> {
     entrysize: (u16, u16),
 
     /// Number of Program and Section Header entries.
-    num: (u16, u16),
+    /// Resolved from the first Section Header when the file uses extended numbering.
+    num: (usize, usize),
 
     /// Index of the Section Header of the string table.
-    shstrndx: u16,
+    /// Resolved from the first Section Header when the file uses extended numbering.
+    shstrndx: usize,
 }
 
-impl<T> ELFHeader<T> {
+impl<T: Copy + Into<u64>> ELFHeader<T> {
+    /// Escape value of `e_phnum` when the number of Program Headers is in the first Section Header.
+    const PN_XNUM: usize = 0xFFFF;
+
+    /// Escape value of `e_shstrndx` when the index is in the first Section Header.
+    const SHN_XINDEX: usize = 0xFFFF;
+
+    /// Start of the reserved range of section indices.
+    const SHN_LORESERVE: usize = 0xFF00;
+
     fn create(header: &[u8], endian: Endian, read: fn(&[u8]) -> T) -> Self {
         // Get the read functions.
         let (read16, read32): (fn(&[u8]) -> u16, fn(&[u8]) -> u32) = match endian {
@@ -93,7 +112,7 @@ impl<T> ELFHeader<T> {
         // Continue with common section of the file header.
 
         // Get the Architecture Flags.
-        let flags = ArchFlags::from( read32( &header[i..i+4] ) );
+        let flags = ArchFlags::from( (read32( &header[i..i+4] ), isa, s) );
         i += 4;
         i += 2;
 
@@ -107,14 +126,14 @@ impl<T> ELFHeader<T> {
 
         // Get the number of Program and Section Headers.
         let num = (
-            read16( &header[i+0..i+2] ),
-            read16( &header[i+4..i+6] ),
+            read16( &header[i+0..i+2] ) as usize,
+            read16( &header[i+4..i+6] ) as usize,
         );
 
         i+= 6;
 
         // Get the index of the string section.
-        let shstrndx = read16( &header[i..i+2] );
+        let shstrndx = read16( &header[i..i+2] ) as usize;
 
         Self {
             endian,
@@ -129,4 +148,201 @@ impl<T> ELFHeader<T> {
             shstrndx,
         }
     }
+
+    /// Checks the consistency of the table descriptions of the header and
+    /// resolves the extended numbering from the first Section Header.
+    fn check(self, raw: &[u8], read: fn(&[u8]) -> T, phsize: usize, shsize: usize) -> Result<Self, ELFError> {
+        // File offset of the table entry sizes.
+        let base = 0x18 + (3 * core::mem::size_of::<T>()) + 6;
+
+        // Check the Program Header size.
+        if (self.num.0 > 0) && (self.entrysize.0 as usize != phsize) {
+            return Err( ELFError::BadEntrySize { offset: base, expected: phsize, found: self.entrysize.0 as usize } );
+        }
+
+        // Check the Section Header size.
+        let shoffset = self.offset.1.into() as usize;
+
+        if ((self.num.1 > 0) || (shoffset != 0)) && (self.entrysize.1 as usize != shsize) {
+            return Err( ELFError::BadEntrySize { offset: base + 4, expected: shsize, found: self.entrysize.1 as usize } );
+        }
+
+        // Resolve the extended numbering.
+        let header = self.extend(raw, read)?;
+
+        // Check the index of the string section.
+        if (header.shstrndx > 0) && (header.shstrndx >= header.num.1) {
+            return Err( ELFError::BadStringTableIndex { offset: base + 8, index: header.shstrndx, count: header.num.1 } );
+        }
+
+        Ok( header )
+    }
+
+    /// Writes the table descriptions over the serialized header, escaping the values that do not fit.
+    /// Entry sizes are only written for tables with entries.
+    fn store(&self, header: &mut [u8], write: fn(&mut [u8], T), offset: (T, T), num: (usize, usize), shstrndx: usize, (phsize, shsize): (u16, u16)) {
+        // Get the write function.
+        let write16: fn(&mut [u8], u16) = match self.endian {
+            Endian::Little => LittleEndian::write_u16,
+            Endian::Big => BigEndian::write_u16,
+        };
+
+        // Write the Program and Section Header Tables offsets.
+        let s = core::mem::size_of::<T>();
+        let i = 0x18 + s;
+
+        write( &mut header[i..i+s], offset.0 );
+        write( &mut header[i+s..i+(2*s)], offset.1 );
+
+        // File offset of the table entry sizes.
+        let base = 0x18 + (3 * s) + 6;
+
+        // Write the Program and Section Header sizes.
+        if num.0 > 0 { write16( &mut header[base..base+2], phsize ); }
+        if num.1 > 0 { write16( &mut header[base+4..base+6], shsize ); }
+
+        // Write the number of Program and Section Headers and the index of the string section.
+        let phnum = match num.0 {
+            n if n >= Self::PN_XNUM => Self::PN_XNUM,
+            n => n,
+        };
+
+        let shnum = match num.1 {
+            n if n >= Self::SHN_LORESERVE => 0,
+            n => n,
+        };
+
+        let shstrndx = match shstrndx {
+            n if n >= Self::SHN_LORESERVE => Self::SHN_XINDEX,
+            n => n,
+        };
+
+        write16( &mut header[base+2..base+4], phnum as u16 );
+        write16( &mut header[base+6..base+8], shnum as u16 );
+        write16( &mut header[base+8..base+10], shstrndx as u16 );
+    }
+
+    /// Reads the real number of headers and string section index from the first
+    /// Section Header when they do not fit in the file header.
+    fn extend(mut self, raw: &[u8], read: fn(&[u8]) -> T) -> Result<Self, ELFError> {
+        // Check if any of the escape mechanisms is used.
+        let shoffset = self.offset.1.into() as usize;
+
+        let shnum = (self.num.1 == 0) && (shoffset != 0);
+        let phnum = self.num.0 == Self::PN_XNUM;
+        let shstrndx = self.shstrndx == Self::SHN_XINDEX;
+
+        if !(shnum || phnum || shstrndx) {
+            // Indices in the reserved range cannot reference a section.
+            if self.shstrndx >= Self::SHN_LORESERVE {
+                let base = 0x18 + (3 * core::mem::size_of::<T>()) + 6;
+
+                return Err( ELFError::BadStringTableIndex { offset: base + 8, index: self.shstrndx, count: self.num.1 } );
+            }
+
+            return Ok( self );
+        }
+
+        // Get the read function.
+        let read32: fn(&[u8]) -> u32 = match self.endian {
+            Endian::Little => little32,
+            Endian::Big => big32,
+        };
+
+        // Get the first Section Header.
+        let s = core::mem::size_of::<T>();
+        let first = read::slice(raw, shoffset, 0x10 + (6 * s))?;
+
+        // Get the size, link and info fields.
+        let size = read( &first[0x08 + (3 * s)..0x08 + (4 * s)] ).into() as usize;
+        let link = read32( &first[0x08 + (4 * s)..0x0C + (4 * s)] ) as usize;
+        let info = read32( &first[0x0C + (4 * s)..0x10 + (4 * s)] ) as usize;
+
+        if shnum     { self.num.1 = size; }
+        if phnum     { self.num.0 = info; }
+        if shstrndx  { self.shstrndx = link; }
+
+        Ok( self )
+    }
+}
+
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::elf::{
+        traits::FileHeader,
+        ELFFile,
+    };
+
+    /// Relocatable objects of both pointer widths and endiannesses. Built by `tests/fixtures/build.sh`.
+    const ELF32: &[u8] = include_bytes!("../../../tests/fixtures/elf32le.o");
+    const ELF64: &[u8] = include_bytes!("../../../tests/fixtures/elf64be.o");
+
+    /// Shared library with Program Headers. Built by `tests/fixtures/build.sh`.
+    const LIBRARY: &[u8] = include_bytes!("../../../tests/fixtures/libsample.so");
+
+    #[test]
+    fn truncated() {
+        assert_eq!(ELFHeader::<u32>::parse(&ELF32[..0x30]).err(), Some(ELFError::Truncated { offset: 0, expected: 0x34, found: 0x30 }));
+        assert_eq!(ELFHeader::<u64>::parse(&ELF64[..0x3F]).err(), Some(ELFError::Truncated { offset: 0, expected: 0x40, found: 0x3F }));
+
+        // The identification bytes are checked before the pointer width.
+        assert_eq!(ELFFile::parse(&ELF64[..0x08]).err(), Some(ELFError::Truncated { offset: 0, expected: 0x10, found: 0x08 }));
+    }
+
+    #[test]
+    fn identification() {
+        let mut raw = ELF64.to_vec();
+        raw[0x06] = 2;
+        assert_eq!(ELFHeader::<u64>::parse(&raw).err(), Some(ELFError::BadVersion { offset: 0x06, version: 2 }));
+
+        let mut raw = ELF32.to_vec();
+        raw[0x05] = 3;
+        assert_eq!(ELFHeader::<u32>::parse(&raw).err(), Some(ELFError::BadEndian { offset: 0x05, value: 3 }));
+
+        let mut raw = ELF32.to_vec();
+        raw[0x04] = 3;
+        assert_eq!(ELFFile::parse(&raw).err(), Some(ELFError::BadPointerWidth(3)));
+
+        let mut raw = ELF32.to_vec();
+        raw[0x00] = 0;
+        assert_eq!(ELFFile::parse(&raw).err(), Some(ELFError::BadMagic([0, 0x45, 0x4C, 0x46])));
+    }
+
+    #[test]
+    fn string_table_index() {
+        // One past the last section.
+        let mut raw = ELF32.to_vec();
+        raw[0x32..0x34].copy_from_slice(&12u16.to_le_bytes());
+        assert_eq!(ELFHeader::<u32>::parse(&raw).err(), Some(ELFError::BadStringTableIndex { offset: 0x32, index: 12, count: 12 }));
+
+        // Reserved index.
+        let mut raw = ELF64.to_vec();
+        raw[0x3E..0x40].copy_from_slice(&0xFF00u16.to_be_bytes());
+        assert_eq!(ELFHeader::<u64>::parse(&raw).err(), Some(ELFError::BadStringTableIndex { offset: 0x3E, index: 0xFF00, count: 12 }));
+    }
+
+    #[test]
+    fn entry_size() {
+        // Section Header size.
+        let mut raw = ELF32.to_vec();
+        raw[0x2E..0x30].copy_from_slice(&0x40u16.to_le_bytes());
+        assert_eq!(ELFHeader::<u32>::parse(&raw).err(), Some(ELFError::BadEntrySize { offset: 0x2E, expected: 0x28, found: 0x40 }));
+
+        let mut raw = ELF64.to_vec();
+        raw[0x3A..0x3C].copy_from_slice(&0x28u16.to_be_bytes());
+        assert_eq!(ELFHeader::<u64>::parse(&raw).err(), Some(ELFError::BadEntrySize { offset: 0x3A, expected: 0x40, found: 0x28 }));
+
+        // Program Header size, only checked in files with Program Headers.
+        let mut raw = LIBRARY.to_vec();
+        raw[0x36..0x38].copy_from_slice(&0x20u16.to_le_bytes());
+        assert_eq!(ELFHeader::<u64>::parse(&raw).err(), Some(ELFError::BadEntrySize { offset: 0x36, expected: 0x38, found: 0x20 }));
+
+        let mut raw = ELF64.to_vec();
+        raw[0x36..0x38].copy_from_slice(&0x20u16.to_be_bytes());
+        assert!(ELFHeader::<u64>::parse(&raw).is_ok());
+    }
 }
@@ -10,9 +10,11 @@ use byteorder::{
 use crate::{
     elf::{
         common::{
-            Endian,
+            ArchFlags, Endian, FileType, InstructionSet,
         },
 
+        ELFError,
+
         traits::{
             FileHeader,
         },
@@ -24,15 +26,20 @@ use super::ELFHeader;
 
 
 impl FileHeader for ELFHeader<u64> {
-    fn parse(header: &[u8]) -> Self {
+    fn parse(header: &[u8]) -> Result<Self, ELFError> {
+        // Check the header is complete.
+        if header.len() < Self::SIZE {
+            return Err( ELFError::Truncated { offset: 0, expected: Self::SIZE, found: header.len() } );
+        }
+
         // Check magic number.
         if header[0x00..0x04] != [0x7F, 0x45, 0x4C, 0x46] {
-            todo!("Gracefully return error")
+            return Err( ELFError::BadMagic( [header[0], header[1], header[2], header[3]] ) );
         }
 
         // Check ELF version.
         if header[0x06] != 1 {
-            todo!("Gracefully return error")
+            return Err( ELFError::BadVersion { offset: 0x06, version: header[0x06] } );
         }
 
         // Get endianness.
@@ -40,16 +47,28 @@ impl FileHeader for ELFHeader<u64> {
             1 => (Endian::Little, Self::read::<LittleEndian>),
             2 => (Endian::Big   , Self::read::<BigEndian>   ),
 
-            _ => todo!("Gracefully return error"),
+            value => return Err( ELFError::BadEndian { offset: 0x05, value } ),
         };
 
-        Self::create(header, endian, read)
+        Self::create(header, endian, read).check(header, read, Self::PHSIZE, Self::SHSIZE)
     }
 
     fn endian(&self) -> Endian {
         self.endian
     }
 
+    fn filetype(&self) -> FileType {
+        self.filetype
+    }
+
+    fn isa(&self) -> InstructionSet {
+        self.isa
+    }
+
+    fn flags(&self) -> ArchFlags {
+        self.flags
+    }
+
     fn phtoffset(&self) -> usize {
         self.offset.0 as usize
     }
@@ -67,15 +86,29 @@ impl FileHeader for ELFHeader<u64> {
     }
 
     fn phnum(&self) -> usize {
-        self.num.0 as usize
+        self.num.0
     }
 
     fn shnum(&self) -> usize {
-        self.num.1 as usize
+        self.num.1
     }
 
     fn shstrndx(&self) -> usize {
-        self.shstrndx as usize
+        self.shstrndx
+    }
+
+    fn setshstrndx(&mut self, index: usize) {
+        self.shstrndx = index;
+    }
+
+    fn write(&self, header: &mut [u8], (phoff, shoff): (usize, usize), num: (usize, usize), shstrndx: usize) {
+        // Get the write function.
+        let write: fn(&mut [u8], u64) = match self.endian {
+            Endian::Little => LittleEndian::write_u64,
+            Endian::Big => BigEndian::write_u64,
+        };
+
+        self.store(header, write, (phoff as u64, shoff as u64), num, shstrndx, (Self::PHSIZE as u16, Self::SHSIZE as u16));
     }
 }
 
@@ -117,6 +150,15 @@ impl core::fmt::Display for ELFHeader<u64> {
 }
 
 impl ELFHeader<u64> {
+    /// Size of a 64-bit file header.
+    const SIZE: usize = 0x40;
+
+    /// Size of a 64-bit Program Header.
+    const PHSIZE: usize = 0x38;
+
+    /// Size of a 64-bit Section Header.
+    const SHSIZE: usize = 0x40;
+
     /// Internal read function.
     fn read<B: ByteOrder>(data: &[u8]) -> u64 {
         B::read_u64( data )
@@ -0,0 +1,193 @@
+//! Builder of small ELF images for the tests.
+
+
+
+/// Program Header of a test image.
+#[derive(Default)]
+pub(crate) struct Segment<'a> {
+    /// Type of the segment (`p_type`).
+    pub ptype: u32,
+
+    /// Virtual address of the segment.
+    pub vaddr: u64,
+
+    /// Contents of the segment in the file.
+    pub contents: &'a [u8],
+
+    /// Size of the segment in memory.
+    pub memsize: u64,
+
+    /// Alignment of the segment.
+    pub align: u64,
+}
+
+/// Section Header of a test image.
+#[derive(Default)]
+pub(crate) struct Section<'a> {
+    /// Name of the section. A section name string table is added if any section is named.
+    pub name: &'a str,
+
+    /// Type of the section (`sh_type`).
+    pub stype: u32,
+
+    /// Flags of the section.
+    pub flags: u64,
+
+    /// Contents of the section in the file.
+    pub contents: &'a [u8],
+
+    /// Index of the segment whose contents are the contents of the section.
+    pub segment: Option<usize>,
+
+    /// Link and information fields of the section.
+    pub link: u32,
+    pub info: u32,
+
+    /// Size of the entries of the section.
+    pub entsize: u64,
+}
+
+/// 64-bit little endian ELF image. The contents of the segments follow the Program Header Table,
+/// then come the contents of the sections and the Section Header Table.
+pub(crate) struct Image<'a> {
+    /// Object file type and machine of the file header.
+    filetype: u16,
+    machine: u16,
+
+    /// Program Headers of the image.
+    segments: Vec<Segment<'a>>,
+
+    /// Section Headers of the image, without the NULL section.
+    sections: Vec<Section<'a>>,
+}
+
+impl<'a> Image<'a> {
+    /// Size of the file header.
+    const HEADER: usize = 0x40;
+
+    /// Size of a Program Header.
+    const PHSIZE: usize = 0x38;
+
+    /// Size of a Section Header.
+    const SHSIZE: usize = 0x40;
+
+    /// Creates an empty image of the given file type and machine.
+    pub fn new(filetype: u16, machine: u16) -> Self {
+        Self { filetype, machine, segments: Vec::new(), sections: Vec::new() }
+    }
+
+    /// Adds a Program Header.
+    pub fn segment(mut self, segment: Segment<'a>) -> Self {
+        self.segments.push( segment );
+        self
+    }
+
+    /// Adds a Section Header.
+    pub fn section(mut self, section: Section<'a>) -> Self {
+        self.sections.push( section );
+        self
+    }
+
+    /// Serializes the image.
+    pub fn build(self) -> Vec<u8> {
+        let mut raw = vec![0u8; Self::HEADER + (self.segments.len() * Self::PHSIZE)];
+
+        // File header.
+        raw[0x00..0x08].copy_from_slice(&[0x7F, 0x45, 0x4C, 0x46, 2, 1, 1, 0]);
+        raw[0x10..0x12].copy_from_slice(&self.filetype.to_le_bytes());
+        raw[0x12..0x14].copy_from_slice(&self.machine.to_le_bytes());
+        raw[0x14..0x18].copy_from_slice(&1u32.to_le_bytes());
+
+        // Program Headers, followed by their contents.
+        if !self.segments.is_empty() {
+            raw[0x20..0x28].copy_from_slice(&(Self::HEADER as u64).to_le_bytes());
+            raw[0x36..0x38].copy_from_slice(&(Self::PHSIZE as u16).to_le_bytes());
+            raw[0x38..0x3A].copy_from_slice(&(self.segments.len() as u16).to_le_bytes());
+        }
+
+        let mut segments = Vec::new();
+
+        for (i, segment) in self.segments.iter().enumerate() {
+            let base = Self::HEADER + (i * Self::PHSIZE);
+            let offset = raw.len();
+
+            raw[base..base+4].copy_from_slice(&segment.ptype.to_le_bytes());
+            raw[base+0x08..base+0x10].copy_from_slice(&(offset as u64).to_le_bytes());
+            raw[base+0x10..base+0x18].copy_from_slice(&segment.vaddr.to_le_bytes());
+            raw[base+0x20..base+0x28].copy_from_slice(&(segment.contents.len() as u64).to_le_bytes());
+            raw[base+0x28..base+0x30].copy_from_slice(&segment.memsize.to_le_bytes());
+            raw[base+0x30..base+0x38].copy_from_slice(&segment.align.to_le_bytes());
+
+            raw.extend_from_slice(segment.contents);
+            segments.push( (offset, segment.contents.len()) );
+        }
+
+        if self.sections.is_empty() {
+            return raw;
+        }
+
+        // Section names, with the name of the string table itself last.
+        let named = self.sections.iter().any(|s| !s.name.is_empty());
+        let mut shstrtab = vec![0u8];
+
+        let mut name = |name: &str| match name.is_empty() {
+            true => 0,
+            false => {
+                let strndx = shstrtab.len();
+                shstrtab.extend_from_slice(name.as_bytes());
+                shstrtab.push(0);
+                strndx as u32
+            },
+        };
+
+        let mut headers = self.sections.iter()
+            .map(|section| {
+                let (offset, size) = match section.segment {
+                    Some(i) => segments[i],
+                    _ => {
+                        raw.extend_from_slice(section.contents);
+                        (raw.len() - section.contents.len(), section.contents.len())
+                    },
+                };
+
+                (name(section.name), section.stype, section.flags, offset, size, section.link, section.info, section.entsize)
+            })
+            .collect::<Vec<_>>();
+
+        if named {
+            let strndx = name(".shstrtab");
+
+            headers.push( (strndx, 3, 0, raw.len(), shstrtab.len(), 0, 0, 0) );
+            raw.extend_from_slice(&shstrtab);
+        }
+
+        // Section Header Table, after the NULL section.
+        raw.resize((raw.len() + 7) & !7, 0);
+
+        let shoff = raw.len();
+        raw.resize(shoff + ((headers.len() + 1) * Self::SHSIZE), 0);
+
+        raw[0x28..0x30].copy_from_slice(&(shoff as u64).to_le_bytes());
+        raw[0x3A..0x3C].copy_from_slice(&(Self::SHSIZE as u16).to_le_bytes());
+        raw[0x3C..0x3E].copy_from_slice(&((headers.len() + 1) as u16).to_le_bytes());
+
+        if named {
+            raw[0x3E..0x40].copy_from_slice(&(headers.len() as u16).to_le_bytes());
+        }
+
+        for (i, (name, stype, flags, offset, size, link, info, entsize)) in headers.into_iter().enumerate() {
+            let base = shoff + ((i + 1) * Self::SHSIZE);
+
+            raw[base..base+4].copy_from_slice(&name.to_le_bytes());
+            raw[base+4..base+8].copy_from_slice(&stype.to_le_bytes());
+            raw[base+8..base+0x10].copy_from_slice(&flags.to_le_bytes());
+            raw[base+0x18..base+0x20].copy_from_slice(&(offset as u64).to_le_bytes());
+            raw[base+0x20..base+0x28].copy_from_slice(&(size as u64).to_le_bytes());
+            raw[base+0x28..base+0x2C].copy_from_slice(&link.to_le_bytes());
+            raw[base+0x2C..base+0x30].copy_from_slice(&info.to_le_bytes());
+            raw[base+0x38..base+0x40].copy_from_slice(&entsize.to_le_bytes());
+        }
+
+        raw
+    }
+}
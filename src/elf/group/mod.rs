@@ -0,0 +1,128 @@
+//! ELF Section Group parsing and interaction.
+
+
+
+use crate::{
+    elf::{
+        common::{
+            read::{
+                self,
+                big32, little32,
+            },
+
+            Endian,
+        },
+
+        ELFError,
+    },
+};
+
+
+
+/// Contains a section group (`SHT_GROUP`): a set of sections that must be kept
+/// or discarded together by the linker.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ELFSectionGroup {
+    /// Index of the group section.
+    pub section: usize,
+
+    /// Group flags.
+    pub flags: u32,
+
+    /// Name of the signature symbol that identifies the group.
+    pub signature: String,
+
+    /// Indices of the member sections.
+    pub members: Vec<usize>,
+}
+
+impl ELFSectionGroup {
+    /// COMDAT group flag: duplicates of the group in other files are discarded.
+    pub const COMDAT: u32 = 0x1;
+
+    /// Parses the flags and members of a group section.
+    pub(crate) fn parse(data: &[u8], offset: usize, endian: Endian, section: usize, signature: String) -> Result<Self, ELFError> {
+        // Get the read function.
+        let read32: fn(&[u8]) -> u32 = match endian {
+            Endian::Little => little32,
+            Endian::Big => big32,
+        };
+
+        // Get the flags.
+        let flags = read32( read::slice(data, 0, 4).map_err(|e| e.shift(offset))? );
+
+        // Get the members.
+        let members = data[4..].chunks_exact(4)
+            .map(|b| read32(b) as usize)
+            .collect();
+
+        Ok( Self { section, flags, signature, members } )
+    }
+
+    /// Returns `true` if this is a COMDAT group.
+    pub fn comdat(&self) -> bool {
+        (self.flags & Self::COMDAT) != 0
+    }
+}
+
+impl core::fmt::Display for ELFSectionGroup {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        // Build the output string.
+        let mut string = String::from("ELF Section Group\n");
+
+        // Add group information.
+        string += &format!("|- Section  : {}\n", self.section);
+        string += &format!("|- Signature: {}\n", self.signature);
+        string += &format!("|- Flags    : {:#X}{}\n", self.flags, if self.comdat() { " (COMDAT)" } else { "" });
+        string += &format!("|- Members  : {}\n\n", self.members.iter().map(|m| format!("{}", m)).collect::<Vec<_>>().join(", "));
+
+        f.write_str(&string)
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::elf::{
+        fixture::{
+            Image, Section,
+        },
+
+        ELFContent,
+    };
+
+    #[test]
+    fn parse() {
+        let data = [0, 0, 0, 1,  0, 0, 0, 4,  0, 0, 0, 5];
+
+        let group = ELFSectionGroup::parse(&data, 0x40, Endian::Big, 3, String::from("f")).unwrap();
+
+        assert!(group.comdat());
+        assert_eq!(group.members, [4, 5]);
+        assert_eq!((group.section, group.signature.as_str()), (3, "f"));
+    }
+
+    #[test]
+    fn truncated() {
+        assert!(matches!(ELFSectionGroup::parse(&[1, 0], 0x40, Endian::Little, 3, String::new()), Err(ELFError::OutOfBounds { offset: 0x40, .. })));
+    }
+
+    #[test]
+    fn malformed_group() {
+        // 64-bit little endian relocatable file with a group section linked to a missing symbol table.
+        let raw = Image::new(1, 0)
+            .section( Section { stype: 17, contents: &[0; 8], link: 7, entsize: 4, ..Default::default() } )
+            .build();
+
+        let elf = ELFContent::parse(raw.clone()).unwrap();
+
+        assert_eq!(elf.sections.len(), 2);
+        assert!(elf.groups.is_empty());
+        assert!(matches!(elf.errors[..], [ELFError::BadSectionIndex { index: 7, .. }]));
+
+        // The group is written back from its contents.
+        assert_eq!(elf.write().unwrap(), raw);
+    }
+}
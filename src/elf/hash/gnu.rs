@@ -0,0 +1,235 @@
+//! GNU symbol hash table (`.gnu.hash`).
+
+
+
+use crate::{
+    elf::{
+        common::{
+            read::{
+                big32, little32,
+                big64, little64,
+            },
+
+            Endian, SymbolSection,
+        },
+
+        traits::{
+            Symbol,
+        },
+
+        ELFError, StringTable,
+    },
+};
+
+
+
+/// Contains a GNU symbol hash table.
+pub struct ELFGNUHash {
+    /// Index of the symbol table the hash table indexes (`sh_link`).
+    pub symtab: usize,
+
+    /// Index of the first symbol in the hash table.
+    pub symoffset: u32,
+
+    /// Shift of the second bloom filter hash.
+    pub bloomshift: u32,
+
+    /// Number of bits of a bloom filter word (32 or 64).
+    pub bloombits: u32,
+
+    /// Bloom filter words.
+    pub bloom: Vec<u64>,
+
+    /// First symbol of each bucket.
+    pub buckets: Vec<u32>,
+
+    /// Hash of each symbol from `symoffset`, with the lowest bit marking the end of a chain.
+    pub chains: Vec<u32>,
+}
+
+impl ELFGNUHash {
+    /// Parses a GNU hash table from the contents of its section.
+    pub(crate) fn parse(data: &[u8], offset: usize, endian: Endian, word: usize, symtab: usize) -> Result<Self, ELFError> {
+        // Get the read functions.
+        let read32: fn(&[u8]) -> u32 = match endian {
+            Endian::Little => little32,
+            Endian::Big => big32,
+        };
+
+        // Bloom filter words have the size of a word of the file.
+        let readword: fn(&[u8]) -> u64 = match (endian, word) {
+            (Endian::Little, 4) => |b| little32(b) as u64,
+            (Endian::Big, 4) => |b| big32(b) as u64,
+            (Endian::Little, _) => little64,
+            (Endian::Big, _) => big64,
+        };
+
+        // Check the header is complete.
+        if data.len() < 16 {
+            return Err( ELFError::Truncated { offset, expected: 16, found: data.len() } );
+        }
+
+        let nbuckets   = read32( &data[0..4] ) as usize;
+        let symoffset  = read32( &data[4..8] );
+        let nbloom     = read32( &data[8..12] ) as usize;
+        let bloomshift = read32( &data[12..16] );
+
+        // Check the bloom filter and buckets are complete.
+        let buckets = nbloom.saturating_mul(word).saturating_add(16);
+        let chains = nbuckets.saturating_mul(4).saturating_add(buckets);
+
+        if data.len() < chains {
+            return Err( ELFError::Truncated { offset, expected: chains, found: data.len() } );
+        }
+
+        Ok( Self {
+            symtab,
+            symoffset,
+            bloomshift,
+            bloombits: (word * 8) as u32,
+            bloom: data[16..buckets].chunks_exact(word).map(readword).collect(),
+            buckets: data[buckets..chains].chunks_exact(4).map(read32).collect(),
+            chains: data[chains..].chunks_exact(4).map(read32).collect(),
+        })
+    }
+
+    /// GNU hash function of a symbol name.
+    pub fn hash(name: &[u8]) -> u32 {
+        name.iter().fold(5381u32, |h, c| h.wrapping_mul(33).wrapping_add(*c as u32))
+    }
+
+    /// Finds the index of the defined symbol with the given name in the indexed symbol table.
+    /// Names are compared with the strings of the given string table of the symbol names,
+    /// so unnamed symbols do not match the placeholder names given to them.
+    pub fn lookup(&self, name: &[u8], symbols: &[Box<dyn Symbol>], strtab: &StringTable) -> Option<usize> {
+        if self.buckets.is_empty() || self.bloom.is_empty() || (self.bloombits == 0) {
+            return None;
+        }
+
+        let hash = Self::hash(name);
+
+        // Check the bloom filter.
+        let bits = self.bloombits;
+        let word = self.bloom[((hash / bits) as usize) % self.bloom.len()];
+        let mask = (1u64 << (hash % bits)) | (1u64 << ((hash >> (self.bloomshift % 32)) % bits));
+
+        if (word & mask) != mask {
+            return None;
+        }
+
+        // Get the first symbol of the bucket.
+        let mut i = self.buckets[(hash as usize) % self.buckets.len()] as usize;
+
+        if i < (self.symoffset as usize) {
+            return None;
+        }
+
+        // Walk the chain until its end marker.
+        loop {
+            let chain = *self.chains.get(i - (self.symoffset as usize))?;
+
+            // Skip undefined symbols, which only reference a definition elsewhere.
+            if (chain | 1) == (hash | 1) {
+                let symbol = symbols.get(i)?;
+
+                if (symbol.section() != SymbolSection::Undefined) && (strtab.get( symbol.strndx() ) == Ok(name)) {
+                    return Some( i );
+                }
+            }
+
+            if (chain & 1) != 0 {
+                return None;
+            }
+
+            i += 1;
+        }
+    }
+}
+
+impl core::fmt::Display for ELFGNUHash {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        // Build the output string.
+        let mut string = String::from("ELF GNU Symbol Hash Table\n");
+
+        // Add table information.
+        string += &format!("|- Symbol table : {}\n", self.symtab);
+        string += &format!("|- Symbol offset: {}\n", self.symoffset);
+        string += &format!("|- Bloom filter : {} x {} bit (shift {})\n", self.bloom.len(), self.bloombits, self.bloomshift);
+        string += &format!("|- Buckets      : {}\n", self.buckets.len());
+        string += &format!("|- Chains       : {}\n\n", self.chains.len());
+
+        f.write_str(&string)
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::elf::{
+        traits::Rename,
+        ELFSymbol,
+    };
+
+    /// Builds a 64-bit big endian symbol with the given name offset and section index.
+    fn symbol(strndx: u32, shndx: u16, name: &str) -> Box<dyn Symbol> {
+        let mut chunk = [0u8; 24];
+
+        chunk[0..4].copy_from_slice(&strndx.to_be_bytes());
+        chunk[4] = 0x12;
+        chunk[6..8].copy_from_slice(&shndx.to_be_bytes());
+
+        let mut symbol = ELFSymbol::<u64>::parse(&chunk, Endian::Big).unwrap();
+        symbol.setname( String::from(name) );
+
+        Box::new( symbol )
+    }
+
+    #[test]
+    fn hash() {
+        assert_eq!(ELFGNUHash::hash(b""), 5381);
+        assert_eq!(ELFGNUHash::hash(b"printf"), 0x156B2BB8);
+    }
+
+    #[test]
+    fn lookup() {
+        let strtab = StringTable::new(b"\0foo\0bar\0", 0);
+
+        // Unnamed symbols are given a placeholder name when they are loaded.
+        let symbols = [symbol(0, 0, "NULL"), symbol(1, 1, "foo"), symbol(0, 1, "NULL"), symbol(5, 0, "bar")];
+
+        // One bucket starting at symbol 1, a full bloom filter and the chain hashes of symbols 1 to 3.
+        let hash = |name: &[u8]| ELFGNUHash::hash(name) & !1;
+
+        let mut data = [1u32, 1, 1, 6].iter().flat_map(|w| w.to_be_bytes()).collect::<Vec<_>>();
+        data.extend_from_slice(&u64::MAX.to_be_bytes());
+        data.extend( [1u32, hash(b"foo"), hash(b"NULL"), hash(b"bar") | 1].iter().flat_map(|w| w.to_be_bytes()) );
+
+        let table = ELFGNUHash::parse(&data, 0, Endian::Big, 8, 1).unwrap();
+
+        assert_eq!((table.symoffset, table.bloombits, table.bloomshift), (1, 64, 6));
+        assert_eq!(table.buckets, [1]);
+        assert_eq!(table.chains.len(), 3);
+
+        assert_eq!(table.lookup(b"foo", &symbols, &strtab), Some(1));
+        assert_eq!(table.lookup(b"NULL", &symbols, &strtab), None);
+        assert_eq!(table.lookup(b"bar", &symbols, &strtab), None);
+        assert_eq!(table.lookup(b"baz", &symbols, &strtab), None);
+    }
+
+    #[test]
+    fn bloom_filter() {
+        let strtab = StringTable::new(b"\0foo\0", 0);
+        let symbols = [symbol(0, 0, "NULL"), symbol(1, 1, "foo")];
+
+        // An empty bloom filter rejects every name before the buckets are read.
+        let mut data = [1u32, 1, 1, 6].iter().flat_map(|w| w.to_be_bytes()).collect::<Vec<_>>();
+        data.extend_from_slice(&0u64.to_be_bytes());
+        data.extend( [1u32, ELFGNUHash::hash(b"foo") | 1].iter().flat_map(|w| w.to_be_bytes()) );
+
+        let table = ELFGNUHash::parse(&data, 0, Endian::Big, 8, 1).unwrap();
+
+        assert_eq!(table.lookup(b"foo", &symbols, &strtab), None);
+    }
+}
@@ -0,0 +1,58 @@
+//! Symbol hash tables parsing and lookup.
+
+
+
+mod gnu;
+mod sysv;
+
+
+
+pub use gnu::ELFGNUHash;
+pub use sysv::ELFSysVHash;
+
+use crate::{
+    elf::{
+        common::{
+            SectionType,
+        },
+    },
+};
+
+
+
+/// A disagreement between a hash table and the symbol table it indexes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum HashMismatch {
+    /// The hash table does not cover the same number of symbols as the symbol table.
+    Count {
+        /// Type of the hash table (`Hash` or `GNUHash`).
+        table: SectionType,
+
+        /// Number of symbols in the symbol table.
+        expected: usize,
+
+        /// Number of symbols covered by the hash table.
+        found: usize,
+    },
+
+    /// A named symbol cannot be found through the hash table.
+    Missing {
+        /// Type of the hash table (`Hash` or `GNUHash`).
+        table: SectionType,
+
+        /// Index of the symbol in the symbol table.
+        symbol: usize,
+
+        /// Name of the symbol.
+        name: String,
+    },
+}
+
+impl core::fmt::Display for HashMismatch {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            Self::Count { table, expected, found } => write!(f, "{}: covers {} symbols, symbol table has {}", table, found, expected),
+            Self::Missing { table, symbol, name } => write!(f, "{}: symbol {} ({}) is not found", table, symbol, name),
+        }
+    }
+}
@@ -0,0 +1,194 @@
+//! SysV symbol hash table (`.hash`).
+
+
+
+use crate::{
+    elf::{
+        common::{
+            read::{
+                big32, little32,
+            },
+
+            Endian, SymbolSection,
+        },
+
+        traits::{
+            Symbol,
+        },
+
+        ELFError, StringTable,
+    },
+};
+
+
+
+/// Contains a SysV symbol hash table.
+pub struct ELFSysVHash {
+    /// Index of the symbol table the hash table indexes (`sh_link`).
+    pub symtab: usize,
+
+    /// Chain start of each bucket.
+    pub buckets: Vec<u32>,
+
+    /// Next symbol in the chain of each symbol.
+    pub chains: Vec<u32>,
+}
+
+impl ELFSysVHash {
+    /// Parses a SysV hash table from the contents of its section.
+    pub(crate) fn parse(data: &[u8], offset: usize, endian: Endian, symtab: usize) -> Result<Self, ELFError> {
+        // Get the read function.
+        let read32: fn(&[u8]) -> u32 = match endian {
+            Endian::Little => little32,
+            Endian::Big => big32,
+        };
+
+        // Check the header is complete.
+        if data.len() < 8 {
+            return Err( ELFError::Truncated { offset, expected: 8, found: data.len() } );
+        }
+
+        let nbuckets = read32( &data[0..4] ) as usize;
+        let nchains = read32( &data[4..8] ) as usize;
+
+        // Check the table is complete.
+        let expected = nbuckets.saturating_add(nchains).saturating_mul(4).saturating_add(8);
+
+        if data.len() < expected {
+            return Err( ELFError::Truncated { offset, expected, found: data.len() } );
+        }
+
+        let mut words = data[8..expected].chunks_exact(4).map(read32);
+
+        Ok( Self {
+            symtab,
+            buckets: words.by_ref().take(nbuckets).collect(),
+            chains: words.collect(),
+        })
+    }
+
+    /// SysV hash function of a symbol name.
+    pub fn hash(name: &[u8]) -> u32 {
+        name.iter().fold(0u32, |h, c| {
+            let h = (h << 4).wrapping_add(*c as u32);
+            let g = h & 0xF0000000;
+
+            (h ^ (g >> 24)) & !g
+        })
+    }
+
+    /// Finds the index of the defined symbol with the given name in the indexed symbol table.
+    /// Names are compared with the strings of the given string table of the symbol names,
+    /// so unnamed symbols do not match the placeholder names given to them.
+    pub fn lookup(&self, name: &[u8], symbols: &[Box<dyn Symbol>], strtab: &StringTable) -> Option<usize> {
+        if self.buckets.is_empty() {
+            return None;
+        }
+
+        let hash = Self::hash(name);
+
+        // Follow the chain of the bucket. Bound the walk to survive cyclic chains.
+        let mut i = self.buckets[(hash as usize) % self.buckets.len()] as usize;
+
+        for _ in 0..self.chains.len() {
+            if i == 0 {
+                break;
+            }
+
+            // Skip undefined symbols, which only reference a definition elsewhere.
+            let symbol = symbols.get(i)?;
+
+            if (symbol.section() != SymbolSection::Undefined) && (strtab.get( symbol.strndx() ) == Ok(name)) {
+                return Some( i );
+            }
+
+            i = *self.chains.get(i)? as usize;
+        }
+
+        None
+    }
+}
+
+impl core::fmt::Display for ELFSysVHash {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        // Build the output string.
+        let mut string = String::from("ELF Symbol Hash Table\n");
+
+        // Add table information.
+        string += &format!("|- Symbol table: {}\n", self.symtab);
+        string += &format!("|- Buckets     : {}\n", self.buckets.len());
+        string += &format!("|- Chains      : {}\n\n", self.chains.len());
+
+        f.write_str(&string)
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::elf::{
+        traits::Rename,
+        ELFSymbol,
+    };
+
+    /// Builds a 64-bit little endian symbol with the given name offset and section index.
+    fn symbol(strndx: u32, shndx: u16, name: &str) -> Box<dyn Symbol> {
+        let mut chunk = [0u8; 24];
+
+        chunk[0..4].copy_from_slice(&strndx.to_le_bytes());
+        chunk[4] = 0x12;
+        chunk[6..8].copy_from_slice(&shndx.to_le_bytes());
+
+        let mut symbol = ELFSymbol::<u64>::parse(&chunk, Endian::Little).unwrap();
+        symbol.setname( String::from(name) );
+
+        Box::new( symbol )
+    }
+
+    #[test]
+    fn hash() {
+        assert_eq!(ELFSysVHash::hash(b""), 0);
+        assert_eq!(ELFSysVHash::hash(b"printf"), 0x077905A6);
+    }
+
+    #[test]
+    fn lookup() {
+        let strtab = StringTable::new(b"\0foo\0bar\0", 0);
+
+        // Unnamed symbols are given a placeholder name when they are loaded.
+        let symbols = [symbol(0, 0, "NULL"), symbol(1, 1, "foo"), symbol(0, 1, "NULL"), symbol(5, 0, "bar")];
+
+        // A single bucket chaining all the symbols: 3 -> 2 -> 1.
+        let data = [1u32, 4, 3, 0, 0, 1, 2].iter().flat_map(|w| w.to_le_bytes()).collect::<Vec<_>>();
+        let table = ELFSysVHash::parse(&data, 0, Endian::Little, 1).unwrap();
+
+        assert_eq!(table.buckets, [3]);
+        assert_eq!(table.chains, [0, 0, 1, 2]);
+
+        assert_eq!(table.lookup(b"foo", &symbols, &strtab), Some(1));
+        assert_eq!(table.lookup(b"NULL", &symbols, &strtab), None);
+        assert_eq!(table.lookup(b"bar", &symbols, &strtab), None);
+        assert_eq!(table.lookup(b"baz", &symbols, &strtab), None);
+    }
+
+    #[test]
+    fn cyclic_chain() {
+        let strtab = StringTable::new(b"\0foo\0", 0);
+        let symbols = [symbol(0, 0, "NULL"), symbol(1, 1, "foo"), symbol(1, 1, "foo")];
+
+        // Symbol 2 chains back to itself.
+        let data = [1u32, 3, 2, 0, 0, 2].iter().flat_map(|w| w.to_le_bytes()).collect::<Vec<_>>();
+        let table = ELFSysVHash::parse(&data, 0, Endian::Little, 1).unwrap();
+
+        assert_eq!(table.lookup(b"bar", &symbols, &strtab), None);
+    }
+
+    #[test]
+    fn truncated() {
+        let data = [4u32, 4, 0].iter().flat_map(|w| w.to_le_bytes()).collect::<Vec<_>>();
+
+        assert!(matches!(ELFSysVHash::parse(&data, 0x80, Endian::Little, 1), Err(ELFError::Truncated { offset: 0x80, expected: 40, found: 12 })));
+    }
+}
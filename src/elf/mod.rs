@@ -2,17 +2,60 @@
 
 
 
+mod attributes;
 pub mod common;
+mod compression;
+mod coredump;
+mod data;
+mod demangle;
+mod dynamic;
+mod edit;
 mod error;
 mod file;
+#[cfg(test)]
+mod fixture;
+mod group;
+mod hash;
+mod note;
+mod relocation;
 mod section;
+mod segment;
 mod symbol;
 pub mod traits;
+mod version;
+mod view;
+mod writer;
 
 
 
 use common::{
-    SectionType,
+    read,
+    DynamicTag, FileType, SectionType, SegmentType, SymbolSection,
+};
+
+pub use attributes::{
+    ARMAttributeTag, AttributeScope, AttributeTag, AttributeValue, ELFAttribute,
+    ELFAttributeGroup, ELFAttributeSubsection, ELFAttributes, RISCVAttributeTag,
+};
+
+pub use compression::{
+    CompressionHeader,
+};
+
+pub use coredump::{
+    ELFCore,
+};
+
+pub use data::{
+    ELFData,
+};
+
+pub use demangle::{
+    demangle,
+};
+
+pub use dynamic::{
+    ELFDynamic,
 };
 
 pub use error::{
@@ -23,21 +66,56 @@ pub use file::{
     ELFHeader,
 };
 
+pub use group::{
+    ELFSectionGroup,
+};
+
+pub use hash::{
+    ELFGNUHash, ELFSysVHash, HashMismatch,
+};
+
+pub use note::{
+    ABITag, Aarch64Features, ELFNote, GNUProperty, NoteData, Notes, X86Features,
+    AMD64Registers, Aarch64Registers, AuxEntry, MappedFile,
+    ProcessInfo, ProcessStatus, Registers, SignalInfo,
+};
+
+pub use relocation::{
+    ELFRelocation, ELFRelocationTable,
+};
+
 pub use section::{
     ELFSection,
 };
 
+pub use segment::{
+    ELFSegment,
+};
+
 pub use symbol::{
-    ELFSymbol,
+    ELFSymbol, ELFSymbolIndex, ELFSymbolTable,
+};
+
+pub use version::{
+    ELFVersionDefinition, ELFVersionNeeded, ELFVersionRequirement, SymbolVersion,
+};
+
+pub use view::{
+    AnyDynamic, AnyRelocation, AnySection, AnySegment, AnySymbol,
+    ELFFile, Entries, Entry, SectionIndices, StringTable,
 };
 
 use std::{
-    error::{
-        Error,
+    borrow::{
+        Cow,
     },
 
-    ffi::{
-        CStr,
+    collections::{
+        HashMap,
+    },
+
+    error::{
+        Error,
     },
 
     fs::{
@@ -57,174 +135,600 @@ use std::{
 
 pub struct ELFContent {
     /// Raw ELF data.
-    pub raw: Vec<u8>,
+    pub raw: ELFData,
 
     /// File header of the ELF.
     pub header: Box<dyn traits::FileHeader>,
 
+    /// List of all segments in this ELF file.
+    pub segments: Vec<Box<dyn traits::ProgramHeader>>,
+
     /// List of all sections in this ELF file.
     pub sections: Vec<Box<dyn traits::SectionHeader>>,
 
-    /// List of all symbols in this ELF file.
-    pub symbols: Vec<Box<dyn traits::Symbol>>,
+    /// List of all symbol tables in this ELF file, each with its own symbols.
+    pub symbols: Vec<ELFSymbolTable>,
+
+    /// Address to symbol index of the function and object symbols of this ELF file.
+    pub symindex: ELFSymbolIndex,
+
+    /// List of all relocation tables in this ELF file.
+    pub relocations: Vec<ELFRelocationTable>,
+
+    /// List of all section groups of this ELF file.
+    pub groups: Vec<ELFSectionGroup>,
+
+    /// List of all build attribute sections of this ELF file.
+    pub attributes: Vec<ELFAttributes>,
+
+    /// List of all entries of the dynamic section of this ELF file.
+    pub dynamic: Vec<Box<dyn traits::DynamicEntry>>,
+
+    /// SysV symbol hash table (`.hash`) of this ELF file.
+    pub hash: Option<ELFSysVHash>,
+
+    /// GNU symbol hash table (`.gnu.hash`) of this ELF file.
+    pub gnuhash: Option<ELFGNUHash>,
+
+    /// List of all GNU version definitions of this ELF file.
+    pub verdef: Vec<ELFVersionDefinition>,
+
+    /// List of all GNU version requirements of this ELF file.
+    pub verneed: Vec<ELFVersionRequirement>,
+
+    /// Process state recorded in core dumps.
+    pub coredump: Option<ELFCore>,
+
+    /// Errors of the optional tables that could not be parsed and were left out of this file.
+    pub errors: Vec<ELFError>,
+
+    /// Contents of the sections added or replaced by the editing methods, by section index.
+    edits: HashMap<usize, Vec<u8>>,
 }
 
 impl ELFContent {
     /// Parses the contents of a file and attempts to build an ELF object from them.
     pub fn parse(raw: Vec<u8>) -> Result<Self, Box<dyn Error>> {
-        use traits::{
-            FileHeader, SectionHeader,
+        Self::load( ELFData::Owned(raw) )
+    }
+
+    /// Memory maps the file at the given path and builds an ELF object from it.
+    /// Section data and DWARF information are borrowed from the mapping.
+    ///
+    /// # Safety
+    /// The file must not be modified or truncated while the object is alive.
+    #[cfg(feature = "mmap")]
+    pub unsafe fn mmap(path: &std::path::Path) -> Result<Self, Box<dyn Error>> {
+        let file = File::open(path)?;
+
+        Self::load( ELFData::map(&file)? )
+    }
+
+    /// Builds an ELF object from the given raw data.
+    pub fn load(raw: ELFData) -> Result<Self, Box<dyn Error>> {
+        let (header, segments, sections, symbols, symindex, relocations, groups, attributes, dynamic, hash, gnuhash, verdef, verneed, coredump, errors) = {
+            // Get the borrowed view of the file.
+            let file = ELFFile::parse(&raw)?;
+
+            // Errors of the optional tables, which do not prevent loading the file.
+            let mut errors = Vec::new();
+
+            // Get the segments.
+            let segments = file.segments()?.map(|s| s.map(Box::from)).collect::<Result<Vec<_>, _>>()?;
+
+            // Get the sections.
+            let mut sections = file.sections()?.map(|s| s.map(Box::from)).collect::<Result<Vec<Box<dyn traits::SectionHeader>>, _>>()?;
+
+            // Get the names of the sections.
+            if let Some(shstrtab) = file.shstrtab()? {
+                Self::rename(&shstrtab, &mut sections)?;
+            }
+
+            // Get the GNU version definitions and requirements.
+            let verdef = sections.iter()
+                .find(|s| s.stype() == SectionType::GNUVersionDefinition)
+                .and_then(|s| Self::optional(file.version_definitions(s.as_ref()), &mut errors))
+                .unwrap_or_default();
+
+            let verneed = sections.iter()
+                .find(|s| s.stype() == SectionType::GNUVersionNeed)
+                .and_then(|s| Self::optional(file.version_requirements(s.as_ref()), &mut errors))
+                .unwrap_or_default();
+
+            // Get all the symbol tables.
+            let mut symbols = sections.iter()
+                .filter(|s| (s.stype() == SectionType::SymbolTable) || (s.stype() == SectionType::DynamicSymbol))
+                .map(|table| Self::symboltable(&file, table.as_ref(), &sections))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            // Attach the GNU versions to the symbols of the tables they belong to.
+            for versym in sections.iter().filter(|s| s.stype() == SectionType::GNUVersionSymbol) {
+                if let Some(table) = symbols.iter_mut().find(|t| t.section == versym.link()) {
+                    let indices = Self::optional(file.version_symbols(versym.as_ref()), &mut errors).unwrap_or_default();
+
+                    for (symbol, index) in table.symbols.iter_mut().zip(indices) {
+                        if let Some(version) = Self::version(index, &verdef, &verneed) {
+                            symbol.setversion( version );
+                        }
+                    }
+                }
+            }
+
+            // Index the symbols by address.
+            let symindex = ELFSymbolIndex::new(&symbols, &sections);
+
+            // Get the relocation tables, expanding the packed formats.
+            let relocations = sections.iter()
+                .filter_map(|s| match s.stype() {
+                    SectionType::Relocation | SectionType::RelocationA => Some( (s, false) ),
+                    SectionType::RelativeRelocation | SectionType::AndroidRelativeRelocation |
+                    SectionType::AndroidRelocation | SectionType::AndroidRelocationA => Some( (s, true) ),
+                    _ => None,
+                })
+                .map(|(s, packed)| Ok( ELFRelocationTable {
+                    section: s.index(),
+                    symtab: s.link(),
+                    target: s.info(),
+                    entries: match packed {
+                        true => file.packed_relocations(s.as_ref())?,
+                        false => file.relocations(s.as_ref())?.map(|r| r.map(Box::from)).collect::<Result<Vec<_>, _>>()?,
+                    },
+                }))
+                .filter_map(|table| Self::optional(table, &mut errors))
+                .collect::<Vec<_>>();
+
+            // Get the section groups.
+            let groups = sections.iter()
+                .filter(|s| s.stype() == SectionType::Group)
+                .filter_map(|s| Self::optional(file.group(s.as_ref()), &mut errors))
+                .collect::<Vec<_>>();
+
+            // Get the build attribute sections.
+            let attributes = sections.iter()
+                .filter(|s| matches!(s.stype(), SectionType::ARMAttributes | SectionType::RISCVAttributes | SectionType::GNUAttributes))
+                .filter_map(|s| Self::optional(file.attributes(s.as_ref()), &mut errors))
+                .collect::<Vec<_>>();
+
+            // Get the dynamic section entries up to the terminator.
+            let dynamic = sections.iter()
+                .find(|s| s.stype() == SectionType::Dynamic)
+                .and_then(|section| {
+                    let dynamic = || -> Result<Vec<_>, ELFError> {
+                        let mut dynamic = file.dynamic(section.as_ref())?
+                            .take_while(|entry| !matches!(entry, Ok(e) if e.tag() == DynamicTag::Null))
+                            .map(|entry| entry.map(Box::from))
+                            .collect::<Result<Vec<_>, _>>()?;
+
+                        // Get the strings of the entries from the linked string table.
+                        let dynstr = file.strtab( file.section( section.link() )?.as_ref() )?;
+
+                        Self::rename(&dynstr, &mut dynamic)?;
+
+                        Ok( dynamic )
+                    };
+
+                    Self::optional(dynamic(), &mut errors)
+                })
+                .unwrap_or_default();
+
+            // Get the symbol hash tables.
+            let hash = sections.iter()
+                .find(|s| s.stype() == SectionType::Hash)
+                .and_then(|s| Self::optional(file.sysv_hash(s.as_ref()), &mut errors));
+
+            let gnuhash = sections.iter()
+                .find(|s| s.stype() == SectionType::GNUHash)
+                .and_then(|s| Self::optional(file.gnu_hash(s.as_ref()), &mut errors));
+
+            // Get the process state of core dumps.
+            let coredump = match file.header().filetype() {
+                FileType::Core => Self::optional(ELFCore::parse(&file), &mut errors),
+                _ => None,
+            };
+
+            (file.into_header(), segments, sections, symbols, symindex, relocations, groups, attributes, dynamic, hash, gnuhash, verdef, verneed, coredump, errors)
         };
 
+        Ok( ELFContent {
+            raw,
+            header,
+            segments,
+            sections,
+            symbols,
+            symindex,
+            relocations,
+            groups,
+            attributes,
+            dynamic,
+            hash,
+            gnuhash,
+            verdef,
+            verneed,
+            coredump,
+            errors,
+            edits: HashMap::new(),
+        })
+    }
+
+    /// Creates a borrowed view of the raw data of this file.
+    pub fn view(&self) -> Result<ELFFile<'_>, ELFError> {
+        ELFFile::parse(&self.raw)
+    }
+
+    /// Returns the GNU build ID of this file, if it has one.
+    pub fn build_id(&self) -> Result<Option<&[u8]>, ELFError> {
+        self.view()?.build_id()
+    }
+
+    /// Translates a virtual address into the file offset of its contents.
+    /// Returns `None` if the address is unmapped or not backed by the file (BSS).
+    pub fn offset_of(&self, address: usize) -> Option<usize> {
+        self.mappings().into_iter()
+            .find(|&(vaddr, _, _, filesize)| (vaddr <= address) && (address - vaddr < filesize))
+            .and_then(|(vaddr, _, offset, _)| offset.checked_add(address - vaddr))
+    }
+
+    /// Translates a file offset into the virtual address where its contents are mapped.
+    /// Returns `None` if the offset is not part of a loadable segment or allocated section.
+    pub fn address_of(&self, offset: usize) -> Option<usize> {
+        self.mappings().into_iter()
+            .find(|&(_, _, start, filesize)| (start <= offset) && (offset - start < filesize))
+            .and_then(|(vaddr, _, start, _)| vaddr.checked_add(offset - start))
+    }
+
+    /// Reads the memory image (or the process memory of a core dump) at the given virtual
+    /// address, which may span several segments. Memory not backed by the file (BSS) reads as zeros.
+    /// Fails with `ELFError::UnmappedAddress` on the first unmapped part of the range.
+    pub fn read_virt(&self, address: usize, size: usize) -> Result<Cow<'_, [u8]>, ELFError> {
+        let mappings = self.mappings();
 
-        const EMPTY: [u8; 0] = [];
+        let end = address.checked_add(size).ok_or( ELFError::UnmappedAddress { address, size } )?;
 
-        // Check the magic.
-        if raw[0..4] != [0x7F, 0x45, 0x4C, 0x46] {
-            return Err( Box::new( ELFError::BadMagic( [raw[0], raw[1], raw[2], raw[3]] ) ) );
+        // Contents read so far.
+        let mut data = Vec::new();
+
+        let mut current = address;
+
+        while current < end {
+            let mapping = mappings.iter().find(|&&(vaddr, vsize, _, _)| (vaddr <= current) && (current - vaddr < vsize));
+
+            let (vaddr, vsize, offset, filesize) = match mapping {
+                Some(mapping) => *mapping,
+
+                _ => {
+                    // Report the range up to the next mapped address.
+                    let next = mappings.iter()
+                        .map(|&(vaddr, ..)| vaddr)
+                        .filter(|vaddr| *vaddr > current)
+                        .min()
+                        .map_or(end, |vaddr| vaddr.min(end));
+
+                    return Err( ELFError::UnmappedAddress { address: current, size: next - current } );
+                },
+            };
+
+            // Get the file backed and zero filled parts of the range in this mapping.
+            let start = current - vaddr;
+            let length = (vsize - start).min(end - current);
+            let backed = filesize.saturating_sub(start).min(length);
+
+            let bytes = match backed {
+                0 => &[],
+                _ => read::slice(&self.raw, offset.saturating_add(start), backed)?,
+            };
+
+            // Borrow ranges fully backed by a single mapping.
+            if (current == address) && (backed == size) {
+                return Ok( Cow::Borrowed( bytes ) );
+            }
+
+            data.extend_from_slice( bytes );
+            data.resize( data.len() + (length - backed), 0 );
+
+            current += length;
         }
 
-        // Check the pointer width.
-        match raw[4] {
-            1 => {
-                // Get the file header.
-                let header = ELFHeader::<u32>::parse(&raw);
+        Ok( Cow::Owned( data ) )
+    }
 
-                // Get the sections.
-                let (s, n, b) = (header.shtoffset(), header.shnum(), header.shsize());
-                let e = s + (n * b);
-                let mut sections = ELFSection::<u32>::all(&raw[s..e], &header);
+    /// Internal list of the memory mappings of the file as (virtual address, memory size,
+    /// file offset, file size). Uses the loadable segments, or the allocated sections of
+    /// files without them (relocatable objects).
+    fn mappings(&self) -> Vec<(usize, usize, usize, usize)> {
+        let segments = self.segments.iter()
+            .filter(|s| s.stype() == SegmentType::Load)
+            .map(|s| (s.virt().0, s.virt().1, s.phys().0, s.phys().1))
+            .collect::<Vec<_>>();
+
+        if !segments.is_empty() {
+            return segments;
+        }
 
-                // Get the contents of the section header string section.
-                let shstrtab = Self::sectiondata(&raw, &sections[header.shstrndx()]);
+        self.sections.iter()
+            .filter(|s| s.flags().alloc())
+            .map(|s| match s.stype() {
+                SectionType::Bss => (s.virt().0, s.virt().1, s.phys().0, 0),
+                _ => (s.virt().0, s.virt().1, s.phys().0, s.phys().1),
+            })
+            .collect()
+    }
 
-                // Get the names of the sections.
-                Self::rename(shstrtab, &mut sections);
+    /// Access to the raw contents of a section.
+    pub fn section_data(&self, section: &dyn traits::SectionHeader) -> Result<&[u8], ELFError> {
+        // Sections with edited contents.
+        if let Some(data) = self.edits.get(&section.index()) {
+            return Ok( data );
+        }
 
-                // Get the contents of the string table.
-                let findstrtab = sections.iter()
-                    .find(|section| (section.stype() == SectionType::StringTable) && (section.name() == ".strtab"));
+        // Sections with no data in the file image.
+        if section.stype() == SectionType::Bss {
+            return Ok( &[] );
+        }
 
-                let strtab = match findstrtab {
-                    None => &EMPTY,
-                    Some(section) => Self::sectiondata(&raw, section),
-                };
+        // Get the offset and size of the section.
+        let (o, s) = section.phys();
 
-                // Search for the symbol table.
-                let mut symbols = match sections.iter().find(|s| s.stype() == SectionType::SymbolTable) {
-                    None => Vec::new(),
-                    Some(symtab) => {
-                        use traits::Symbol;
+        read::slice(&self.raw, o, s)
+    }
 
-                        // Get the symbol table.
-                        let table = Self::sectiondata( &raw, symtab );
+    /// Returns the contents of a section, decompressed if the section is compressed.
+    /// The decompression algorithms are enabled by the `zlib` and `zstd` features.
+    pub fn section_contents(&self, section: &dyn traits::SectionHeader) -> Result<Cow<'_, [u8]>, ELFError> {
+        let data = self.section_data(section)?;
 
-                        ELFSymbol::<u32>::all(table, header.endian())
-                    },
-                };
+        match self.compression(section)? {
+            Some(header) => Ok( Cow::Owned( header.decompress(data, section.phys().0)? ) ),
+            _ => Ok( Cow::Borrowed( data ) ),
+        }
+    }
 
-                // Get the names of the symbols.
-                Self::rename(strtab, &mut symbols);
+    /// Returns the compression header of a section if its contents are compressed, either with
+    /// `SHF_COMPRESSED` or as a legacy `.zdebug_*` section. Edited sections are read with their new
+    /// contents and name.
+    pub fn compression(&self, section: &dyn traits::SectionHeader) -> Result<Option<CompressionHeader>, ELFError> {
+        let data = self.section_data(section)?;
 
-                Ok( ELFContent {
-                    raw,
-                    header: Box::new( header ),
-                    sections,
-                    symbols,
-                })
-            },
+        if section.flags().compressed() {
+            let word = match self.raw[0x04] {
+                2 => 8,
+                _ => 4,
+            };
 
-            2 => {
-                // Get the file header.
-                let header = ELFHeader::<u64>::parse(&raw);
+            return CompressionHeader::parse(data, self.header.endian(), word)
+                .map( Some )
+                .map_err(|e| e.shift(section.phys().0));
+        }
 
-                // Get the sections.
-                let (s, n, b) = (header.shtoffset(), header.shnum(), header.shsize());
-                let e = s + (n * b);
-                let mut sections = ELFSection::<u64>::all(&raw[s..e], &header);
+        if section.name().starts_with(".zdebug") {
+            return Ok( CompressionHeader::zdebug(data) );
+        }
 
-                // Get the contents of the section header string section.
-                let shstrtab = Self::sectiondata(&raw, &sections[header.shstrndx()]);
+        Ok( None )
+    }
 
-                // Get the names of the sections.
-                Self::rename(shstrtab, &mut sections);
+    /// Returns the first static symbol table (`.symtab`).
+    pub fn symtab(&self) -> Option<&ELFSymbolTable> {
+        self.symbols.iter().find(|table| table.stype == SectionType::SymbolTable)
+    }
 
-                // Get the contents of the string table.
-                let findstrtab = sections.iter()
-                    .find(|section| (section.stype() == SectionType::StringTable) && (section.name() == ".strtab"));
+    /// Returns the dynamic symbol table (`.dynsym`).
+    pub fn dynsym(&self) -> Option<&ELFSymbolTable> {
+        self.symbols.iter().find(|table| table.stype == SectionType::DynamicSymbol)
+    }
 
-                let strtab = match findstrtab {
-                    None => &EMPTY,
-                    Some(section) => Self::sectiondata(&raw, section),
-                };
+    /// Returns the function or object symbol containing the given address and the offset
+    /// of the address into it. Uses the index of symbols built when the file was loaded.
+    pub fn symbolize(&self, address: usize) -> Option<(&dyn traits::Symbol, usize)> {
+        let (table, symbol, offset) = self.symindex.symbolize(address)?;
 
-                // Search for the symbol table.
-                let mut symbols = match sections.iter().find(|s| s.stype() == SectionType::SymbolTable) {
-                    None => Vec::new(),
-                    Some(symtab) => {
-                        use traits::Symbol;
+        Some( (self.symbols.get(table)?.symbols.get(symbol)?.as_ref(), offset) )
+    }
 
-                        // Get the symbol table.
-                        let table = Self::sectiondata( &raw, symtab );
+    /// Returns the group the section at the given index is a member of.
+    pub fn group(&self, section: usize) -> Option<&ELFSectionGroup> {
+        self.groups.iter().find(|group| group.members.contains(&section))
+    }
 
-                        ELFSymbol::<u64>::all(table, header.endian())
-                    },
-                };
+    /// Builds a map from the index of each grouped section to the index of its group section.
+    pub fn groupmap(&self) -> HashMap<usize, usize> {
+        self.groups.iter()
+            .flat_map(|group| group.members.iter().map(move |member| (*member, group.section)))
+            .collect()
+    }
 
-                // Get the names of the symbols.
-                Self::rename(strtab, &mut symbols);
+    /// Finds a dynamic symbol by name through the symbol hash tables, as the dynamic loader does.
+    /// The GNU hash table is preferred over the SysV hash table when both are present.
+    pub fn lookup(&self, name: &str) -> Option<&dyn traits::Symbol> {
+        // Get the table to search and the symbols it indexes.
+        let (index, symbols) = match (&self.gnuhash, &self.hash) {
+            (Some(gnu), _) => {
+                let (symbols, strtab) = self.hashed(gnu.symtab)?;
+                (gnu.lookup(name.as_bytes(), symbols, &strtab)?, symbols)
+            },
 
-                Ok( ELFContent {
-                    raw,
-                    header: Box::new( header ),
-                    sections,
-                    symbols,
-                })
+            (_, Some(sysv)) => {
+                let (symbols, strtab) = self.hashed(sysv.symtab)?;
+                (sysv.lookup(name.as_bytes(), symbols, &strtab)?, symbols)
             },
 
-            _ => Err( Box::new( ELFError::BadPointerWidth( raw[4] ) ) ),
+            _ => return None,
+        };
+
+        Some( symbols[index].as_ref() )
+    }
+
+    /// Checks that every defined symbol of the indexed symbol tables is found through
+    /// the symbol hash tables, and returns all the disagreements.
+    pub fn check_hash(&self) -> Vec<HashMismatch> {
+        let mut mismatches = Vec::new();
+
+        if let Some(sysv) = &self.hash {
+            let (symbols, strtab) = self.hashed(sysv.symtab).unwrap_or( (&[], StringTable::new(&[], 0)) );
+
+            // Every symbol has a chain entry.
+            if sysv.chains.len() != symbols.len() {
+                mismatches.push( HashMismatch::Count { table: SectionType::Hash, expected: symbols.len(), found: sysv.chains.len() } );
+            }
+
+            mismatches.extend( Self::unhashed(SectionType::Hash, symbols, 1, strtab, |name| sysv.lookup(name, symbols, &strtab)) );
         }
+
+        if let Some(gnu) = &self.gnuhash {
+            let (symbols, strtab) = self.hashed(gnu.symtab).unwrap_or( (&[], StringTable::new(&[], 0)) );
+
+            // Symbols from the offset have a chain entry.
+            let found = (gnu.symoffset as usize) + gnu.chains.len();
+
+            if found != symbols.len() {
+                mismatches.push( HashMismatch::Count { table: SectionType::GNUHash, expected: symbols.len(), found } );
+            }
+
+            mismatches.extend( Self::unhashed(SectionType::GNUHash, symbols, gnu.symoffset as usize, strtab, |name| gnu.lookup(name, symbols, &strtab)) );
+        }
+
+        mismatches
     }
 
-    /// Access to the raw contents of a section.
-    fn sectiondata<'a>(raw: &'a [u8], section: &Box<dyn traits::SectionHeader>) -> &'a [u8] {
-        // Get the offset and size of the symbol section.
-        let (o, s) = section.phys();
+    /// Returns the symbols of the symbol table indexed by a hash table and the string table of their names.
+    fn hashed(&self, symtab: usize) -> Option<(&[Box<dyn traits::Symbol>], StringTable<'_>)> {
+        let table = self.symbols.iter().find(|t| t.section == symtab)?;
+        let strtab = self.sections.get(table.strtab)?;
+
+        Some( (&table.symbols, StringTable::new( self.section_data(strtab.as_ref()).ok()?, strtab.phys().0 )) )
+    }
+
+    /// Returns the named, defined symbols from the given index that the lookup function does not find.
+    /// Symbols with a duplicate name resolve to the first one in the chain.
+    fn unhashed<'a>(table: SectionType, symbols: &'a [Box<dyn traits::Symbol>], start: usize, strtab: StringTable<'a>, lookup: impl Fn(&[u8]) -> Option<usize> + 'a) -> impl Iterator<Item = HashMismatch> + 'a {
+        symbols.iter()
+            .enumerate()
+            .skip(start)
+            .filter(|(_, symbol)| (symbol.strndx() != 0) && (symbol.section() != SymbolSection::Undefined))
+            .filter(move |(_, symbol)| matches!(strtab.get( symbol.strndx() ), Ok(name) if lookup(name).is_none()))
+            .map(move |(i, symbol)| HashMismatch::Missing { table, symbol: i, name: String::from(symbol.name()) })
+    }
+
+    /// Parses a symbol table, resolving the extended section indices, the names
+    /// from the string table linked to it and the names of the symbol sections.
+    fn symboltable(file: &ELFFile, table: &dyn traits::SectionHeader, sections: &[Box<dyn traits::SectionHeader>]) -> Result<ELFSymbolTable, ELFError> {
+        let mut symbols = file.symbols(table)?.map(|s| s.map(Box::from)).collect::<Result<Vec<Box<dyn traits::Symbol>>, _>>()?;
+
+        // Resolve the extended section indices.
+        if let Some(indices) = file.extended_indices(table)? {
+            for (i, symbol) in symbols.iter_mut().enumerate() {
+                if symbol.section() == SymbolSection::Extended {
+                    symbol.extend( indices.get(i)? );
+                }
+            }
+        }
+
+        // Get the names of the sections of the symbols.
+        for symbol in symbols.iter_mut() {
+            if let SymbolSection::Index(i) = symbol.section() {
+                if let Some(section) = sections.get(i) {
+                    symbol.setsectionname( String::from( section.name() ) );
+                }
+            }
+        }
 
-        &raw[o..o+s]
+        // Get the names of the symbols.
+        if table.link() != 0 {
+            let strtab = file.strtab( file.section( table.link() )?.as_ref() )?;
+
+            Self::rename(&strtab, &mut symbols)?;
+        }
+
+        Ok( ELFSymbolTable {
+            section: table.index(),
+            stype: table.stype(),
+            strtab: table.link(),
+            symbols,
+        })
+    }
+
+    /// Returns the parsed optional table, or records its error so the rest of the file can be loaded.
+    fn optional<T>(result: Result<T, ELFError>, errors: &mut Vec<ELFError>) -> Option<T> {
+        result.map_err(|e| errors.push(e)).ok()
+    }
+
+    /// Resolves a `.gnu.version` entry into the version it references.
+    /// The local and global indices (0 and 1) do not reference a version.
+    fn version(index: u16, verdef: &[ELFVersionDefinition], verneed: &[ELFVersionRequirement]) -> Option<SymbolVersion> {
+        // Split the hidden bit from the index.
+        let (hidden, index) = ((index & 0x8000) != 0, index & 0x7FFF);
+
+        if index < 2 {
+            return None;
+        }
+
+        // Search the definitions first, then the requirements.
+        if let Some(definition) = verdef.iter().find(|d| d.index == index) {
+            return Some( SymbolVersion { name: definition.name.clone(), hidden, defined: true } );
+        }
+
+        verneed.iter()
+            .flat_map(|r| r.versions.iter())
+            .find(|v| v.index == index)
+            .map(|v| SymbolVersion { name: v.name.clone(), hidden, defined: false })
     }
 
     /// Names a list of items from the strings in the given string table.
-    fn rename<R: traits::Rename + ?Sized>(strtab: &[u8], objects: &mut Vec<Box<R>>) {
-        if strtab.len() == 0 { return; }
+    fn rename<R: traits::Rename + ?Sized>(strtab: &StringTable, objects: &mut [Box<R>]) -> Result<(), ELFError> {
+        if strtab.data().is_empty() { return Ok(()); }
 
         for object in objects.iter_mut() {
-            // Get the string offset.
-            let offset = object.strndx();
-
             // Get the name.
-            let name = match CStr::from_bytes_until_nul(&strtab[offset..]) {
-                Err(_) => String::from("CORRUPTED"),
+            let name = match strtab.get_str( object.strndx() ) {
+                Err(ELFError::NonUTF8String { .. }) => String::from("CORRUPTED"),
 
-                Ok(cstr) => match cstr.to_owned().into_string() {
-                    Err(_) => String::from("CORRUPTED"),
+                Err(e) => return Err(e),
 
-                    Ok(n) if n.len() == 0 => String::from("NULL"),
+                Ok("") => String::from("NULL"),
 
-                    Ok(n) => n,
-                },
+                Ok(n) => String::from(n),
             };
 
             object.setname( name );
         }
+
+        Ok(())
     }
 
     /// Creates a `gimli` DWARF reader that owns the data (is a copy) of the debug sections.
+    /// Compressed debug sections (including legacy `.zdebug_*` sections) are decompressed
+    /// and the relocations of the debug sections of relocatable files are applied to the copy.
+    /// Edited sections are read with their new contents, which are not relocated.
     pub fn dwarf(&self) -> Result<gimli::read::Dwarf<Vec<u8>>, gimli::Error> {
+        // Error translation.
+        let eof = |e: ELFError| gimli::Error::UnexpectedEof( gimli::ReaderOffsetId( e.offset() as u64 ) );
+
+        // Get the view to relocate the sections.
+        let view = self.view().map_err(eof)?;
+        let relocatable = self.header.filetype() == FileType::Relocatable;
+
         // Create the load closure.
         let load = |id: gimli::SectionId| -> Result<Vec<u8>, gimli::Error> {
-            match self.sections.iter().find(|section| section.name() == id.name() ) {
-                Some(section) => Ok( Self::sectiondata(&self.raw, section).iter().map(|x| *x).collect() ),
+            // Legacy compressed sections replace the `.debug` prefix with `.zdebug`.
+            let zname = id.name().replacen(".debug", ".zdebug", 1);
+
+            match self.sections.iter().find(|section| (section.name() == id.name()) || (section.name() == zname)) {
+                Some(section) => {
+                    let mut data = self.section_contents(section.as_ref()).map_err(eof)?.into_owned();
+
+                    // Relocation tables target the sections by their index in the original file.
+                    // Replaced contents are used as given, as the original relocations do not apply to them.
+                    if relocatable && !self.edits.contains_key(&section.index()) {
+                        let original = view.sections().map_err(eof)?
+                            .filter_map(Result::ok)
+                            .find(|s| (s.phys() == section.phys()) && (s.stype() == section.stype()));
+
+                        if let Some(original) = original {
+                            view.relocate(original.as_ref(), &mut data).map_err(eof)?;
+                        }
+                    }
+
+                    Ok( data )
+                },
                 _ => Ok( Vec::new() ),
             }
         };
@@ -232,6 +736,15 @@ impl ELFContent {
         // Load all the sections.
         gimli::read::Dwarf::load( load )
     }
+
+    /// Creates a `gimli` DWARF reader that borrows the debug sections from the raw data.
+    /// Fails with `gimli::Error::Io` on compressed debug sections, which cannot be borrowed.
+    pub fn dwarf_borrowed(&self) -> Result<gimli::read::Dwarf<gimli::EndianSlice<'_, gimli::RunTimeEndian>>, gimli::Error> {
+        match self.view() {
+            Ok(view) => view.dwarf(),
+            Err(e) => Err( gimli::Error::UnexpectedEof( gimli::ReaderOffsetId( e.offset() as u64 ) ) ),
+        }
+    }
 }
 
 impl core::convert::TryFrom<PathBuf> for ELFContent {
@@ -289,13 +802,57 @@ impl core::fmt::Display for ELFContent {
         // Write the ELF header information.
         string += &format!("{}", self.header);
 
+        // Write all the segments.
+        string += &self.segments.iter()
+            .fold(String::new(), |f, s| f + &format!("{}", s));
+
         // Write all the sections.
         string += &self.sections.iter()
             .fold(String::new(), |f, s| f + &format!("{}", s));
 
-        // Write all the symbols.
+        // Write all the symbol tables, with demangled names in the alternate format.
         string += &self.symbols.iter()
-            .fold(String::new(), |f, s| f + &format!("{}", s));
+            .fold(String::new(), |out, s| match f.alternate() {
+                true => out + &format!("{:#}", s),
+                false => out + &format!("{}", s),
+            });
+
+        // Write all the relocation tables.
+        string += &self.relocations.iter()
+            .fold(String::new(), |f, r| f + &format!("{}", r));
+
+        // Write all the section groups.
+        string += &self.groups.iter()
+            .fold(String::new(), |f, g| f + &format!("{}", g));
+
+        // Write all the build attribute sections.
+        string += &self.attributes.iter()
+            .fold(String::new(), |f, a| f + &format!("{}", a));
+
+        // Write all the dynamic entries.
+        string += &self.dynamic.iter()
+            .fold(String::new(), |f, d| f + &format!("{}", d));
+
+        // Write the symbol hash tables.
+        if let Some(hash) = &self.hash {
+            string += &format!("{}", hash);
+        }
+
+        if let Some(gnuhash) = &self.gnuhash {
+            string += &format!("{}", gnuhash);
+        }
+
+        // Write all the version definitions and requirements.
+        string += &self.verdef.iter()
+            .fold(String::new(), |f, v| f + &format!("{}", v));
+
+        string += &self.verneed.iter()
+            .fold(String::new(), |f, v| f + &format!("{}", v));
+
+        // Write the core dump process state.
+        if let Some(coredump) = &self.coredump {
+            string += &format!("{}", coredump);
+        }
 
         f.write_str(&string)
     }
@@ -596,3 +1153,65 @@ mod test {
         }
     }
 }
+
+
+
+#[cfg(test)]
+mod virt {
+    use super::*;
+
+    use fixture::{
+        Image, Segment,
+    };
+
+    /// 64-bit little endian executable with a loadable segment at 0x1000 of 0x10 file backed
+    /// and 8 zero filled bytes, directly followed by a loadable segment of 8 file backed bytes.
+    fn image() -> ELFContent {
+        // Segment contents.
+        let contents = (1..=0x18).collect::<Vec<u8>>();
+
+        let raw = Image::new(2, 0)
+            .segment( Segment { ptype: 1, vaddr: 0x1000, contents: &contents[..0x10], memsize: 0x18, ..Default::default() } )
+            .segment( Segment { ptype: 1, vaddr: 0x1018, contents: &contents[0x10..], memsize: 8, ..Default::default() } )
+            .build();
+
+        ELFContent::parse(raw).unwrap()
+    }
+
+    #[test]
+    fn translate() {
+        let elf = image();
+
+        assert_eq!(elf.offset_of(0x1004), Some(0xB4));
+        assert_eq!(elf.offset_of(0x101C), Some(0xC4));
+        assert_eq!(elf.address_of(0xC4), Some(0x101C));
+
+        // Zero filled and unmapped addresses have no file offset.
+        assert_eq!(elf.offset_of(0x1010), None);
+        assert_eq!(elf.offset_of(0x0FFF), None);
+        assert_eq!(elf.address_of(0x40), None);
+    }
+
+    #[test]
+    fn read_virt() {
+        let elf = image();
+
+        // Ranges in the file contents of a segment are borrowed.
+        assert!(matches!(elf.read_virt(0x1002, 4), Ok(Cow::Borrowed([3, 4, 5, 6]))));
+
+        // Memory not backed by the file reads as zeros.
+        assert_eq!(elf.read_virt(0x100E, 4).unwrap().as_ref(), [15, 16, 0, 0]);
+
+        // Reads span segments.
+        assert_eq!(elf.read_virt(0x1016, 4).unwrap().as_ref(), [0, 0, 17, 18]);
+    }
+
+    #[test]
+    fn unmapped_address() {
+        let elf = image();
+
+        assert_eq!(elf.read_virt(0x0FF8, 0x10), Err(ELFError::UnmappedAddress { address: 0x0FF8, size: 8 }));
+        assert_eq!(elf.read_virt(0x101C, 8), Err(ELFError::UnmappedAddress { address: 0x1020, size: 4 }));
+        assert_eq!(elf.read_virt(usize::MAX, 2), Err(ELFError::UnmappedAddress { address: usize::MAX, size: 2 }));
+    }
+}
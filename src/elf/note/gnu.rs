@@ -0,0 +1,194 @@
+//! Decoders of the GNU notes.
+
+
+
+use crate::{
+    elf::{
+        common::{
+            read::{
+                self,
+                big32, little32,
+            },
+
+            Endian,
+        },
+
+        ELFError,
+    },
+};
+
+use super::align;
+
+
+
+/// Minimum kernel ABI required by the file (`NT_GNU_ABI_TAG`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ABITag {
+    /// Operating system (0: Linux, 1: Hurd, 2: Solaris, 3: FreeBSD).
+    pub os: u32,
+
+    /// Major, minor and patch version of the ABI.
+    pub version: (u32, u32, u32),
+}
+
+impl ABITag {
+    /// Parses the descriptor of an ABI tag note.
+    pub(crate) fn parse(desc: &[u8], endian: Endian) -> Result<Self, ELFError> {
+        // Get the read function.
+        let read32: fn(&[u8]) -> u32 = match endian {
+            Endian::Little => little32,
+            Endian::Big => big32,
+        };
+
+        // Check the descriptor is complete.
+        let desc = read::slice(desc, 0, 16)?;
+
+        Ok( Self {
+            os: read32( &desc[0..4] ),
+            version: (read32( &desc[4..8] ), read32( &desc[8..12] ), read32( &desc[12..16] )),
+        })
+    }
+}
+
+impl core::fmt::Display for ABITag {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let os = match self.os {
+            0 => String::from("Linux"),
+            1 => String::from("Hurd"),
+            2 => String::from("Solaris"),
+            3 => String::from("FreeBSD"),
+            x => format!("Unknown OS ({})", x),
+        };
+
+        write!(f, "ABI {} {}.{}.{}", os, self.version.0, self.version.1, self.version.2)
+    }
+}
+
+
+
+/// x86 control flow protection features (`GNU_PROPERTY_X86_FEATURE_1_AND`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct X86Features(pub(crate) u32);
+
+impl X86Features {
+    /// Indirect Branch Tracking.
+    pub fn ibt(&self) -> bool {
+        (self.0 & 0x1) != 0
+    }
+
+    /// Shadow Stack.
+    pub fn shstk(&self) -> bool {
+        (self.0 & 0x2) != 0
+    }
+}
+
+impl core::fmt::Display for X86Features {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let mut features = Vec::new();
+
+        if self.ibt()   { features.push("IBT"); }
+        if self.shstk() { features.push("SHSTK"); }
+
+        write!(f, "x86 features: {}", features.join(" + "))
+    }
+}
+
+
+
+/// Aarch64 control flow protection features (`GNU_PROPERTY_AARCH64_FEATURE_1_AND`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Aarch64Features(pub(crate) u32);
+
+impl Aarch64Features {
+    /// Branch Target Identification.
+    pub fn bti(&self) -> bool {
+        (self.0 & 0x1) != 0
+    }
+
+    /// Pointer Authentication.
+    pub fn pac(&self) -> bool {
+        (self.0 & 0x2) != 0
+    }
+}
+
+impl core::fmt::Display for Aarch64Features {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let mut features = Vec::new();
+
+        if self.bti() { features.push("BTI"); }
+        if self.pac() { features.push("PAC"); }
+
+        write!(f, "Aarch64 features: {}", features.join(" + "))
+    }
+}
+
+
+
+/// A GNU program property (`NT_GNU_PROPERTY_TYPE_0`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GNUProperty<'data> {
+    /// x86 control flow protection features.
+    X86Features(X86Features),
+
+    /// Aarch64 control flow protection features.
+    Aarch64Features(Aarch64Features),
+
+    /// Property with no known decoder.
+    Unknown(u32, &'data [u8]),
+}
+
+impl<'data> GNUProperty<'data> {
+    /// Aarch64 feature property type.
+    const AARCH64_FEATURE_1_AND: u32 = 0xC0000000;
+
+    /// x86 feature property type.
+    const X86_FEATURE_1_AND: u32 = 0xC0000002;
+
+    /// Parses all the properties of a property note descriptor.
+    pub(crate) fn all(desc: &'data [u8], endian: Endian, alignment: usize) -> Result<Vec<Self>, ELFError> {
+        // Get the read function.
+        let read32: fn(&[u8]) -> u32 = match endian {
+            Endian::Little => little32,
+            Endian::Big => big32,
+        };
+
+        // List of all properties.
+        let mut properties = Vec::new();
+
+        // Offset of the current property.
+        let mut i = 0;
+
+        while i < desc.len() {
+            // Get the property header.
+            let header = read::slice(desc, i, 8)?;
+
+            let ptype = read32( &header[0..4] );
+            let size = read32( &header[4..8] ) as usize;
+
+            // Get the property data.
+            let data = read::slice(desc, i + 8, size)?;
+
+            let property = match (ptype, data.len()) {
+                (Self::X86_FEATURE_1_AND, 4) => Self::X86Features( X86Features( read32(data) ) ),
+                (Self::AARCH64_FEATURE_1_AND, 4) => Self::Aarch64Features( Aarch64Features( read32(data) ) ),
+                _ => Self::Unknown(ptype, data),
+            };
+
+            properties.push( property );
+
+            i += 8 + align(size, alignment);
+        }
+
+        Ok( properties )
+    }
+}
+
+impl<'data> core::fmt::Display for GNUProperty<'data> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            Self::X86Features(features) => write!(f, "{}", features),
+            Self::Aarch64Features(features) => write!(f, "{}", features),
+            Self::Unknown(ptype, data) => write!(f, "Property {:#X} ({} B)", ptype, data.len()),
+        }
+    }
+}
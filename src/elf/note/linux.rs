@@ -0,0 +1,495 @@
+//! Decoders of the notes of Linux core dumps.
+
+
+
+use byteorder::{
+    BigEndian, LittleEndian,
+    ByteOrder,
+};
+
+use crate::{
+    elf::{
+        common::{
+            read,
+            Endian, InstructionSet,
+        },
+
+        ELFError,
+    },
+};
+
+
+
+/// Internal reader of the fields of a core note descriptor.
+struct Reader<'a> {
+    /// Descriptor of the note.
+    desc: &'a [u8],
+
+    /// Endianness of the descriptor.
+    endian: Endian,
+
+    /// Size of a word (4 or 8).
+    word: usize,
+}
+
+impl<'a> Reader<'a> {
+    /// Reads an integer of the given size at the given offset.
+    fn uint(&self, offset: usize, size: usize) -> Result<u64, ELFError> {
+        let bytes = read::slice(self.desc, offset, size)?;
+
+        match self.endian {
+            Endian::Little => Ok( LittleEndian::read_uint(bytes, size) ),
+            Endian::Big => Ok( BigEndian::read_uint(bytes, size) ),
+        }
+    }
+
+    /// Reads a 32-bit signed integer at the given offset.
+    fn i32(&self, offset: usize) -> Result<i32, ELFError> {
+        Ok( self.uint(offset, 4)? as u32 as i32 )
+    }
+
+    /// Reads a word at the given offset.
+    fn word(&self, offset: usize) -> Result<u64, ELFError> {
+        self.uint(offset, self.word)
+    }
+
+    /// Reads a NULL terminated string in a fixed size field.
+    fn string(&self, offset: usize, size: usize) -> Result<String, ELFError> {
+        let bytes = read::slice(self.desc, offset, size)?;
+
+        let end = bytes.iter().position(|b| *b == 0).unwrap_or(bytes.len());
+
+        Ok( String::from_utf8_lossy( &bytes[..end] ).into_owned() )
+    }
+}
+
+
+
+/// General purpose registers of an AMD64 thread (`user_regs_struct`).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AMD64Registers {
+    pub r15: u64, pub r14: u64, pub r13: u64, pub r12: u64,
+    pub rbp: u64, pub rbx: u64, pub r11: u64, pub r10: u64,
+    pub r9:  u64, pub r8:  u64, pub rax: u64, pub rcx: u64,
+    pub rdx: u64, pub rsi: u64, pub rdi: u64, pub orig_rax: u64,
+    pub rip: u64, pub cs:  u64, pub eflags: u64, pub rsp: u64,
+    pub ss:  u64, pub fs_base: u64, pub gs_base: u64,
+    pub ds:  u64, pub es:  u64, pub fs:  u64, pub gs:  u64,
+}
+
+/// General purpose registers of an Aarch64 thread (`user_pt_regs`).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Aarch64Registers {
+    /// Registers X0 to X30.
+    pub x: [u64; 31],
+
+    /// Stack pointer.
+    pub sp: u64,
+
+    /// Program counter.
+    pub pc: u64,
+
+    /// Processor state.
+    pub pstate: u64,
+}
+
+/// Register set of a thread, decoded according to the target Instruction Set Architecture.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Registers {
+    /// AMD64 general purpose registers.
+    AMD64(AMD64Registers),
+
+    /// Aarch64 general purpose registers.
+    Aarch64(Aarch64Registers),
+
+    /// Raw words of the register set of other architectures.
+    Raw(Vec<u64>),
+}
+
+impl Registers {
+    /// Returns the program counter, if the register set is decoded.
+    pub fn pc(&self) -> Option<u64> {
+        match self {
+            Self::AMD64(r) => Some( r.rip ),
+            Self::Aarch64(r) => Some( r.pc ),
+            Self::Raw(_) => None,
+        }
+    }
+
+    /// Returns the stack pointer, if the register set is decoded.
+    pub fn sp(&self) -> Option<u64> {
+        match self {
+            Self::AMD64(r) => Some( r.rsp ),
+            Self::Aarch64(r) => Some( r.sp ),
+            Self::Raw(_) => None,
+        }
+    }
+}
+
+impl core::fmt::Display for Registers {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            Self::AMD64(r) => {
+                let names = [
+                    ("rax", r.rax), ("rbx", r.rbx), ("rcx", r.rcx), ("rdx", r.rdx),
+                    ("rsi", r.rsi), ("rdi", r.rdi), ("rbp", r.rbp), ("rsp", r.rsp),
+                    ("r8",  r.r8 ), ("r9",  r.r9 ), ("r10", r.r10), ("r11", r.r11),
+                    ("r12", r.r12), ("r13", r.r13), ("r14", r.r14), ("r15", r.r15),
+                    ("rip", r.rip), ("eflags", r.eflags),
+                ];
+
+                let string = names.iter()
+                    .map(|(n, v)| format!("{} 0x{:016X}", n, v))
+                    .collect::<Vec<_>>();
+
+                write!(f, "{}", string.join(", "))
+            },
+
+            Self::Aarch64(r) => {
+                let mut string = r.x.iter().enumerate()
+                    .map(|(i, v)| format!("x{} 0x{:016X}", i, v))
+                    .collect::<Vec<_>>();
+
+                string.push( format!("sp 0x{:016X}", r.sp) );
+                string.push( format!("pc 0x{:016X}", r.pc) );
+                string.push( format!("pstate 0x{:016X}", r.pstate) );
+
+                write!(f, "{}", string.join(", "))
+            },
+
+            Self::Raw(words) => write!(f, "{:X?}", words),
+        }
+    }
+}
+
+
+
+/// Status of a thread at the time of the dump (`NT_PRSTATUS`).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProcessStatus {
+    /// Current signal.
+    pub signal: u16,
+
+    /// Thread ID.
+    pub pid: i32,
+
+    /// Parent process ID.
+    pub ppid: i32,
+
+    /// Process group ID.
+    pub pgrp: i32,
+
+    /// Session ID.
+    pub sid: i32,
+
+    /// General purpose registers.
+    pub registers: Registers,
+}
+
+impl ProcessStatus {
+    /// Parses the descriptor of a `NT_PRSTATUS` note.
+    pub(crate) fn parse(desc: &[u8], endian: Endian, isa: InstructionSet, word: usize) -> Result<Self, ELFError> {
+        let reader = Reader { desc, endian, word };
+
+        // Offsets of the fields after the signal masks.
+        let ids = 16 + (2 * word);
+        let registers = ids + 16 + (8 * word);
+
+        // Get the register set.
+        let registers = match (isa, word) {
+            (InstructionSet::AMD64, 8) => {
+                let mut r = [0u64; 27];
+
+                for (i, value) in r.iter_mut().enumerate() {
+                    *value = reader.word(registers + (i * 8))?;
+                }
+
+                Registers::AMD64( AMD64Registers {
+                    r15: r[0], r14: r[1], r13: r[2], r12: r[3],
+                    rbp: r[4], rbx: r[5], r11: r[6], r10: r[7],
+                    r9:  r[8], r8:  r[9], rax: r[10], rcx: r[11],
+                    rdx: r[12], rsi: r[13], rdi: r[14], orig_rax: r[15],
+                    rip: r[16], cs: r[17], eflags: r[18], rsp: r[19],
+                    ss: r[20], fs_base: r[21], gs_base: r[22],
+                    ds: r[23], es: r[24], fs: r[25], gs: r[26],
+                })
+            },
+
+            (InstructionSet::Aarch64, 8) => {
+                let mut x = [0u64; 31];
+
+                for (i, value) in x.iter_mut().enumerate() {
+                    *value = reader.word(registers + (i * 8))?;
+                }
+
+                Registers::Aarch64( Aarch64Registers {
+                    x,
+                    sp: reader.word(registers + (31 * 8))?,
+                    pc: reader.word(registers + (32 * 8))?,
+                    pstate: reader.word(registers + (33 * 8))?,
+                })
+            },
+
+            _ => {
+                // The register set fills the descriptor up to the FP valid flag.
+                let end = desc.len().saturating_sub(4);
+
+                let words = (registers..end).step_by(word)
+                    .take_while(|i| i + word <= end)
+                    .map(|i| reader.word(i))
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                Registers::Raw( words )
+            },
+        };
+
+        Ok( Self {
+            signal: reader.uint(12, 2)? as u16,
+            pid:  reader.i32(ids)?,
+            ppid: reader.i32(ids + 4)?,
+            pgrp: reader.i32(ids + 8)?,
+            sid:  reader.i32(ids + 12)?,
+            registers,
+        })
+    }
+}
+
+impl core::fmt::Display for ProcessStatus {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "Thread {} (signal {}) [{}]", self.pid, self.signal, self.registers)
+    }
+}
+
+
+
+/// Information of the dumped process (`NT_PRPSINFO`).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProcessInfo {
+    /// Numeric process state.
+    pub state: u8,
+
+    /// Character process state (`R`, `S`, `D`, `T`, `Z`...).
+    pub sname: char,
+
+    /// Nice value.
+    pub nice: i8,
+
+    /// Process flags.
+    pub flags: u64,
+
+    /// User ID.
+    pub uid: u32,
+
+    /// Group ID.
+    pub gid: u32,
+
+    /// Process ID.
+    pub pid: i32,
+
+    /// Parent process ID.
+    pub ppid: i32,
+
+    /// Process group ID.
+    pub pgrp: i32,
+
+    /// Session ID.
+    pub sid: i32,
+
+    /// Name of the executable.
+    pub fname: String,
+
+    /// Start of the command line.
+    pub args: String,
+}
+
+impl ProcessInfo {
+    /// Parses the descriptor of a `NT_PRPSINFO` note.
+    pub(crate) fn parse(desc: &[u8], endian: Endian, word: usize) -> Result<Self, ELFError> {
+        let reader = Reader { desc, endian, word };
+
+        // Get the IDs. 32-bit layouts use 16-bit user and group IDs.
+        let ((uid, gid), ids) = match word {
+            8 => ((reader.uint(16, 4)?, reader.uint(20, 4)?), 24),
+            _ => ((reader.uint(8, 2)?, reader.uint(10, 2)?), 12),
+        };
+
+        Ok( Self {
+            state: reader.uint(0, 1)? as u8,
+            sname: reader.uint(1, 1)? as u8 as char,
+            nice: reader.uint(3, 1)? as u8 as i8,
+            flags: reader.word(word)?,
+            uid: uid as u32,
+            gid: gid as u32,
+            pid:  reader.i32(ids)?,
+            ppid: reader.i32(ids + 4)?,
+            pgrp: reader.i32(ids + 8)?,
+            sid:  reader.i32(ids + 12)?,
+            fname: reader.string(ids + 16, 16)?,
+            args: reader.string(ids + 32, 80)?,
+        })
+    }
+}
+
+impl core::fmt::Display for ProcessInfo {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "Process {} ({}) state {} uid {} gid {}: {}", self.pid, self.fname, self.sname, self.uid, self.gid, self.args)
+    }
+}
+
+
+
+/// Signal that caused the dump (`NT_SIGINFO`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SignalInfo {
+    /// Signal number.
+    pub signo: i32,
+
+    /// Error number.
+    pub errno: i32,
+
+    /// Signal code.
+    pub code: i32,
+
+    /// Faulting address, for fault signals.
+    pub address: u64,
+}
+
+impl SignalInfo {
+    /// Parses the descriptor of a `NT_SIGINFO` note.
+    pub(crate) fn parse(desc: &[u8], endian: Endian, word: usize) -> Result<Self, ELFError> {
+        let reader = Reader { desc, endian, word };
+
+        // The signal fields union is word aligned.
+        let union = match word {
+            8 => 16,
+            _ => 12,
+        };
+
+        Ok( Self {
+            signo: reader.i32(0)?,
+            errno: reader.i32(4)?,
+            code:  reader.i32(8)?,
+            address: reader.word(union)?,
+        })
+    }
+}
+
+impl core::fmt::Display for SignalInfo {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "Signal {} code {} errno {} address 0x{:X}", self.signo, self.code, self.errno, self.address)
+    }
+}
+
+
+
+/// Entry of the auxiliary vector (`NT_AUXV`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AuxEntry {
+    /// Type of the entry (`AT_*`).
+    pub atype: u64,
+
+    /// Value of the entry.
+    pub value: u64,
+}
+
+impl AuxEntry {
+    /// Parses the descriptor of a `NT_AUXV` note up to the `AT_NULL` terminator.
+    pub(crate) fn all(desc: &[u8], endian: Endian, word: usize) -> Result<Vec<Self>, ELFError> {
+        let reader = Reader { desc, endian, word };
+
+        let mut entries = Vec::new();
+
+        for i in (0..desc.len() / (2 * word)).map(|i| i * 2 * word) {
+            let entry = Self { atype: reader.word(i)?, value: reader.word(i + word)? };
+
+            if entry.atype == 0 {
+                break;
+            }
+
+            entries.push( entry );
+        }
+
+        Ok( entries )
+    }
+}
+
+impl core::fmt::Display for AuxEntry {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let name = match self.atype {
+            3  => "AT_PHDR",
+            4  => "AT_PHENT",
+            5  => "AT_PHNUM",
+            6  => "AT_PAGESZ",
+            7  => "AT_BASE",
+            9  => "AT_ENTRY",
+            11 => "AT_UID",
+            16 => "AT_HWCAP",
+            25 => "AT_RANDOM",
+            26 => "AT_HWCAP2",
+            31 => "AT_EXECFN",
+            33 => "AT_SYSINFO_EHDR",
+            _  => return write!(f, "AT_{} 0x{:X}", self.atype, self.value),
+        };
+
+        write!(f, "{} 0x{:X}", name, self.value)
+    }
+}
+
+
+
+/// File mapped in the address space of the process (`NT_FILE`).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MappedFile {
+    /// Start address of the mapping.
+    pub start: u64,
+
+    /// End address of the mapping.
+    pub end: u64,
+
+    /// Offset of the mapping in the file, in bytes.
+    pub offset: u64,
+
+    /// Path of the file.
+    pub name: String,
+}
+
+impl MappedFile {
+    /// Parses the descriptor of a `NT_FILE` note.
+    pub(crate) fn all(desc: &[u8], endian: Endian, word: usize) -> Result<Vec<Self>, ELFError> {
+        let reader = Reader { desc, endian, word };
+
+        // Get the number of mappings and the page size.
+        let count = reader.word(0)? as usize;
+        let page = reader.word(word)?;
+
+        // Get the start of the file names.
+        let names = match count.checked_mul(3 * word).and_then(|n| n.checked_add(2 * word)) {
+            Some(names) => names,
+            _ => return Err( ELFError::OutOfBounds { offset: 0, size: count, filesize: desc.len() } ),
+        };
+
+        let mut strings = read::slice(desc, names, desc.len().saturating_sub(names))?
+            .split(|b| *b == 0);
+
+        let mut files = Vec::new();
+
+        for i in 0..count {
+            let entry = (2 * word) + (i * 3 * word);
+
+            files.push( Self {
+                start: reader.word(entry)?,
+                end: reader.word(entry + word)?,
+                offset: reader.word(entry + (2 * word))?.wrapping_mul(page),
+                name: String::from_utf8_lossy( strings.next().unwrap_or(&[]) ).into_owned(),
+            });
+        }
+
+        Ok( files )
+    }
+}
+
+impl core::fmt::Display for MappedFile {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "0x{:X}-0x{:X} @ 0x{:X} {}", self.start, self.end, self.offset, self.name)
+    }
+}
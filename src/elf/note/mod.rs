@@ -0,0 +1,408 @@
+//! ELF Note parsing and interaction.
+
+
+
+mod gnu;
+mod linux;
+
+
+
+pub use gnu::{
+    ABITag, Aarch64Features, GNUProperty, X86Features,
+};
+
+pub use linux::{
+    AMD64Registers, Aarch64Registers, AuxEntry, MappedFile,
+    ProcessInfo, ProcessStatus, Registers, SignalInfo,
+};
+
+use crate::{
+    elf::{
+        common::{
+            read::{
+                self,
+                big32, little32,
+            },
+
+            Endian, InstructionSet,
+        },
+
+        ELFError,
+    },
+};
+
+
+
+/// GNU build ID note type.
+const NT_GNU_BUILD_ID: u32 = 3;
+
+/// GNU ABI tag note type.
+const NT_GNU_ABI_TAG: u32 = 1;
+
+/// GNU gold linker version note type.
+const NT_GNU_GOLD_VERSION: u32 = 4;
+
+/// GNU program properties note type.
+const NT_GNU_PROPERTY_TYPE_0: u32 = 5;
+
+/// Core dump thread status note type.
+const NT_PRSTATUS: u32 = 1;
+
+/// Core dump process information note type.
+const NT_PRPSINFO: u32 = 3;
+
+/// Core dump auxiliary vector note type.
+const NT_AUXV: u32 = 6;
+
+/// Core dump signal information note type.
+const NT_SIGINFO: u32 = 0x53494749;
+
+/// Core dump mapped files note type.
+const NT_FILE: u32 = 0x46494C45;
+
+/// systemd package metadata note type.
+const NT_FDO_PACKAGING_METADATA: u32 = 0xCAFE1A7E;
+
+
+
+/// A note borrowed from the raw ELF data.
+#[derive(Clone, Copy, Debug)]
+pub struct ELFNote<'data> {
+    /// File offset of the note.
+    offset: usize,
+
+    /// Owner of the note, without the NULL terminator.
+    name: &'data [u8],
+
+    /// Type of the note, interpreted according to its owner.
+    ntype: u32,
+
+    /// Descriptor of the note.
+    desc: &'data [u8],
+
+    /// File offset of the descriptor.
+    descoffset: usize,
+
+    /// Endianness of the descriptor.
+    endian: Endian,
+
+    /// Target Instruction Set Architecture of the file.
+    isa: InstructionSet,
+
+    /// Size of a word of the file (4 or 8).
+    word: usize,
+
+    /// Alignment of the note entries.
+    align: usize,
+}
+
+impl<'data> ELFNote<'data> {
+    /// Returns the file offset of the note.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns the owner of the note.
+    pub fn name(&self) -> &'data [u8] {
+        self.name
+    }
+
+    /// Returns the type of the note.
+    pub fn ntype(&self) -> u32 {
+        self.ntype
+    }
+
+    /// Returns the raw descriptor of the note.
+    pub fn desc(&self) -> &'data [u8] {
+        self.desc
+    }
+
+    /// Decodes the descriptor of the note according to its owner and type.
+    pub fn data(&self) -> Result<NoteData<'data>, ELFError> {
+        let data = match (self.name, self.ntype) {
+            (b"GNU", NT_GNU_BUILD_ID) => NoteData::BuildID( self.desc ),
+            (b"GNU", NT_GNU_ABI_TAG) => NoteData::ABITag( ABITag::parse(self.desc, self.endian).map_err(|e| e.shift(self.descoffset))? ),
+            (b"GNU", NT_GNU_GOLD_VERSION) => NoteData::GoldVersion( String::from_utf8_lossy( until_nul(self.desc) ).into_owned() ),
+            (b"GNU", NT_GNU_PROPERTY_TYPE_0) => NoteData::Properties( GNUProperty::all(self.desc, self.endian, self.align).map_err(|e| e.shift(self.descoffset))? ),
+            (b"FDO", NT_FDO_PACKAGING_METADATA) => NoteData::Package( String::from_utf8_lossy( until_nul(self.desc) ).into_owned() ),
+
+            (b"CORE", NT_PRSTATUS) => NoteData::ProcessStatus( Box::new( ProcessStatus::parse(self.desc, self.endian, self.isa, self.word).map_err(|e| e.shift(self.descoffset))? ) ),
+            (b"CORE", NT_PRPSINFO) => NoteData::ProcessInfo( ProcessInfo::parse(self.desc, self.endian, self.word).map_err(|e| e.shift(self.descoffset))? ),
+            (b"CORE", NT_AUXV) => NoteData::AuxVector( AuxEntry::all(self.desc, self.endian, self.word).map_err(|e| e.shift(self.descoffset))? ),
+            (b"CORE", NT_SIGINFO) => NoteData::SignalInfo( SignalInfo::parse(self.desc, self.endian, self.word).map_err(|e| e.shift(self.descoffset))? ),
+            (b"CORE", NT_FILE) => NoteData::MappedFiles( MappedFile::all(self.desc, self.endian, self.word).map_err(|e| e.shift(self.descoffset))? ),
+
+            _ => NoteData::Unknown( self.desc ),
+        };
+
+        Ok( data )
+    }
+}
+
+impl<'data> core::fmt::Display for ELFNote<'data> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        // Build the output string.
+        let mut string = String::from("ELF Note\n");
+
+        // Add note information.
+        string += &format!("|- Owner: {}\n", String::from_utf8_lossy(self.name));
+        string += &format!("|- Type : {:#X}\n", self.ntype);
+        string += &format!("|- Size : {} B\n", self.desc.len());
+
+        match self.data() {
+            Ok(data) => string += &format!("|- Data : {}\n\n", data),
+            Err(e) => string += &format!("|- Data : <{}>\n\n", e),
+        }
+
+        f.write_str(&string)
+    }
+}
+
+
+
+/// Decoded descriptor of a note.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum NoteData<'data> {
+    /// Unique build ID of the file (`NT_GNU_BUILD_ID`).
+    BuildID(&'data [u8]),
+
+    /// Minimum kernel ABI (`NT_GNU_ABI_TAG`).
+    ABITag(ABITag),
+
+    /// Version of the gold linker that built the file (`NT_GNU_GOLD_VERSION`).
+    GoldVersion(String),
+
+    /// GNU program properties (`NT_GNU_PROPERTY_TYPE_0`).
+    Properties(Vec<GNUProperty<'data>>),
+
+    /// JSON package metadata (`.note.package`).
+    Package(String),
+
+    /// Status and registers of a thread of a core dump (`NT_PRSTATUS`).
+    ProcessStatus(Box<ProcessStatus>),
+
+    /// Information of the process of a core dump (`NT_PRPSINFO`).
+    ProcessInfo(ProcessInfo),
+
+    /// Auxiliary vector of the process of a core dump (`NT_AUXV`).
+    AuxVector(Vec<AuxEntry>),
+
+    /// Signal that caused a core dump (`NT_SIGINFO`).
+    SignalInfo(SignalInfo),
+
+    /// Files mapped in the process of a core dump (`NT_FILE`).
+    MappedFiles(Vec<MappedFile>),
+
+    /// Note with no known decoder.
+    Unknown(&'data [u8]),
+}
+
+impl<'data> core::fmt::Display for NoteData<'data> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            Self::BuildID(id) => write!(f, "Build ID {}", id.iter().map(|b| format!("{:02x}", b)).collect::<String>()),
+            Self::ABITag(tag) => write!(f, "{}", tag),
+            Self::GoldVersion(version) => write!(f, "Gold version {}", version),
+            Self::Properties(properties) => write!(f, "Properties [{}]", properties.iter().map(|p| format!("{}", p)).collect::<Vec<_>>().join(", ")),
+            Self::Package(json) => write!(f, "Package {}", json),
+            Self::ProcessStatus(status) => write!(f, "{}", status),
+            Self::ProcessInfo(info) => write!(f, "{}", info),
+            Self::AuxVector(entries) => write!(f, "Auxiliary vector [{}]", entries.iter().map(|e| format!("{}", e)).collect::<Vec<_>>().join(", ")),
+            Self::SignalInfo(info) => write!(f, "{}", info),
+            Self::MappedFiles(files) => write!(f, "Mapped files [{}]", files.iter().map(|m| format!("{}", m)).collect::<Vec<_>>().join(", ")),
+            Self::Unknown(desc) => write!(f, "{:02X?}", desc),
+        }
+    }
+}
+
+
+
+/// Iterator over the notes of a note section or segment.
+pub struct Notes<'data> {
+    /// Contents of the note section or segment.
+    data: &'data [u8],
+
+    /// File offset of the contents.
+    offset: usize,
+
+    /// Offset of the next note.
+    index: usize,
+
+    /// Endianness of the notes.
+    endian: Endian,
+
+    /// Target Instruction Set Architecture of the file.
+    isa: InstructionSet,
+
+    /// Size of a word of the file (4 or 8).
+    word: usize,
+
+    /// Alignment of the note entries (4 or 8).
+    align: usize,
+}
+
+impl<'data> Notes<'data> {
+    /// Creates an iterator over the given note data with the alignment of its section or segment.
+    pub(crate) fn new(data: &'data [u8], offset: usize, (endian, isa, word): (Endian, InstructionSet, usize), align: usize) -> Self {
+        // Notes are 4 byte aligned, except for 8 byte aligned sections and segments.
+        let align = match align {
+            8 => 8,
+            _ => 4,
+        };
+
+        Self { data, offset, index: 0, endian, isa, word, align }
+    }
+
+    /// Internal parse function of the next note.
+    fn note(&self) -> Result<(ELFNote<'data>, usize), ELFError> {
+        // Get the read function.
+        let read32: fn(&[u8]) -> u32 = match self.endian {
+            Endian::Little => little32,
+            Endian::Big => big32,
+        };
+
+        // Get the note header.
+        let header = read::slice(self.data, self.index, 12)?;
+
+        let namesz = read32( &header[0..4] ) as usize;
+        let descsz = read32( &header[4..8] ) as usize;
+        let ntype  = read32( &header[8..12] );
+
+        // Get the name.
+        let start = self.index + 12;
+        let name = read::slice(self.data, start, namesz)?;
+
+        // Get the descriptor, aligned from the start of the note.
+        let start = self.index + align(12 + namesz, self.align);
+        let desc = read::slice(self.data, start, descsz)?;
+
+        // Get the start of the next note.
+        let next = self.index + align((start - self.index) + descsz, self.align);
+
+        let note = ELFNote {
+            offset: self.offset + self.index,
+            name: until_nul(name),
+            ntype,
+            desc,
+            descoffset: self.offset + start,
+            endian: self.endian,
+            isa: self.isa,
+            word: self.word,
+            align: self.align,
+        };
+
+        Ok( (note, next) )
+    }
+}
+
+impl<'data> Iterator for Notes<'data> {
+    type Item = Result<ELFNote<'data>, ELFError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Check if there are notes left.
+        if self.index >= self.data.len() {
+            return None;
+        }
+
+        match self.note() {
+            Ok((note, next)) => {
+                self.index = next;
+                Some( Ok( note ) )
+            },
+
+            Err(e) => {
+                // Stop after a malformed note.
+                self.index = self.data.len();
+                Some( Err( e.shift(self.offset) ) )
+            },
+        }
+    }
+}
+
+
+
+/// Internal alignment of a size.
+fn align(size: usize, align: usize) -> usize {
+    size.div_ceil(align) * align
+}
+
+/// Internal removal of the NULL terminator and padding of a string.
+fn until_nul(bytes: &[u8]) -> &[u8] {
+    match bytes.iter().position(|b| *b == 0) {
+        Some(end) => &bytes[..end],
+        _ => bytes,
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Layout of a 64-bit little endian x86-64 file.
+    const LAYOUT: (Endian, InstructionSet, usize) = (Endian::Little, InstructionSet::AMD64, 8);
+
+    #[test]
+    fn four_byte_notes() {
+        // GNU build ID (name padded to 4 bytes) followed by a GNU ABI tag.
+        let data = [
+            4, 0, 0, 0,  3, 0, 0, 0,  3, 0, 0, 0,  b'G', b'N', b'U', 0,
+            0xAA, 0xBB, 0xCC, 0,
+            4, 0, 0, 0,  16, 0, 0, 0,  1, 0, 0, 0,  b'G', b'N', b'U', 0,
+            0, 0, 0, 0,  3, 0, 0, 0,  2, 0, 0, 0,  0, 0, 0, 0,
+        ];
+
+        let notes = Notes::new(&data, 0x100, LAYOUT, 4).collect::<Result<Vec<_>, _>>().unwrap();
+
+        assert_eq!(notes.len(), 2);
+
+        assert_eq!(notes[0].offset(), 0x100);
+        assert_eq!(notes[0].name(), b"GNU");
+        assert_eq!(notes[0].data().unwrap(), NoteData::BuildID( &[0xAA, 0xBB, 0xCC] ));
+
+        // The descriptor of 3 bytes is padded to 4 bytes.
+        assert_eq!(notes[1].offset(), 0x114);
+        assert_eq!(notes[1].data().unwrap(), NoteData::ABITag( ABITag { os: 0, version: (3, 2, 0) } ));
+    }
+
+    #[test]
+    fn eight_byte_notes() {
+        // GNU property note with an x86 feature property, padded to 8 bytes, then an unknown note.
+        let data = [
+            4, 0, 0, 0,  16, 0, 0, 0,  5, 0, 0, 0,  b'G', b'N', b'U', 0,
+            0x02, 0, 0, 0xC0,  4, 0, 0, 0,  0x03, 0, 0, 0,  0, 0, 0, 0,
+            2, 0, 0, 0,  1, 0, 0, 0,  0x10, 0, 0, 0,  b'X', 0, 0, 0,
+            0x42, 0, 0, 0,  0, 0, 0, 0,
+        ];
+
+        let notes = Notes::new(&data, 0, LAYOUT, 8).collect::<Result<Vec<_>, _>>().unwrap();
+
+        assert_eq!(notes.len(), 2);
+
+        match notes[0].data().unwrap() {
+            NoteData::Properties(properties) => {
+                assert_eq!(properties.len(), 1);
+                assert!(matches!(properties[0], GNUProperty::X86Features(f) if f.ibt() && f.shstk()));
+            },
+
+            data => panic!("unexpected note data {:?}", data),
+        }
+
+        // The name (2 bytes) and descriptor (1 byte) are padded to 8 bytes.
+        assert_eq!(notes[1].offset(), 0x20);
+        assert_eq!(notes[1].ntype(), 0x10);
+        assert_eq!(notes[1].name(), b"X");
+        assert_eq!(notes[1].data().unwrap(), NoteData::Unknown( &[0x42] ));
+    }
+
+    #[test]
+    fn truncated_note() {
+        // The descriptor size points past the end of the data.
+        let data = [4, 0, 0, 0,  0x40, 0, 0, 0,  3, 0, 0, 0,  b'G', b'N', b'U', 0,  1, 2, 3, 4];
+
+        let mut notes = Notes::new(&data, 0x200, LAYOUT, 4);
+
+        assert!(matches!(notes.next(), Some(Err(ELFError::OutOfBounds { offset: 0x210, .. }))));
+        assert!(notes.next().is_none());
+    }
+}
@@ -0,0 +1,141 @@
+//! ELF Relocation parsing and interaction.
+
+
+
+mod packed;
+mod x32;
+mod x64;
+
+
+
+pub(crate) use packed::{
+    aps2, relr,
+};
+
+use crate::{
+    elf::{
+        common::{
+            RelocationType,
+        },
+
+        traits::{
+            Relocation,
+        },
+    },
+};
+
+
+
+/// Contains an ELF Relocation entry (REL or RELA).
+pub struct ELFRelocation<T> {
+    /// Index of the entry in its relocation table.
+    index: usize,
+
+    /// Location to relocate.
+    offset: T,
+
+    /// Index of the referenced symbol.
+    symbol: u32,
+
+    /// Type of relocation.
+    rtype: RelocationType,
+
+    /// Explicit addend of RELA entries.
+    addend: Option<i64>,
+}
+
+
+
+/// Contains a relocation table and the sections it is associated with.
+pub struct ELFRelocationTable {
+    /// Index of the relocation section.
+    pub section: usize,
+
+    /// Index of the associated symbol table (`sh_link`).
+    pub symtab: usize,
+
+    /// Index of the section the relocations apply to (`sh_info`).
+    pub target: usize,
+
+    /// Relocation entries.
+    pub entries: Vec<Box<dyn Relocation>>,
+}
+
+impl core::fmt::Display for ELFRelocationTable {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        // Build the output string.
+        let mut string = String::from("ELF Relocation Table\n");
+
+        // Add table information.
+        string += &format!("|- Relocation section index: {}\n", self.section);
+        string += &format!("|- Symbol table index      : {}\n", self.symtab);
+        string += &format!("|- Target section index    : {}\n", self.target);
+        string += &format!("|- Number of entries       : {}\n\n", self.entries.len());
+
+        // Add all the entries.
+        string += &self.entries.iter()
+            .fold(String::new(), |f, r| f + &format!("{}", r));
+
+        f.write_str(&string)
+    }
+}
+
+
+
+#[cfg(test)]
+mod test {
+    use crate::elf::{
+        common::{
+            AMD64Relocation, RelocationType, X86Relocation,
+        },
+
+        ELFContent,
+    };
+
+    /// Relocatable objects of both pointer widths and endiannesses. Built by `tests/fixtures/build.sh`.
+    const ELF32LE: &[u8] = include_bytes!("../../../tests/fixtures/elf32le.o");
+    const ELF32BE: &[u8] = include_bytes!("../../../tests/fixtures/elf32be.o");
+    const ELF64LE: &[u8] = include_bytes!("../../../tests/fixtures/elf64le.o");
+    const ELF64BE: &[u8] = include_bytes!("../../../tests/fixtures/elf64be.o");
+
+    /// Sections of a relocation table, with the offset, symbol, type and addend of its entries.
+    type Table = ((usize, usize, usize), Vec<(usize, usize, RelocationType, Option<i64>)>);
+
+    /// Returns the relocation tables of the given file.
+    fn tables(raw: &[u8]) -> Vec<Table> {
+        let elf = ELFContent::parse(raw.to_vec()).unwrap();
+
+        elf.relocations.iter()
+            .map(|t| ((t.section, t.symtab, t.target), t.entries.iter().map(|r| (r.offset(), r.symbol(), r.rtype(), r.addend())).collect()))
+            .collect()
+    }
+
+    #[test]
+    fn rela() {
+        // A call to `f` in `.text` and a pointer to `main` in `.data`.
+        assert_eq!(tables(ELF64LE), [
+            ((3, 9, 2), vec![(1, 2, RelocationType::AMD64( AMD64Relocation::PLT32 ), Some(-4))]),
+            ((5, 9, 4), vec![(0, 3, RelocationType::AMD64( AMD64Relocation::Abs64 ), Some(0))]),
+        ]);
+
+        // Machines without known relocation types keep the raw type.
+        assert_eq!(tables(ELF64BE), [
+            ((3, 9, 2), vec![(1, 2, RelocationType::Other(4), Some(-4))]),
+            ((5, 9, 4), vec![(0, 3, RelocationType::Other(1), Some(0))]),
+        ]);
+    }
+
+    #[test]
+    fn rel() {
+        // REL entries have no explicit addend.
+        assert_eq!(tables(ELF32LE), [
+            ((3, 9, 2), vec![(1, 2, RelocationType::X86( X86Relocation::PC32 ), None)]),
+            ((5, 9, 4), vec![(0, 3, RelocationType::X86( X86Relocation::Abs32 ), None)]),
+        ]);
+
+        assert_eq!(tables(ELF32BE), [
+            ((3, 9, 2), vec![(1, 2, RelocationType::Other(2), None)]),
+            ((5, 9, 4), vec![(0, 3, RelocationType::Other(1), None)]),
+        ]);
+    }
+}
@@ -0,0 +1,362 @@
+//! Decoders of the packed relocation formats: RELR relative relocations and
+//! Android APS2 packed relocations.
+
+
+
+use crate::{
+    elf::{
+        common::{
+            read::{
+                big32, little32,
+                big64, little64,
+            },
+
+            Endian, InstructionSet, RelocationType,
+        },
+
+        traits::{
+            Relocation,
+        },
+
+        ELFError,
+    },
+};
+
+use super::ELFRelocation;
+
+
+
+/// Magic prefix of Android packed relocation tables.
+const APS2: &[u8] = b"APS2";
+
+/// Maximum number of relocations of an APS2 table. Groups that share the offset delta, `r_info`
+/// and addend encode any number of relocations in a few bytes, so the size of the table does
+/// not bound the number of relocations it declares.
+const APS2_MAX_RELOCATIONS: i64 = 1 << 22;
+
+/// Relocations of a group share the same `r_info`.
+const GROUPED_BY_INFO: i64 = 0x1;
+
+/// Relocations of a group share the same offset delta.
+const GROUPED_BY_OFFSET_DELTA: i64 = 0x2;
+
+/// Relocations of a group share the same addend delta.
+const GROUPED_BY_ADDEND: i64 = 0x4;
+
+/// Relocations of a group have an addend.
+const GROUP_HAS_ADDEND: i64 = 0x8;
+
+
+
+/// Expands a RELR table (`SHT_RELR` or `SHT_ANDROID_RELR`) into relative relocations.
+/// Each even word is the address of a relocation and each odd word is a bitmap of
+/// relocations in the following `8 * word - 1` words.
+pub(crate) fn relr(data: &[u8], (endian, isa, word): (Endian, InstructionSet, usize)) -> Vec<Box<dyn Relocation>> {
+    // Get the read function.
+    let read: fn(&[u8]) -> u64 = match (endian, word) {
+        (Endian::Little, 4) => |b| little32(b) as u64,
+        (Endian::Big, 4) => |b| big32(b) as u64,
+        (Endian::Little, _) => little64,
+        (Endian::Big, _) => big64,
+    };
+
+    // Number of addresses covered by each bitmap.
+    let bits = (8 * word as u64) - 1;
+
+    // List of all relocations.
+    let mut relocations = Vec::new();
+
+    // Next address to relocate.
+    let mut address: u64 = 0;
+
+    for entry in data.chunks_exact(word).map(read) {
+        match entry & 1 {
+            0 => {
+                relocations.push( entry );
+                address = entry.wrapping_add(word as u64);
+            },
+
+            _ => {
+                let bitmap = entry >> 1;
+
+                relocations.extend( (0..bits).filter(|i| (bitmap >> i) & 1 != 0).map(|i| address.wrapping_add(i * word as u64)) );
+
+                address = address.wrapping_add(bits * word as u64);
+            },
+        }
+    }
+
+    let rtype = RelocationType::relative(isa);
+
+    relocations.into_iter()
+        .enumerate()
+        .map(|(index, offset)| entry(word, index, offset, 0, rtype, None))
+        .collect()
+}
+
+/// Expands an Android APS2 packed relocation table (`SHT_ANDROID_REL` or `SHT_ANDROID_RELA`).
+/// The table is a stream of SLEB128 values: the relocation count, the initial offset and
+/// groups of relocations, each with a size, flags and the fields shared by the group.
+pub(crate) fn aps2(data: &[u8], offset: usize, (_, isa, word): (Endian, InstructionSet, usize), addend: bool) -> Result<Vec<Box<dyn Relocation>>, ELFError> {
+    // Error of a malformed table.
+    let bad = ELFError::BadPackedRelocations { offset };
+
+    if !data.starts_with(APS2) {
+        return Err( bad );
+    }
+
+    // Create the SLEB128 reader.
+    let mut reader = Leb128 { data, index: APS2.len() };
+
+    // Get the number of relocations and the initial offset.
+    let count = reader.next().ok_or(bad)?;
+    let mut current = reader.next().ok_or(bad)? as u64;
+
+    if !(0..=APS2_MAX_RELOCATIONS).contains(&count) {
+        return Err( bad );
+    }
+
+    // List of all relocations.
+    let mut relocations = Vec::new();
+
+    // State shared between relocations.
+    let (mut info, mut addend_value) = (0u64, 0i64);
+
+    while relocations.len() < count as usize {
+        // Get the group header.
+        let size  = reader.next().ok_or(bad)?;
+        let flags = reader.next().ok_or(bad)?;
+
+        if (size <= 0) || (size as u64 > (count as u64 - relocations.len() as u64)) {
+            return Err( bad );
+        }
+
+        let delta = match flags & GROUPED_BY_OFFSET_DELTA {
+            0 => None,
+            _ => Some( reader.next().ok_or(bad)? as u64 ),
+        };
+
+        if (flags & GROUPED_BY_INFO) != 0 {
+            info = reader.next().ok_or(bad)? as u64;
+        }
+
+        let hasaddend = (flags & GROUP_HAS_ADDEND) != 0;
+
+        match (hasaddend, (flags & GROUPED_BY_ADDEND) != 0) {
+            (true, true) if !addend => return Err( bad ),
+            (true, true) => addend_value = addend_value.wrapping_add( reader.next().ok_or(bad)? ),
+            (false, _) => addend_value = 0,
+            _ => (),
+        }
+
+        // Get the relocations of the group.
+        for _ in 0..size {
+            current = current.wrapping_add( match delta {
+                Some(delta) => delta,
+                _ => reader.next().ok_or(bad)? as u64,
+            });
+
+            if (flags & GROUPED_BY_INFO) == 0 {
+                info = reader.next().ok_or(bad)? as u64;
+            }
+
+            if hasaddend && ((flags & GROUPED_BY_ADDEND) == 0) {
+                addend_value = addend_value.wrapping_add( reader.next().ok_or(bad)? );
+            }
+
+            // Split the symbol and type according to the word size.
+            let (symbol, rtype) = match word {
+                4 => (info >> 8, info & 0xFF),
+                _ => (info >> 32, info & 0xFFFFFFFF),
+            };
+
+            let index = relocations.len();
+
+            relocations.push( entry(word, index, current, symbol as u32, RelocationType::from( (isa, rtype as u32) ), addend.then_some(addend_value)) );
+        }
+    }
+
+    Ok( relocations )
+}
+
+
+
+/// Internal builder of a relocation entry of the given word size.
+fn entry(word: usize, index: usize, offset: u64, symbol: u32, rtype: RelocationType, addend: Option<i64>) -> Box<dyn Relocation> {
+    match word {
+        4 => Box::new( ELFRelocation::<u32> { index, offset: offset as u32, symbol, rtype, addend: addend.map(|a| a as i32 as i64) } ),
+        _ => Box::new( ELFRelocation::<u64> { index, offset, symbol, rtype, addend } ),
+    }
+}
+
+
+
+/// Internal reader of SLEB128 values.
+struct Leb128<'data> {
+    /// Encoded values.
+    data: &'data [u8],
+
+    /// Offset of the next value.
+    index: usize,
+}
+
+impl<'data> Iterator for Leb128<'data> {
+    type Item = i64;
+
+    fn next(&mut self) -> Option<i64> {
+        let mut value: i64 = 0;
+        let mut shift = 0;
+
+        loop {
+            let byte = *self.data.get(self.index)?;
+            self.index += 1;
+
+            if shift < 64 {
+                value |= ((byte & 0x7F) as i64) << shift;
+            }
+
+            shift += 7;
+
+            if (byte & 0x80) == 0 {
+                // Sign extend the value.
+                if (shift < 64) && ((byte & 0x40) != 0) {
+                    value |= -1i64 << shift;
+                }
+
+                return Some( value );
+            }
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::elf::{
+        common::{
+            AMD64Relocation, Aarch64Relocation,
+        },
+
+        fixture::{
+            Image, Section,
+        },
+    };
+
+    /// Layout of a 64-bit little endian Aarch64 file.
+    const AARCH64: (Endian, InstructionSet, usize) = (Endian::Little, InstructionSet::Aarch64, 8);
+
+    /// Encodes an APS2 table with the given SLEB128 values.
+    fn aps2table(values: &[i64]) -> Vec<u8> {
+        let mut data = Vec::from(APS2);
+
+        for &value in values {
+            let mut value = value;
+
+            loop {
+                let byte = (value & 0x7F) as u8;
+                value >>= 7;
+
+                let last = ((value == 0) && (byte & 0x40) == 0) || ((value == -1) && (byte & 0x40) != 0);
+
+                data.push( if last { byte } else { byte | 0x80 } );
+
+                if last {
+                    break;
+                }
+            }
+        }
+
+        data
+    }
+
+    /// Offset, symbol and addend of the given relocations.
+    fn fields(relocations: &[Box<dyn Relocation>]) -> Vec<(usize, usize, Option<i64>)> {
+        relocations.iter().map(|r| (r.offset(), r.symbol(), r.addend())).collect()
+    }
+
+    #[test]
+    fn relr() {
+        // An address followed by a bitmap of the 1st and 3rd words after it.
+        let data = [0x1000u64, (0b101 << 1) | 1].iter().flat_map(|w| w.to_le_bytes()).collect::<Vec<_>>();
+
+        let relocations = super::relr(&data, (Endian::Little, InstructionSet::AMD64, 8));
+
+        assert_eq!(fields(&relocations), [(0x1000, 0, None), (0x1008, 0, None), (0x1018, 0, None)]);
+        assert!(relocations.iter().all(|r| r.rtype() == RelocationType::AMD64( AMD64Relocation::Relative )));
+    }
+
+    #[test]
+    fn relr32() {
+        // Bitmaps of 32-bit tables cover 31 words, and consecutive bitmaps continue from the previous one.
+        let data = [0x2000u32, (1 << 31) | 1, 0b11].iter().flat_map(|w| w.to_be_bytes()).collect::<Vec<_>>();
+
+        let relocations = super::relr(&data, (Endian::Big, InstructionSet::X86, 4));
+
+        assert_eq!(fields(&relocations).iter().map(|f| f.0).collect::<Vec<_>>(), [0x2000, 0x2004 + (30 * 4), 0x2004 + (31 * 4)]);
+    }
+
+    #[test]
+    fn aps2() {
+        let data = aps2table(&[
+            // Count and initial offset.
+            3, 0x1000,
+
+            // Group of 2 relative relocations 8 bytes apart with their own addends.
+            2, GROUPED_BY_OFFSET_DELTA | GROUPED_BY_INFO | GROUP_HAS_ADDEND, 8, 0x403, 0x10, 0x08,
+
+            // Single relocation with its own offset delta and info, and no addend.
+            1, 0, 0x20, (5 << 32) | 0x401,
+        ]);
+
+        let relocations = super::aps2(&data, 0, AARCH64, true).unwrap();
+
+        assert_eq!(fields(&relocations), [(0x1008, 0, Some(0x10)), (0x1010, 0, Some(0x18)), (0x1030, 5, Some(0))]);
+        assert_eq!(relocations[0].rtype(), RelocationType::Aarch64( Aarch64Relocation::Relative ));
+        assert_eq!(relocations[2].rtype(), RelocationType::Aarch64( Aarch64Relocation::GlobDat ));
+    }
+
+    #[test]
+    fn aps2_malformed() {
+        let bad = Some( ELFError::BadPackedRelocations { offset: 0x100 } );
+
+        let table = aps2table(&[2, 0x1000, 2, GROUPED_BY_OFFSET_DELTA | GROUPED_BY_INFO, 8, 0x403]);
+
+        assert!(super::aps2(&table, 0x100, AARCH64, false).is_ok());
+
+        // Missing magic, truncated streams and group sizes past the declared count.
+        assert_eq!(super::aps2(&table[1..], 0x100, AARCH64, false).err(), bad);
+        assert_eq!(super::aps2(&table[..table.len() - 1], 0x100, AARCH64, false).err(), bad);
+        assert_eq!(super::aps2(&aps2table(&[1, 0x1000, 2, 0]), 0x100, AARCH64, false).err(), bad);
+
+        // Grouped addends in a table without addends.
+        assert_eq!(super::aps2(&aps2table(&[1, 0, 1, GROUP_HAS_ADDEND | GROUPED_BY_ADDEND, 1, 8, 0x403]), 0x100, AARCH64, false).err(), bad);
+    }
+
+    #[test]
+    fn aps2_oversized_count() {
+        // A single fully grouped group would expand into the declared number of relocations.
+        let flags = GROUPED_BY_OFFSET_DELTA | GROUPED_BY_INFO | GROUPED_BY_ADDEND | GROUP_HAS_ADDEND;
+
+        for count in [APS2_MAX_RELOCATIONS + 1, 1 << 40, -1] {
+            let table = aps2table(&[count, 0, count, flags, 8, 0x403, 0]);
+
+            assert_eq!(super::aps2(&table, 0x100, AARCH64, true).err(), Some( ELFError::BadPackedRelocations { offset: 0x100 } ));
+        }
+    }
+
+    #[test]
+    fn malformed_table() {
+        // 64-bit little endian shared object with a SHT_ANDROID_RELA section without the APS2 magic.
+        let raw = Image::new(3, 0xB7)
+            .section( Section { stype: 0x60000002, contents: b"APS3\x01\x00\x01\x00", ..Default::default() } )
+            .build();
+
+        let elf = crate::elf::ELFContent::parse(raw).unwrap();
+
+        assert_eq!(elf.sections.len(), 2);
+        assert!(elf.relocations.is_empty());
+        assert_eq!(elf.errors, [ELFError::BadPackedRelocations { offset: 0x40 }]);
+    }
+}
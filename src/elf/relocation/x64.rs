@@ -0,0 +1,127 @@
+//! 64-bit implementation of an ELF Relocation entry.
+
+
+
+use byteorder::{
+    BigEndian, LittleEndian,
+    ByteOrder,
+};
+
+use crate::{
+    elf::{
+        common::{
+            Endian, InstructionSet, RelocationType,
+        },
+
+        traits::{
+            Relocation,
+        },
+
+        ELFError,
+    },
+};
+
+use super::ELFRelocation;
+
+
+
+impl ELFRelocation<u64> {
+    /// Size of a 64-bit REL entry.
+    const SIZE: usize = 16;
+
+    /// Internal read function.
+    fn read<B: ByteOrder>(data: &[u8]) -> u64 {
+        B::read_u64( data )
+    }
+}
+
+impl Relocation for ELFRelocation<u64> {
+    fn parse(chunk: &[u8], endian: Endian, isa: InstructionSet, addend: bool, index: usize) -> Result<Self, ELFError> {
+        // Check the entry is complete.
+        let expected = Self::entrysize(addend);
+
+        if chunk.len() < expected {
+            return Err( ELFError::Truncated { offset: 0, expected, found: chunk.len() } );
+        }
+
+        // Get the read function.
+        let read: fn(&[u8]) -> u64 = match endian {
+            Endian::Little => Self::read::<LittleEndian>,
+            Endian::Big    => Self::read::<BigEndian>,
+        };
+
+        let s = core::mem::size_of::<u64>();
+
+        // Get the location to relocate.
+        let offset = read( &chunk[0..s] );
+
+        // Get the symbol and type.
+        let info = read( &chunk[s..2*s] );
+
+        let symbol = (info >> 32) as u32;
+        let rtype = RelocationType::from( (isa, (info & 0xFFFFFFFF) as u32) );
+
+        // Get the addend.
+        let addend = match addend {
+            true => Some( read( &chunk[2*s..3*s] ) as i64 ),
+            false => None,
+        };
+
+        Ok( Self {
+            index,
+            offset,
+            symbol,
+            rtype,
+            addend,
+        })
+    }
+
+    fn entrysize(addend: bool) -> usize {
+        match addend {
+            true => Self::SIZE + core::mem::size_of::<u64>(),
+            false => Self::SIZE,
+        }
+    }
+
+    fn index(&self) -> usize {
+        self.index
+    }
+
+    fn offset(&self) -> usize {
+        self.offset as usize
+    }
+
+    fn symbol(&self) -> usize {
+        self.symbol as usize
+    }
+
+    fn rtype(&self) -> RelocationType {
+        self.rtype
+    }
+
+    fn addend(&self) -> Option<i64> {
+        self.addend
+    }
+}
+
+impl core::fmt::Display for ELFRelocation<u64> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        // Build the output string.
+        let mut string = String::from("ELF Relocation\n");
+
+        // Add relocation information.
+        string += &format!("|- Relocation index: {}\n", self.index);
+        string += &format!("|- Type            : {}\n", self.rtype);
+        string += &format!("|- Offset          : 0x{:016X}\n", self.offset);
+
+        match self.addend {
+            Some(addend) => {
+                string += &format!("|- Symbol index    : {}\n", self.symbol);
+                string += &format!("|- Addend          : {}\n\n", addend);
+            },
+            _ => string += &format!("|- Symbol index    : {}\n\n", self.symbol),
+        }
+
+        f.write_str(&string)
+    }
+}
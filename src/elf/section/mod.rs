@@ -10,13 +10,14 @@ mod x64;
 use crate::{
     elf::{
         common::{
-            SectionType, SectionFlags,
+            InstructionSet, SectionType, SectionFlags,
         },
 
-
         traits::{
             Rename,
         },
+
+        ELFError,
     },
 };
 
@@ -71,14 +72,38 @@ impl<T> Rename for ELFSection<T> {
     }
 }
 
+impl<T: Default> ELFSection<T> {
+    /// Creates the header of a new section, with no contents and no name.
+    pub(crate) fn new(index: usize, stype: SectionType, flags: SectionFlags) -> Self {
+        Self {
+            index,
+            strndx: 0,
+            name: String::new(),
+
+            stype, flags,
+
+            vaddr: T::default(), offset: T::default(), size: T::default(),
+            link: 0, info: 0, align: T::default(),
+            entrysize: T::default(),
+        }
+    }
+}
+
 impl<T> ELFSection<T> {
     /// Internal parse function.
-    fn create(chunk: &[u8], read32: fn(&[u8]) -> u32, read: fn(&[u8]) -> T) -> (SectionType, T, [T; 5], [u32; 3]) {
+    fn create(chunk: &[u8], isa: InstructionSet, read32: fn(&[u8]) -> u32, read: fn(&[u8]) -> T) -> Result<(SectionType, T, [T; 5], [u32; 3]), ELFError> {
+        // Check the header is complete.
+        let expected = 0x10 + (6 * core::mem::size_of::<T>());
+
+        if chunk.len() < expected {
+            return Err( ELFError::Truncated { offset: 0, expected, found: chunk.len() } );
+        }
+
         // Get the index to the name string.
         let strndx = read32(&chunk[0x00..0x04]);
 
         // Get the section type.
-        let stype = SectionType::from( read32( &chunk[0x04..0x08] ) );
+        let stype = SectionType::new( read32( &chunk[0x04..0x08] ), isa );
 
         // Start dynamic section.
         let mut i = 0x08;
@@ -115,10 +140,115 @@ impl<T> ELFSection<T> {
         // Get the entry size.
         let entrysize = read( &chunk[i..i+s] );
 
-        (
+        Ok((
             stype, flags,
             [vaddr, offset, size, align, entrysize],
             [strndx, link, info],
-        )
+        ))
+    }
+
+    /// Internal serialize function.
+    fn store(chunk: &mut [u8], write32: fn(&mut [u8], u32), write: fn(&mut [u8], T), [strndx, stype, link, info]: [u32; 4], [flags, vaddr, offset, size, align, entrysize]: [T; 6]) {
+        // Write the index to the name string and the section type.
+        write32( &mut chunk[0x00..0x04], strndx );
+        write32( &mut chunk[0x04..0x08], stype );
+
+        // Start dynamic section.
+        let mut i = 0x08;
+        let s = core::mem::size_of::<T>();
+
+        // Write the flags, virtual address, image offset and size.
+        write( &mut chunk[i..i+s], flags );
+        i += s;
+
+        write( &mut chunk[i..i+s], vaddr );
+        i += s;
+
+        write( &mut chunk[i..i+s], offset );
+        i += s;
+
+        write( &mut chunk[i..i+s], size );
+        i += s;
+
+        // Write the link index and the section information.
+        write32( &mut chunk[i..i+4], link );
+        i += 4;
+
+        write32( &mut chunk[i..i+4], info );
+        i += 4;
+
+        // Write the address alignment and the entry size.
+        write( &mut chunk[i..i+s], align );
+        i += s;
+
+        write( &mut chunk[i..i+s], entrysize );
+    }
+}
+
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::elf::ELFContent;
+
+    /// Shared library with hash, version and TLS sections. Built by `tests/fixtures/build.sh`.
+    const LIBRARY: &[u8] = include_bytes!("../../../tests/fixtures/libsample.so");
+
+    /// Relocatable object with a section group and a compressed section. Built by `tests/fixtures/build.sh`.
+    const OBJECT: &[u8] = include_bytes!("../../../tests/fixtures/elf64le.o");
+
+    #[test]
+    fn types() {
+        let elf = ELFContent::parse(LIBRARY.to_vec()).unwrap();
+        let stype = |name| elf.sections[ elf.find_section(name).unwrap() ].stype();
+
+        assert_eq!(stype(".hash"), SectionType::Hash);
+        assert_eq!(stype(".gnu.hash"), SectionType::GNUHash);
+        assert_eq!(stype(".gnu.version"), SectionType::GNUVersionSymbol);
+        assert_eq!(stype(".gnu.version_d"), SectionType::GNUVersionDefinition);
+        assert_eq!(stype(".gnu.version_r"), SectionType::GNUVersionNeed);
+        assert_eq!(stype(".init_array"), SectionType::Init);
+        assert_eq!(stype(".tbss"), SectionType::Bss);
+        assert_eq!(stype(".gnu.hash").value(), 0x6FFFFFF6);
+
+        let elf = ELFContent::parse(OBJECT.to_vec()).unwrap();
+        assert_eq!(elf.sections[1].stype(), SectionType::Group);
+    }
+
+    #[test]
+    fn processor_types() {
+        // The same value is decoded according to the Instruction Set.
+        assert_eq!(SectionType::new(0x70000003, InstructionSet::Aarch32), SectionType::ARMAttributes);
+        assert_eq!(SectionType::new(0x70000003, InstructionSet::RISCV), SectionType::RISCVAttributes);
+        assert_eq!(SectionType::new(0x70000001, InstructionSet::AMD64), SectionType::AMD64Unwind);
+        assert_eq!(SectionType::new(0x70000001, InstructionSet::Aarch32), SectionType::ARMExceptionIndex);
+        assert_eq!(SectionType::new(0x70000003, InstructionSet::AMD64), SectionType::Processor(0x70000003));
+        assert_eq!(SectionType::new(0x70000003, InstructionSet::RISCV).value(), 0x70000003);
+    }
+
+    #[test]
+    fn flags() {
+        let elf = ELFContent::parse(LIBRARY.to_vec()).unwrap();
+        let flags = |name| elf.sections[ elf.find_section(name).unwrap() ].flags();
+
+        assert_eq!(flags(".text").to_string(), "Alloc + Exec");
+        assert_eq!(flags(".tbss").to_string(), "Write + Alloc + TLS");
+        assert_eq!(flags(".rela.plt").to_string(), "Alloc + InfoLink");
+        assert_eq!(flags(".comment").to_string(), "Merge + Strings");
+        assert_eq!(flags(".symtab").to_string(), "----");
+
+        // Group members and compressed sections.
+        let elf = ELFContent::parse(OBJECT.to_vec()).unwrap();
+
+        assert!(elf.sections[ elf.find_section(".text.f").unwrap() ].flags().group());
+        assert!(elf.sections[ elf.find_section(".debug_str").unwrap() ].flags().compressed());
+
+        // Processor specific flags only apply to their Instruction Set.
+        assert!(SectionFlags(0x10000000, InstructionSet::AMD64).large());
+        assert!(!SectionFlags(0x10000000, InstructionSet::Aarch32).large());
+        assert!(SectionFlags(0x20000000, InstructionSet::Aarch32).purecode());
+        assert_eq!(SectionFlags(0x80200000, InstructionSet::AMD64).to_string(), "Retain + Exclude");
     }
 }
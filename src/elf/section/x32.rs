@@ -14,12 +14,14 @@ use crate::{
                 big32, little32,
             },
 
-            Endian, SectionFlags, SectionType,
+            Endian, InstructionSet, SectionFlags, SectionType,
         },
 
         traits::{
             SectionHeader,
         },
+
+        ELFError,
     },
 };
 
@@ -32,31 +34,44 @@ impl ELFSection<u32> {
     fn read<B: ByteOrder>(data: &[u8]) -> u32 {
         B::read_u32( data )
     }
+
+    /// Internal serialize function.
+    fn serialize<B: ByteOrder>(&self, chunk: &mut [u8], strndx: usize, (offset, size): (usize, usize)) {
+        Self::store(
+            chunk, B::write_u32, B::write_u32,
+            [strndx as u32, self.stype.value(), self.link, self.info],
+            [self.flags.0 as u32, self.vaddr, offset as u32, size as u32, self.align, self.entrysize],
+        );
+    }
 }
 
 
 impl SectionHeader for ELFSection<u32> {
-    fn parse(chunk: &[u8], endian: Endian, index: usize) -> Self where Self: Sized {
+    fn parse(chunk: &[u8], endian: Endian, isa: InstructionSet, index: usize) -> Result<Self, ELFError> where Self: Sized {
         // Get the read fucntions.
         let (read32, read): (fn(&[u8]) -> u32, fn(&[u8]) -> u32) = match endian {
             Endian::Little => (little32, Self::read::<LittleEndian>),
             Endian::Big => (big32, Self::read::<BigEndian>),
         };
 
-        let (stype, flags, [vaddr, offset, size, align, entrysize], [strndx, link, info]) = Self::create(chunk, read32, read);
+        let (stype, flags, [vaddr, offset, size, align, entrysize], [strndx, link, info]) = Self::create(chunk, isa, read32, read)?;
 
         // Build the section.
-        Self {
+        Ok( Self {
             index,
             strndx: strndx as usize,
             name: String::new(),
 
-            stype, flags: SectionFlags(flags as u64),
+            stype, flags: SectionFlags(flags as u64, isa),
 
             vaddr, offset, size,
             link, info, align,
             entrysize,
-        }
+        })
+    }
+
+    fn index(&self) -> usize {
+        self.index
     }
 
     fn name(&self) -> &str {
@@ -74,6 +89,53 @@ impl SectionHeader for ELFSection<u32> {
     fn stype(&self) -> SectionType {
         self.stype
     }
+
+    fn entrysize(&self) -> usize {
+        self.entrysize as usize
+    }
+
+    fn flags(&self) -> SectionFlags {
+        self.flags
+    }
+
+    fn align(&self) -> usize {
+        self.align as usize
+    }
+
+    fn link(&self) -> usize {
+        self.link as usize
+    }
+
+    fn info(&self) -> usize {
+        self.info as usize
+    }
+
+    fn setindex(&mut self, index: usize) {
+        self.index = index;
+    }
+
+    fn setflags(&mut self, flags: SectionFlags) {
+        self.flags = flags;
+    }
+
+    fn setalign(&mut self, align: usize) {
+        self.align = align as u32;
+    }
+
+    fn setlink(&mut self, link: usize) {
+        self.link = link as u32;
+    }
+
+    fn setinfo(&mut self, info: usize) {
+        self.info = info as u32;
+    }
+
+    fn write(&self, chunk: &mut [u8], endian: Endian, strndx: usize, phys: (usize, usize)) {
+        match endian {
+            Endian::Little => self.serialize::<LittleEndian>(chunk, strndx, phys),
+            Endian::Big => self.serialize::<BigEndian>(chunk, strndx, phys),
+        }
+    }
 }
 
 impl core::fmt::Display for ELFSection<u32> {
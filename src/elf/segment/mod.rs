@@ -0,0 +1,124 @@
+//! ELF Program Header parsing and interaction.
+
+
+
+mod x32;
+mod x64;
+
+
+
+use crate::{
+    elf::{
+        common::{
+            SegmentFlags, SegmentType,
+        },
+    },
+};
+
+
+
+/// Read functions of the 32-bit fields and of the word sized fields of a Program Header.
+type Readers<T> = (fn(&[u8]) -> u32, fn(&[u8]) -> T);
+
+
+
+/// Contains an ELF Program Header.
+pub struct ELFSegment<T> {
+    /// Segment index.
+    index: usize,
+
+    /// Segment type.
+    stype: SegmentType,
+
+    /// Segment flags.
+    flags: SegmentFlags,
+
+    /// Offset of the segment in the file image.
+    offset: T,
+
+    /// Virtual address of the segment in memory.
+    vaddr: T,
+
+    /// Physical address of the segment, on systems where it is relevant.
+    paddr: T,
+
+    /// Size of the segment in the file image.
+    filesize: T,
+
+    /// Size of the segment in memory.
+    memsize: T,
+
+    /// Alignment of the segment.
+    align: T,
+}
+
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::elf::{
+        common::Endian,
+        traits::ProgramHeader,
+        ELFContent,
+    };
+
+    /// Shared library with loadable, dynamic, note, TLS and GNU segments. Built by `tests/fixtures/build.sh`.
+    const LIBRARY: &[u8] = include_bytes!("../../../tests/fixtures/libsample.so");
+
+    #[test]
+    fn segments() {
+        let elf = ELFContent::parse(LIBRARY.to_vec()).unwrap();
+
+        let types = elf.segments.iter().map(|s| s.stype()).collect::<Vec<_>>();
+
+        assert_eq!(types, [
+            SegmentType::Load, SegmentType::Load, SegmentType::Dynamic, SegmentType::Note,
+            SegmentType::TLS, SegmentType::GNUStack, SegmentType::GNURelRO,
+        ]);
+
+        // Text and data loadable segments.
+        let (text, data) = (&elf.segments[0], &elf.segments[1]);
+
+        assert_eq!((text.phys(), text.virt(), text.align()), ((0, 0x784), (0, 0x784), 0x1000));
+        assert_eq!(text.flags().to_string(), "Read + Exec");
+        assert_eq!((data.phys(), data.virt()), ((0xD80, 0x28C), (0x1D80, 0x290)));
+        assert!(data.flags().read() && data.flags().write() && !data.flags().exec());
+
+        // The dynamic segment maps the `.dynamic` section.
+        let dynamic = &elf.sections[ elf.find_section(".dynamic").unwrap() ];
+        assert_eq!(elf.segments[2].phys(), dynamic.phys());
+
+        // TLS template without initialized data.
+        assert_eq!((elf.segments[4].phys().1, elf.segments[4].virt().1, elf.segments[4].align()), (0, 4, 4));
+        assert_eq!(elf.segments[6].flags(), SegmentFlags(4));
+    }
+
+    #[test]
+    fn parse32() {
+        // Big endian loadable segment of 0x100 file bytes and 0x200 memory bytes.
+        let mut chunk = [0u8; 0x20];
+
+        chunk[0x00..0x04].copy_from_slice(&1u32.to_be_bytes());
+        chunk[0x04..0x08].copy_from_slice(&0x1000u32.to_be_bytes());
+        chunk[0x08..0x0C].copy_from_slice(&0x8000u32.to_be_bytes());
+        chunk[0x0C..0x10].copy_from_slice(&0x9000u32.to_be_bytes());
+        chunk[0x10..0x14].copy_from_slice(&0x100u32.to_be_bytes());
+        chunk[0x14..0x18].copy_from_slice(&0x200u32.to_be_bytes());
+        chunk[0x18..0x1C].copy_from_slice(&6u32.to_be_bytes());
+        chunk[0x1C..0x20].copy_from_slice(&0x10u32.to_be_bytes());
+
+        let segment = ELFSegment::<u32>::parse(&chunk, Endian::Big, 3).unwrap();
+
+        assert_eq!((segment.index(), segment.stype()), (3, SegmentType::Load));
+        assert_eq!((segment.phys(), segment.virt(), segment.paddr()), ((0x1000, 0x100), (0x8000, 0x200), 0x9000));
+        assert_eq!((segment.flags().to_string(), segment.align()), (String::from("Read + Write"), 0x10));
+
+        // The header is written back unchanged.
+        let mut out = [0u8; 0x20];
+        segment.write(&mut out, Endian::Big);
+
+        assert_eq!(out, chunk);
+    }
+}
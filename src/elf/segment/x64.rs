@@ -0,0 +1,156 @@
+//! 64-bit implementation of an ELF Program Header.
+
+
+
+use byteorder::{
+    BigEndian, LittleEndian,
+    ByteOrder,
+};
+
+use crate::{
+    elf::{
+        common::{
+            read::{
+                big32, little32,
+            },
+
+            Endian, SegmentFlags, SegmentType,
+        },
+
+        traits::{
+            ProgramHeader,
+        },
+
+        ELFError,
+    },
+};
+
+use super::{
+    ELFSegment, Readers,
+};
+
+
+
+impl ELFSegment<u64> {
+    /// Size of a 64-bit Program Header.
+    const SIZE: usize = 0x38;
+
+    /// Internal read function.
+    fn read<B: ByteOrder>(data: &[u8]) -> u64 {
+        B::read_u64( data )
+    }
+
+    /// Internal serialize function.
+    fn serialize<B: ByteOrder>(&self, chunk: &mut [u8]) {
+        B::write_u32( &mut chunk[0x00..0x04], self.stype.value() );
+        B::write_u32( &mut chunk[0x04..0x08], self.flags.0 );
+        B::write_u64( &mut chunk[0x08..0x10], self.offset );
+        B::write_u64( &mut chunk[0x10..0x18], self.vaddr );
+        B::write_u64( &mut chunk[0x18..0x20], self.paddr );
+        B::write_u64( &mut chunk[0x20..0x28], self.filesize );
+        B::write_u64( &mut chunk[0x28..0x30], self.memsize );
+        B::write_u64( &mut chunk[0x30..0x38], self.align );
+    }
+}
+
+impl ProgramHeader for ELFSegment<u64> {
+    fn parse(chunk: &[u8], endian: Endian, index: usize) -> Result<Self, ELFError> where Self: Sized {
+        // Check the header is complete.
+        if chunk.len() < Self::SIZE {
+            return Err( ELFError::Truncated { offset: 0, expected: Self::SIZE, found: chunk.len() } );
+        }
+
+        // Get the read functions.
+        let (read32, read): Readers<u64> = match endian {
+            Endian::Little => (little32, Self::read::<LittleEndian>),
+            Endian::Big => (big32, Self::read::<BigEndian>),
+        };
+
+        // Get the segment type.
+        let stype = SegmentType::from( read32( &chunk[0x00..0x04] ) );
+
+        // Get the flags.
+        let flags = SegmentFlags( read32( &chunk[0x04..0x08] ) );
+
+        // Get the image offset.
+        let offset = read( &chunk[0x08..0x10] );
+
+        // Get the virtual and physical addresses.
+        let vaddr = read( &chunk[0x10..0x18] );
+        let paddr = read( &chunk[0x18..0x20] );
+
+        // Get the file and memory sizes.
+        let filesize = read( &chunk[0x20..0x28] );
+        let memsize  = read( &chunk[0x28..0x30] );
+
+        // Get the alignment.
+        let align = read( &chunk[0x30..0x38] );
+
+        // Build the segment.
+        Ok( Self {
+            index,
+            stype, flags,
+            offset, vaddr, paddr,
+            filesize, memsize,
+            align,
+        })
+    }
+
+    fn index(&self) -> usize {
+        self.index
+    }
+
+    fn stype(&self) -> SegmentType {
+        self.stype
+    }
+
+    fn flags(&self) -> SegmentFlags {
+        self.flags
+    }
+
+    fn phys(&self) -> (usize, usize) {
+        (self.offset as usize, self.filesize as usize)
+    }
+
+    fn virt(&self) -> (usize, usize) {
+        (self.vaddr as usize, self.memsize as usize)
+    }
+
+    fn paddr(&self) -> usize {
+        self.paddr as usize
+    }
+
+    fn align(&self) -> usize {
+        self.align as usize
+    }
+
+    fn write(&self, chunk: &mut [u8], endian: Endian) {
+        match endian {
+            Endian::Little => self.serialize::<LittleEndian>(chunk),
+            Endian::Big => self.serialize::<BigEndian>(chunk),
+        }
+    }
+}
+
+impl core::fmt::Display for ELFSegment<u64> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        // Build the output string.
+        let mut string = String::from("ELF Program Header\n");
+
+        // Add segment information.
+        string += &format!("|- Segment index: {}\n", self.index);
+        string += &format!("|- Segment type : {}\n", self.stype);
+        string += &format!("|- Segment flags: {}\n", self.flags);
+
+        // Add address information.
+        string += "|- Segment Addressing Information\n";
+        string += &format!("   |- Virtual Address : 0x{:016X}\n", self.vaddr);
+        string += &format!("   |- Physical Address: 0x{:016X}\n", self.paddr);
+        string += &format!("   |- File offset     : 0x{:016X}\n", self.offset);
+        string += &format!("   |- File size       : {:.2} MiB | {:.2} kiB | {} B \n", self.filesize as f64 / (1024.0 * 1024.0), self.filesize as f64 / 1024.0, self.filesize);
+        string += &format!("   |- Memory size     : {:.2} MiB | {:.2} kiB | {} B \n", self.memsize as f64 / (1024.0 * 1024.0), self.memsize as f64 / 1024.0, self.memsize);
+        string += &format!("   |- Alignment       : {} bytes\n\n", self.align);
+
+        f.write_str(&string)
+    }
+}
@@ -0,0 +1,279 @@
+//! Address to symbol resolution index.
+
+
+
+use crate::{
+    elf::{
+        common::{
+            SectionType, SymbolBind, SymbolSection, SymbolType,
+        },
+
+        traits::{
+            SectionHeader, Symbol,
+        },
+    },
+};
+
+use std::{
+    cmp::{
+        Reverse,
+    },
+
+    collections::{
+        BinaryHeap,
+    },
+};
+
+use super::ELFSymbolTable;
+
+
+
+/// Sorted index of the address ranges covered by the function and object symbols of a file.
+/// Overlaps are resolved when the index is built, so each query is a binary search.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ELFSymbolIndex {
+    /// Disjoint address ranges as (start, end, table, symbol, address of the symbol), sorted by
+    /// start address. `table` indexes the symbol tables of the file and `symbol` its symbols.
+    ranges: Vec<(usize, usize, usize, usize, usize)>,
+}
+
+impl ELFSymbolIndex {
+    /// Builds the index from the static symbol table, or the dynamic symbol table of stripped files.
+    ///
+    /// When several symbols cover an address, the innermost one (latest start, then smallest size)
+    /// is chosen, and aliases prefer global over weak over local names, then default versions.
+    /// Zero sized symbols cover the addresses up to the next symbol or the end of their section,
+    /// but only where no sized symbol does.
+    pub fn new(tables: &[ELFSymbolTable], sections: &[Box<dyn SectionHeader>]) -> Self {
+        // Get the symbol table to index.
+        let table = match tables.iter().position(|t| t.stype == SectionType::SymbolTable) {
+            Some(table) => table,
+            _ => match tables.iter().position(|t| t.stype == SectionType::DynamicSymbol) {
+                Some(table) => table,
+                _ => return Self::default(),
+            },
+        };
+
+        // Get the named, defined function and object symbols.
+        let symbols = tables[table].symbols.iter()
+            .enumerate()
+            .filter(|(_, s)| matches!(s.stype(), SymbolType::Function | SymbolType::Object | SymbolType::GNUIndirectFunction))
+            .filter(|(_, s)| matches!(s.section(), SymbolSection::Index(_)) && !s.name().is_empty())
+            .map(|(i, s)| (i, s.address(), s.size(), s.shndx(), Self::rank(s.as_ref())))
+            .collect::<Vec<_>>();
+
+        // Get the sorted start addresses, and the sorted intervals of the sized symbols,
+        // to bound the zero sized symbols.
+        let mut starts = symbols.iter().map(|&(_, a, ..)| a).collect::<Vec<_>>();
+        starts.sort_unstable();
+        starts.dedup();
+
+        let mut sized = symbols.iter()
+            .filter(|&&(_, _, s, ..)| s != 0)
+            .map(|&(_, a, s, ..)| (a, a.saturating_add(s)))
+            .collect::<Vec<_>>();
+
+        sized.sort_unstable();
+
+        // Get the covered interval of each symbol as (start, end, sized, index, rank).
+        let mut intervals = symbols.iter()
+            .map(|&(i, address, size, section, rank)| match size {
+                0 => {
+                    // Up to the next symbol.
+                    let next = starts.partition_point(|s| *s <= address);
+                    let mut end = starts.get(next).copied().unwrap_or(usize::MAX);
+
+                    // Up to the end of the section.
+                    if let Some((vaddr, vsize)) = sections.get(section).map(|s| s.virt()) {
+                        if (vaddr <= address) && (address - vaddr < vsize) {
+                            end = end.min(vaddr.saturating_add(vsize));
+                        }
+                    }
+
+                    // Up to the end of the sized aliases of the symbol.
+                    let first = sized.partition_point(|&(a, _)| a < address);
+                    let last = sized.partition_point(|&(a, _)| a <= address);
+
+                    if let Some(alias) = sized[first..last].iter().map(|&(_, e)| e).max() {
+                        end = end.min(alias);
+                    }
+
+                    (address, end, false, i, rank)
+                },
+
+                _ => (address, address.saturating_add(size), true, i, rank),
+            })
+            .filter(|&(start, end, ..)| start < end)
+            .collect::<Vec<_>>();
+
+        intervals.sort_unstable_by_key(|&(start, ..)| start);
+
+        // Get the boundaries of the elementary ranges.
+        let mut points = intervals.iter()
+            .flat_map(|&(start, end, ..)| [start, end])
+            .collect::<Vec<_>>();
+
+        points.sort_unstable();
+        points.dedup();
+
+        // Sweep the boundaries, keeping the preferred active interval on top of the heap.
+        let mut active = BinaryHeap::new();
+        let mut ranges: Vec<(usize, usize, usize, usize, usize)> = Vec::new();
+        let mut next = 0;
+
+        for window in points.windows(2) {
+            let (start, end) = (window[0], window[1]);
+
+            // Add the intervals starting here.
+            while let Some(&(s, e, sized, i, rank)) = intervals.get(next) {
+                if s > start {
+                    break;
+                }
+
+                active.push( (sized, s, Reverse(e), rank, Reverse(i), e) );
+                next += 1;
+            }
+
+            // Remove the intervals that already ended.
+            while active.peek().is_some_and(|top| top.5 <= start) {
+                active.pop();
+            }
+
+            // Extend the previous range or start a new one.
+            if let Some(&(_, address, _, _, Reverse(i), _)) = active.peek() {
+                match ranges.last_mut() {
+                    Some(last) if (last.1 == start) && (last.3 == i) => last.1 = end,
+                    _ => ranges.push( (start, end, table, i, address) ),
+                }
+            }
+        }
+
+        Self { ranges }
+    }
+
+    /// Returns the table index, symbol index and offset into the symbol of the given address.
+    pub fn symbolize(&self, address: usize) -> Option<(usize, usize, usize)> {
+        let i = self.ranges.partition_point(|&(start, ..)| start <= address).checked_sub(1)?;
+        let (_, end, table, symbol, start) = self.ranges[i];
+
+        if address >= end {
+            return None;
+        }
+
+        Some( (table, symbol, address - start) )
+    }
+
+    /// Returns the number of disjoint address ranges of the index.
+    pub fn len(&self) -> usize {
+        self.ranges.len()
+    }
+
+    /// Returns `true` if the index covers no addresses.
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Internal preference of a symbol among its aliases: by binding, then default versions
+    /// over hidden ones.
+    fn rank(symbol: &dyn Symbol) -> u8 {
+        let binding = match symbol.binding() {
+            SymbolBind::Global => 3,
+            SymbolBind::Weak | SymbolBind::GNUUnique => 2,
+            SymbolBind::Local => 1,
+            _ => 0,
+        };
+
+        let default = match symbol.version() {
+            Some(version) => !version.hidden as u8,
+            _ => 1,
+        };
+
+        (binding * 2) + default
+    }
+}
+
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::elf::{
+        common::Endian,
+        traits::Rename,
+        ELFSymbol,
+    };
+
+    /// Builds a 64-bit little endian symbol of section 1 with the given info byte and range.
+    fn symbol(name: &str, info: u8, address: u64, size: u64) -> Box<dyn Symbol> {
+        let mut chunk = [0u8; 24];
+
+        chunk[4] = info;
+        chunk[6..8].copy_from_slice(&1u16.to_le_bytes());
+        chunk[8..16].copy_from_slice(&address.to_le_bytes());
+        chunk[16..24].copy_from_slice(&size.to_le_bytes());
+
+        let mut symbol = ELFSymbol::<u64>::parse(&chunk, Endian::Little).unwrap();
+        symbol.setname( String::from(name) );
+
+        Box::new( symbol )
+    }
+
+    /// Builds the index of a static symbol table with a nested function, a weak alias and a zero sized symbol.
+    fn index() -> ELFSymbolIndex {
+        let symbols = vec![
+            symbol("NULL", 0x00, 0, 0),
+            symbol("f", 0x12, 0x1000, 0x10),
+            symbol("g", 0x12, 0x1020, 0x10),
+            symbol("inner", 0x02, 0x1024, 4),
+            symbol("label", 0x12, 0x1040, 0),
+            symbol("h", 0x11, 0x1050, 8),
+            symbol("alias", 0x22, 0x1000, 0x10),
+        ];
+
+        let table = ELFSymbolTable { section: 2, stype: SectionType::SymbolTable, strtab: 3, symbols };
+
+        ELFSymbolIndex::new(&[table], &[])
+    }
+
+    #[test]
+    fn symbolize() {
+        let index = index();
+
+        // Global symbols are preferred over their weak aliases.
+        assert_eq!(index.symbolize(0x1000), Some((0, 1, 0)));
+        assert_eq!(index.symbolize(0x100F), Some((0, 1, 0xF)));
+
+        // Nested symbols cover their range of the outer symbol.
+        assert_eq!(index.symbolize(0x1022), Some((0, 2, 2)));
+        assert_eq!(index.symbolize(0x1025), Some((0, 3, 1)));
+        assert_eq!(index.symbolize(0x1028), Some((0, 2, 8)));
+
+        // Zero sized symbols cover the addresses up to the next symbol.
+        assert_eq!(index.symbolize(0x1048), Some((0, 4, 8)));
+        assert_eq!(index.symbolize(0x1050), Some((0, 5, 0)));
+    }
+
+    #[test]
+    fn unsymbolized() {
+        let index = index();
+
+        // Before the first symbol.
+        assert_eq!(index.symbolize(0), None);
+        assert_eq!(index.symbolize(0x0FFF), None);
+
+        // Between symbols.
+        assert_eq!(index.symbolize(0x1010), None);
+        assert_eq!(index.symbolize(0x101F), None);
+
+        // Past the end of the last symbol.
+        assert_eq!(index.symbolize(0x1058), None);
+        assert_eq!(index.symbolize(usize::MAX), None);
+    }
+
+    #[test]
+    fn empty() {
+        assert!(ELFSymbolIndex::new(&[], &[]).is_empty());
+        assert_eq!(ELFSymbolIndex::new(&[], &[]).symbolize(0), None);
+    }
+}
@@ -2,20 +2,25 @@
 
 
 
+mod index;
 mod x32;
 mod x64;
 
 
 
+pub use index::ELFSymbolIndex;
+
 use crate::{
     elf::{
         common::{
-            SymbolBind, SymbolType,
+            SectionType, SymbolBind, SymbolSection, SymbolType, SymbolVisibility,
         },
 
         traits::{
-            Rename
+            Rename, Symbol,
         },
+
+        SymbolVersion,
     },
 };
 
@@ -35,16 +40,26 @@ pub struct ELFSymbol<T> {
     binding: SymbolBind,
 
     /// Visibility of the symbol.
-    visibility: u8,
+    visibility: SymbolVisibility,
+
+    /// Raw type and binding (`st_info`) and visibility (`st_other`) bytes, kept to write the symbol back.
+    raw: (u8, u8),
 
-    /// Section index relativity.
-    relativity: u16,
+    /// Section the symbol is defined relative to.
+    /// Resolved from the extended section index table when it is `SHN_XINDEX`.
+    section: SymbolSection,
+
+    /// Name of the section the symbol is defined in.
+    sectionname: Option<String>,
 
     /// Value of the symbol.
     value: T,
 
     /// Size of the symbol.
     size: T,
+
+    /// GNU version of the symbol.
+    version: Option<SymbolVersion>,
 }
 
 impl<T> Rename for ELFSymbol<T> {
@@ -56,3 +71,143 @@ impl<T> Rename for ELFSymbol<T> {
         self.name = name;
     }
 }
+
+
+
+/// Contains a symbol table and the string table its names come from.
+pub struct ELFSymbolTable {
+    /// Index of the symbol table section.
+    pub section: usize,
+
+    /// Type of the symbol table (`SymbolTable` or `DynamicSymbol`).
+    pub stype: SectionType,
+
+    /// Index of the associated string table (`sh_link`).
+    pub strtab: usize,
+
+    /// Symbols of the table.
+    pub symbols: Vec<Box<dyn Symbol>>,
+}
+
+impl core::fmt::Display for ELFSymbolTable {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        // Build the output string.
+        let mut string = String::from("ELF Symbol Table\n");
+
+        // Add table information.
+        string += &format!("|- Symbol table index: {}\n", self.section);
+        string += &format!("|- Table type        : {}\n", self.stype);
+        string += &format!("|- String table index: {}\n", self.strtab);
+        string += &format!("|- Number of symbols : {}\n\n", self.symbols.len());
+
+        // Add all the symbols, demangled in the alternate format.
+        string += &self.symbols.iter()
+            .fold(String::new(), |out, s| match f.alternate() {
+                true => out + &format!("{:#}", s),
+                false => out + &format!("{}", s),
+            });
+
+        f.write_str(&string)
+    }
+}
+
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::elf::ELFContent;
+
+    /// Shared library with a static and a dynamic symbol table. Built by `tests/fixtures/build.sh`.
+    const LIBRARY: &[u8] = include_bytes!("../../../tests/fixtures/libsample.so");
+
+    /// Returns the names of the symbols of the given table.
+    fn names(table: &ELFSymbolTable) -> Vec<&str> {
+        table.symbols.iter().map(|s| s.name()).collect()
+    }
+
+    #[test]
+    fn tables() {
+        let elf = ELFContent::parse(LIBRARY.to_vec()).unwrap();
+
+        assert_eq!(elf.symbols.len(), 2);
+
+        // Each table is tied to the string table of its own `sh_link`.
+        let (dynsym, symtab) = (elf.dynsym().unwrap(), elf.symtab().unwrap());
+
+        assert_eq!((dynsym.section, dynsym.strtab, dynsym.symbols.len()), (4, 5, 14));
+        assert_eq!((symtab.section, symtab.strtab, symtab.symbols.len()), (25, 26, 34));
+        assert_eq!((elf.sections[dynsym.strtab].name(), elf.sections[symtab.strtab].name()), (".dynstr", ".strtab"));
+
+        assert_eq!(names(dynsym)[7..], ["copy", "LIB_1.0", "LIB_2.0", "copy", "increment", "counter", "local"]);
+
+        // Local symbols are only in the static symbol table.
+        assert!(names(symtab).contains(&"copy_new"));
+        assert!(!names(dynsym).contains(&"copy_new"));
+    }
+
+    #[test]
+    fn stripped() {
+        let mut raw = LIBRARY.to_vec();
+
+        // Turn `.symtab` into plain data, as if the file was stripped.
+        let shoff = u64::from_le_bytes( raw[0x28..0x30].try_into().unwrap() ) as usize;
+        let stype = shoff + (25 * 64) + 4;
+        raw[stype..stype+4].copy_from_slice(&1u32.to_le_bytes());
+
+        let elf = ELFContent::parse(raw).unwrap();
+
+        assert!(elf.symtab().is_none());
+        assert_eq!(elf.symbols.len(), 1);
+        assert_eq!(elf.dynsym().unwrap().symbols[11].name(), "increment");
+    }
+
+    /// Returns the symbol with the given name of the static symbol table.
+    fn symbol<'a>(elf: &'a ELFContent, name: &str) -> &'a dyn Symbol {
+        elf.symtab().unwrap().symbols.iter().find(|s| s.name() == name).unwrap().as_ref()
+    }
+
+    #[test]
+    fn attributes() {
+        let elf = ELFContent::parse(LIBRARY.to_vec()).unwrap();
+
+        // Type, binding, visibility, section and section name.
+        let fields = |name| {
+            let s = symbol(&elf, name);
+            (s.stype(), s.binding(), s.visibility(), s.section(), s.sectionname().map(String::from))
+        };
+
+        assert_eq!(fields("increment"), (SymbolType::Function, SymbolBind::Global, SymbolVisibility::Default, SymbolSection::Index(14), Some(String::from(".text"))));
+        assert_eq!(fields("counter"), (SymbolType::Object, SymbolBind::Global, SymbolVisibility::Default, SymbolSection::Index(22), Some(String::from(".data"))));
+        assert_eq!(fields("local"), (SymbolType::TLS, SymbolBind::Global, SymbolVisibility::Default, SymbolSection::Index(17), Some(String::from(".tbss"))));
+        assert_eq!(fields("copy_new"), (SymbolType::Function, SymbolBind::Local, SymbolVisibility::Default, SymbolSection::Index(14), Some(String::from(".text"))));
+
+        // Version definition symbols are absolute and imports are undefined.
+        assert_eq!(fields("LIB_1.0"), (SymbolType::Object, SymbolBind::Global, SymbolVisibility::Default, SymbolSection::Absolute, None));
+        assert_eq!(fields("memcpy@GLIBC_2.14"), (SymbolType::Function, SymbolBind::Global, SymbolVisibility::Default, SymbolSection::Undefined, None));
+        assert_eq!(fields("__gmon_start__"), (SymbolType::None, SymbolBind::Weak, SymbolVisibility::Default, SymbolSection::Undefined, None));
+        assert_eq!(fields("library.c").0, SymbolType::File);
+    }
+
+    #[test]
+    fn extended_attributes() {
+        let mut raw = LIBRARY.to_vec();
+        let elf = ELFContent::parse(raw.clone()).unwrap();
+
+        // Symbols 24 (`increment`) and 29 (`counter`) of `.symtab`.
+        let offset = elf.sections[ elf.symtab().unwrap().section ].phys().0;
+        let entry = |index: usize| offset + (index * 24);
+
+        // GNU indirect function of unique binding, and hidden and protected visibilities.
+        raw[entry(24) + 4] = (10 << 4) | 10;
+        raw[entry(24) + 5] = 3;
+        raw[entry(29) + 5] = 2;
+
+        let elf = ELFContent::parse(raw).unwrap();
+
+        let increment = symbol(&elf, "increment");
+        assert_eq!((increment.stype(), increment.binding(), increment.visibility()), (SymbolType::GNUIndirectFunction, SymbolBind::GNUUnique, SymbolVisibility::Protected));
+        assert_eq!(symbol(&elf, "counter").visibility(), SymbolVisibility::Hidden);
+    }
+}
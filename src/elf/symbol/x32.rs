@@ -15,12 +15,15 @@ use crate::{
                 big32, little32,
             },
 
-            Endian, SymbolBind, SymbolType,
+            Endian, SymbolBind, SymbolSection, SymbolType, SymbolVisibility,
         },
 
         traits::{
             Symbol
         },
+
+        demangle,
+        ELFError, SymbolVersion,
     },
 };
 
@@ -35,21 +38,25 @@ impl ELFSymbol<u32> {
     fn read<B: ByteOrder>(data: &[u8]) -> u32 {
         B::read_u32( data )
     }
+
+    /// Internal serialize function.
+    fn serialize<B: ByteOrder>(&self, chunk: &mut [u8], strndx: usize, shndx: u16) {
+        B::write_u32( &mut chunk[0..4], strndx as u32 );
+        B::write_u32( &mut chunk[4..8], self.value );
+        B::write_u32( &mut chunk[8..12], self.size );
+        chunk[12] = self.raw.0;
+        chunk[13] = self.raw.1;
+        B::write_u16( &mut chunk[14..16], shndx );
+    }
 }
 
 impl Symbol for ELFSymbol<u32> {
-    fn all(table: &[u8], endian: Endian) -> Vec<Box<dyn Symbol>> where Self: Sized + 'static {
-        // List of all sections.
-        let mut out: Vec<Box<dyn Symbol>> = Vec::new();
-
-        for chunk in table.chunks(Self::SIZE) {
-            out.push( Box::new( Self::parse(chunk, endian) ) )
+    fn parse(chunk: &[u8], endian: Endian) -> Result<Self, ELFError> {
+        // Check the symbol is complete.
+        if chunk.len() < Self::SIZE {
+            return Err( ELFError::Truncated { offset: 0, expected: Self::SIZE, found: chunk.len() } );
         }
 
-        out
-    }
-
-    fn parse(chunk: &[u8], endian: Endian) -> Self {
         // Get the read function.
         let (read16, read32, read): (fn(&[u8]) -> u16, fn(&[u8]) -> u32, fn(&[u8]) -> u32) = match endian {
             Endian::Little => (little16, little32, Self::read::<LittleEndian>),
@@ -86,23 +93,29 @@ impl Symbol for ELFSymbol<u32> {
         };
 
         // Get the visibility.
-        //let visibility = SymbolVisibility::from( chunk[5] );
-        let visibility = chunk[i];
+        let visibility = SymbolVisibility::from( chunk[i] );
         i += 1;
 
-        // Get the relativity.
-        let relativity = read16( &chunk[i..i+2] );
+        // Get the section.
+        let section = SymbolSection::from( read16( &chunk[i..i+2] ) );
 
-        ELFSymbol {
+        Ok( ELFSymbol {
             strndx,
             name: String::new(),
             stype,
             binding,
             visibility,
-            relativity,
+            raw: (chunk[12], chunk[13]),
+            section,
+            sectionname: None,
             value,
             size,
-        }
+            version: None,
+        })
+    }
+
+    fn entrysize() -> usize {
+        Self::SIZE
     }
 
     fn name(&self) -> &str {
@@ -120,6 +133,56 @@ impl Symbol for ELFSymbol<u32> {
     fn stype(&self) -> SymbolType {
         self.stype
     }
+
+    fn binding(&self) -> SymbolBind {
+        self.binding
+    }
+
+    fn visibility(&self) -> SymbolVisibility {
+        self.visibility
+    }
+
+    fn section(&self) -> SymbolSection {
+        self.section
+    }
+
+    fn shndx(&self) -> usize {
+        self.section.index()
+    }
+
+    fn extend(&mut self, index: u32) {
+        if self.section == SymbolSection::Extended {
+            self.section = SymbolSection::Index( index as usize );
+        }
+    }
+
+    fn setsection(&mut self, section: SymbolSection) {
+        self.section = section;
+        self.sectionname = None;
+    }
+
+    fn sectionname(&self) -> Option<&str> {
+        self.sectionname.as_deref()
+    }
+
+    fn setsectionname(&mut self, name: String) {
+        self.sectionname = Some( name );
+    }
+
+    fn version(&self) -> Option<&SymbolVersion> {
+        self.version.as_ref()
+    }
+
+    fn setversion(&mut self, version: SymbolVersion) {
+        self.version = Some( version );
+    }
+
+    fn write(&self, chunk: &mut [u8], endian: Endian, strndx: usize, shndx: u16) {
+        match endian {
+            Endian::Little => self.serialize::<LittleEndian>(chunk, strndx, shndx),
+            Endian::Big    => self.serialize::<BigEndian>(chunk, strndx, shndx),
+        }
+    }
 }
 
 impl core::fmt::Display for ELFSymbol<u32> {
@@ -127,10 +190,17 @@ impl core::fmt::Display for ELFSymbol<u32> {
         // Indicate object type.
         let mut string = String::from("ELF Symbol\n");
 
+        // Demangle the name in the alternate format.
+        let demangled = f.alternate().then(|| demangle(&self.name, false)).flatten();
+        let name = demangled.as_deref().unwrap_or(&self.name);
+
         // Add symbol identification.
         string += "|- Symbol ID\n";
         string += &format!("|  |- Name offset  : {}\n", self.strndx);
-        string += &format!("|  |- Name         : {}\n", self.name);
+        match &self.version {
+            Some(version) => string += &format!("|  |- Name         : {}{}\n", name, version),
+            _ => string += &format!("|  |- Name         : {}\n", name),
+        }
 
         // Add symbol information.
         string += "|- Symbol Information\n";
@@ -138,10 +208,12 @@ impl core::fmt::Display for ELFSymbol<u32> {
         string += &format!("|  |- Binding   : {}\n", self.binding);
         string += &format!("|  |- Visibility: {}\n", self.visibility);
 
-        if self.relativity != 0 {
-            string += &format!("|- Related section index: {} \n", self.relativity)
+        match &self.sectionname {
+            Some(name) => string += &format!("|  |- Section   : {} ({})\n", self.section, name),
+            _ => string += &format!("|  |- Section   : {}\n", self.section),
         }
 
+
         // Add address information.
         string += "|- Symbol value\n";
 
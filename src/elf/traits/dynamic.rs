@@ -0,0 +1,53 @@
+//! Common trait for dynamic section entries of all architectural types.
+
+
+
+use crate::{
+    elf::{
+        common::{
+            DynamicFlags, DynamicFlags1, DynamicTag, Endian,
+        },
+
+        ELFError,
+    },
+};
+
+use super::Rename;
+
+
+
+pub trait DynamicEntry: core::fmt::Display + Rename {
+    /// Parses a dynamic entry.
+    fn parse(chunk: &[u8], endian: Endian, i: usize) -> Result<Self, ELFError> where Self: Sized;
+
+    /// Returns the size of a dynamic entry of this format.
+    fn entrysize() -> usize where Self: Sized;
+
+    /// Returns the index of the entry in the dynamic section.
+    fn index(&self) -> usize;
+
+    /// Returns the tag of the entry.
+    fn tag(&self) -> DynamicTag;
+
+    /// Returns the raw value (integer or address) of the entry.
+    fn value(&self) -> u64;
+
+    /// Returns the string referenced by the entry, if its tag is string-valued.
+    fn string(&self) -> Option<&str>;
+
+    /// Returns the decoded flags of a `DT_FLAGS` entry.
+    fn flags(&self) -> Option<DynamicFlags> {
+        match self.tag() {
+            DynamicTag::Flags => Some( DynamicFlags( self.value() ) ),
+            _ => None,
+        }
+    }
+
+    /// Returns the decoded flags of a `DT_FLAGS_1` entry.
+    fn flags1(&self) -> Option<DynamicFlags1> {
+        match self.tag() {
+            DynamicTag::Flags1 => Some( DynamicFlags1( self.value() ) ),
+            _ => None,
+        }
+    }
+}
@@ -5,8 +5,10 @@
 use crate::{
     elf::{
         common::{
-            Endian,
+            ArchFlags, Endian, FileType, InstructionSet,
         },
+
+        ELFError,
     },
 };
 
@@ -14,11 +16,21 @@ use crate::{
 
 pub trait FileHeader: core::fmt::Display {
     /// Parses an ELF header.
-    fn parse(header: &[u8]) -> Self where Self: Sized;
+    /// When the file uses extended section numbering, `header` must also contain the first Section Header.
+    fn parse(header: &[u8]) -> Result<Self, ELFError> where Self: Sized;
 
     /// Returns the endianness of the target architecture.
     fn endian(&self) -> Endian;
 
+    /// Returns the object file type.
+    fn filetype(&self) -> FileType;
+
+    /// Returns the target Instruction Set Architecture.
+    fn isa(&self) -> InstructionSet;
+
+    /// Returns the architecture specific flags.
+    fn flags(&self) -> ArchFlags;
+
     /// Returns the file offset into the Program Header Table.
     fn phtoffset(&self) -> usize;
 
@@ -31,12 +43,19 @@ pub trait FileHeader: core::fmt::Display {
     /// Returns the size of the Section Header.
     fn shsize(&self) -> usize;
 
-    /// Returns the number of Program Headers.
+    /// Returns the number of Program Headers, resolving extended numbering.
     fn phnum(&self) -> usize;
 
-    /// Returns the number of Section Headers.
+    /// Returns the number of Section Headers, resolving extended numbering.
     fn shnum(&self) -> usize;
 
-    /// Returns the index of the String Section.
+    /// Returns the index of the String Section, resolving extended numbering.
     fn shstrndx(&self) -> usize;
+
+    /// Sets the index of the String Section.
+    fn setshstrndx(&mut self, index: usize);
+
+    /// Writes the table offsets, the number of headers and the index of the String Section over the
+    /// serialized header. Values that do not fit in the header use the extended numbering escapes.
+    fn write(&self, header: &mut [u8], offset: (usize, usize), num: (usize, usize), shstrndx: usize);
 }
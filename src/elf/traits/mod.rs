@@ -2,14 +2,20 @@
 
 
 
+mod dynamic;
 mod file;
+mod relocation;
 mod rename;
 mod section;
+mod segment;
 mod symbol;
 
 
 
+pub use dynamic::DynamicEntry;
 pub use file::FileHeader;
+pub use relocation::Relocation;
 pub use rename::Rename;
 pub use section::SectionHeader;
+pub use segment::ProgramHeader;
 pub use symbol::Symbol;
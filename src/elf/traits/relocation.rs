@@ -0,0 +1,39 @@
+//! Common trait for relocation entries of all architectural types.
+
+
+
+use crate::{
+    elf::{
+        common::{
+            Endian, InstructionSet, RelocationType,
+        },
+
+        ELFError,
+    },
+};
+
+
+
+pub trait Relocation: core::fmt::Display {
+    /// Parses a relocation entry. `addend` indicates if the entry has an explicit addend (RELA).
+    fn parse(chunk: &[u8], endian: Endian, isa: InstructionSet, addend: bool, i: usize) -> Result<Self, ELFError> where Self: Sized;
+
+    /// Returns the size of a relocation entry of this format.
+    fn entrysize(addend: bool) -> usize where Self: Sized;
+
+    /// Returns the index of the entry in its relocation table.
+    fn index(&self) -> usize;
+
+    /// Returns the location to relocate. This is an offset into the target
+    /// section for relocatable files and a virtual address otherwise.
+    fn offset(&self) -> usize;
+
+    /// Returns the index of the referenced symbol in the associated symbol table.
+    fn symbol(&self) -> usize;
+
+    /// Returns the type of relocation.
+    fn rtype(&self) -> RelocationType;
+
+    /// Returns the explicit addend of the relocation, if the entry has one.
+    fn addend(&self) -> Option<i64>;
+}
@@ -5,8 +5,14 @@
 use crate::{
     elf::{
         common::{
-            Endian, SectionType,
+            read::{
+                slice,
+            },
+
+            Endian, InstructionSet, SectionFlags, SectionType,
         },
+
+        ELFError,
     },
 };
 
@@ -18,24 +24,39 @@ use super::{
 
 pub trait SectionHeader: core::fmt::Display + Rename {
     /// Parses all section headers with the given headers configuration.
-    fn all(table: &[u8], header: &dyn FileHeader) -> Vec<Box<dyn SectionHeader>> where Self: Sized + 'static {
+    fn all(raw: &[u8], header: &dyn FileHeader) -> Result<Vec<Box<dyn SectionHeader>>, ELFError> where Self: Sized + 'static {
         // Get the size of each header and the number of headers.
         let endian = header.endian();
+        let isa = header.isa();
+        let offset = header.shtoffset();
         let size = header.shsize();
         let num = header.shnum();
 
+        if num == 0 {
+            return Ok( Vec::new() );
+        }
+
+        // Get the Section Header Table.
+        let table = slice(raw, offset, size * num)?;
+
         // List of all sections.
         let mut out: Vec<Box<dyn SectionHeader>> = Vec::with_capacity(num);
 
-        for (i, chunk) in (&table[0..size*num]).chunks(size).enumerate() {
-            out.push( Box::new( Self::parse(chunk, endian, i) ) )
+        for (i, chunk) in table.chunks(size).enumerate() {
+            let entry = Self::parse(chunk, endian, isa, i)
+                .map_err(|e| e.shift(offset + (i * size)))?;
+
+            out.push( Box::new( entry ) )
         }
 
-        out
+        Ok( out )
     }
 
     /// Parses an ELF Section header.
-    fn parse(chunk: &[u8], endian: Endian, i: usize) -> Self where Self: Sized;
+    fn parse(chunk: &[u8], endian: Endian, isa: InstructionSet, i: usize) -> Result<Self, ELFError> where Self: Sized;
+
+    /// Returns the index of the section in the Section Header Table.
+    fn index(&self) -> usize;
 
     /// Returns a reference to the name of the section.
     fn name(&self) -> &str;
@@ -48,5 +69,38 @@ pub trait SectionHeader: core::fmt::Display + Rename {
 
     /// Returns the type of section.
     fn stype(&self) -> SectionType;
+
+    /// Returns the section flags.
+    fn flags(&self) -> SectionFlags;
+
+    /// Returns the size of each entry for table sections.
+    fn entrysize(&self) -> usize;
+
+    /// Returns the address alignment of the section.
+    fn align(&self) -> usize;
+
+    /// Returns the section index of the associated section (`sh_link`).
+    fn link(&self) -> usize;
+
+    /// Returns the extra information of the section (`sh_info`).
+    fn info(&self) -> usize;
+
+    /// Sets the index of the section in the Section Header Table.
+    fn setindex(&mut self, index: usize);
+
+    /// Sets the section flags.
+    fn setflags(&mut self, flags: SectionFlags);
+
+    /// Sets the address alignment of the section.
+    fn setalign(&mut self, align: usize);
+
+    /// Sets the section index of the associated section (`sh_link`).
+    fn setlink(&mut self, link: usize);
+
+    /// Sets the extra information of the section (`sh_info`).
+    fn setinfo(&mut self, info: usize);
+
+    /// Serializes the section header, with the given name offset and file offset and size.
+    fn write(&self, chunk: &mut [u8], endian: Endian, strndx: usize, phys: (usize, usize));
 }
 
@@ -0,0 +1,80 @@
+//! Common trait for program headers of all architectural types.
+
+
+
+use crate::{
+    elf::{
+        common::{
+            read::{
+                slice,
+            },
+
+            Endian, SegmentFlags, SegmentType,
+        },
+
+        ELFError,
+    },
+};
+
+use super::{
+    FileHeader,
+};
+
+
+
+pub trait ProgramHeader: core::fmt::Display {
+    /// Parses all segment headers with the given headers configuration.
+    fn all(raw: &[u8], header: &dyn FileHeader) -> Result<Vec<Box<dyn ProgramHeader>>, ELFError> where Self: Sized + 'static {
+        // Get the size of each header and the number of headers.
+        let endian = header.endian();
+        let offset = header.phtoffset();
+        let size = header.phsize();
+        let num = header.phnum();
+
+        if num == 0 {
+            return Ok( Vec::new() );
+        }
+
+        // Get the Program Header Table.
+        let table = slice(raw, offset, size * num)?;
+
+        // List of all segments.
+        let mut out: Vec<Box<dyn ProgramHeader>> = Vec::with_capacity(num);
+
+        for (i, chunk) in table.chunks(size).enumerate() {
+            let entry = Self::parse(chunk, endian, i)
+                .map_err(|e| e.shift(offset + (i * size)))?;
+
+            out.push( Box::new( entry ) )
+        }
+
+        Ok( out )
+    }
+
+    /// Parses an ELF Program header.
+    fn parse(chunk: &[u8], endian: Endian, i: usize) -> Result<Self, ELFError> where Self: Sized;
+
+    /// Returns the index of the segment in the Program Header Table.
+    fn index(&self) -> usize;
+
+    /// Returns the type of segment.
+    fn stype(&self) -> SegmentType;
+
+    /// Returns the permission flags of the segment.
+    fn flags(&self) -> SegmentFlags;
+
+    /// Returns the file offset and size of the segment in the file image.
+    fn phys(&self) -> (usize, usize);
+
+    /// Returns the virtual address and size of the segment in memory.
+    fn virt(&self) -> (usize, usize);
+
+    /// Returns the physical address of the segment, for systems where it is relevant.
+    fn paddr(&self) -> usize;
+
+    /// Returns the alignment of the segment.
+    fn align(&self) -> usize;
+
+    /// Serializes the program header.
+    fn write(&self, chunk: &mut [u8], endian: Endian);
+}
@@ -4,22 +4,71 @@
 use crate::{
     elf::{
         common::{
-            Endian, SymbolType
+            read::{
+                slice,
+            },
+
+            Endian, SymbolBind, SymbolSection, SymbolType, SymbolVisibility,
         },
+
+        demangle,
+        ELFError, SymbolVersion,
     },
 };
 
+use super::{
+    SectionHeader,
+};
+
 
 pub trait Symbol: core::fmt::Display + super::Rename {
-    /// Parses a list of Symbols from a given symbol table.
-    fn all(table: &[u8], endian: Endian) -> Vec<Box<dyn Symbol>> where Self: Sized + 'static;
+    /// Parses a list of Symbols from a given symbol table section.
+    fn all(raw: &[u8], table: &dyn SectionHeader, endian: Endian) -> Result<Vec<Box<dyn Symbol>>, ELFError> where Self: Sized + 'static {
+        // Get the location of the table and the size of each symbol.
+        let (offset, size) = table.phys();
+        let entry = Self::entrysize();
+
+        // Check the table contains symbols of this format.
+        if table.entrysize() != entry {
+            return Err( ELFError::BadEntrySize { offset, expected: entry, found: table.entrysize() } );
+        }
+
+        // Check the table contains only complete symbols.
+        if (size % entry) != 0 {
+            return Err( ELFError::Truncated { offset: offset + (size - (size % entry)), expected: entry, found: size % entry } );
+        }
+
+        // Get the symbol table.
+        let data = slice(raw, offset, size)?;
+
+        // List of all symbols.
+        let mut out: Vec<Box<dyn Symbol>> = Vec::with_capacity(size / entry);
+
+        for (i, chunk) in data.chunks(entry).enumerate() {
+            let symbol = Self::parse(chunk, endian)
+                .map_err(|e| e.shift(offset + (i * entry)))?;
+
+            out.push( Box::new( symbol ) )
+        }
+
+        Ok( out )
+    }
 
     /// Parses a single Symbol.
-    fn parse(chunk: &[u8], endian: Endian) -> Self where Self: Sized;
+    fn parse(chunk: &[u8], endian: Endian) -> Result<Self, ELFError> where Self: Sized;
+
+    /// Returns the size of a symbol table entry of this format.
+    fn entrysize() -> usize where Self: Sized;
 
     /// Returns the name of the symbol
     fn name(&self) -> &str;
 
+    /// Returns the demangled name of the symbol, or `None` if the name is not mangled.
+    /// Rust hashes are kept when `hash` is `true`. Requires the `demangle` feature.
+    fn demangled_name(&self, hash: bool) -> Option<String> {
+        demangle(self.name(), hash)
+    }
+
     /// Returns the address of the symbol.
     fn address(&self) -> usize;
 
@@ -28,4 +77,37 @@ pub trait Symbol: core::fmt::Display + super::Rename {
 
     /// Returns the type of this symbol.
     fn stype(&self) -> SymbolType;
+
+    /// Returns the binding of this symbol.
+    fn binding(&self) -> SymbolBind;
+
+    /// Returns the visibility of this symbol.
+    fn visibility(&self) -> SymbolVisibility;
+
+    /// Returns the section this symbol is defined relative to.
+    fn section(&self) -> SymbolSection;
+
+    /// Returns the raw index of the section this symbol is defined in (`st_shndx`).
+    fn shndx(&self) -> usize;
+
+    /// Resolves an escaped (`SHN_XINDEX`) section index with the one in the extended section index table.
+    fn extend(&mut self, index: u32);
+
+    /// Sets the section this symbol is defined relative to, forgetting the name of the previous one.
+    fn setsection(&mut self, section: SymbolSection);
+
+    /// Returns the name of the section this symbol is defined in, once resolved.
+    fn sectionname(&self) -> Option<&str>;
+
+    /// Sets the name of the section this symbol is defined in.
+    fn setsectionname(&mut self, name: String);
+
+    /// Returns the GNU version of the symbol, if it has one.
+    fn version(&self) -> Option<&SymbolVersion>;
+
+    /// Sets the GNU version of the symbol.
+    fn setversion(&mut self, version: SymbolVersion);
+
+    /// Serializes the symbol, with the given name offset and raw section index (`st_shndx`).
+    fn write(&self, chunk: &mut [u8], endian: Endian, strndx: usize, shndx: u16);
 }
@@ -0,0 +1,375 @@
+//! GNU symbol versioning parsing and interaction.
+
+
+
+use crate::{
+    elf::{
+        common::{
+            read::{
+                self,
+                big16, little16,
+                big32, little32,
+            },
+
+            Endian,
+        },
+
+        ELFError, StringTable,
+    },
+};
+
+
+
+/// Version of a dynamic symbol.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SymbolVersion {
+    /// Name of the version.
+    pub name: String,
+
+    /// The version is hidden: the symbol is not the default version of its name.
+    pub hidden: bool,
+
+    /// The version is defined by this file (`.gnu.version_d`) instead of required from another.
+    pub defined: bool,
+}
+
+impl core::fmt::Display for SymbolVersion {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        // Default versions of defined symbols use a double separator.
+        match self.defined && !self.hidden {
+            true => write!(f, "@@{}", self.name),
+            false => write!(f, "@{}", self.name),
+        }
+    }
+}
+
+
+
+/// Contains a version definition of `.gnu.version_d`.
+pub struct ELFVersionDefinition {
+    /// Version index referenced by `.gnu.version`.
+    pub index: u16,
+
+    /// Version flags.
+    pub flags: u16,
+
+    /// Name of the version.
+    pub name: String,
+
+    /// Names of the parent versions.
+    pub parents: Vec<String>,
+}
+
+impl ELFVersionDefinition {
+    /// Size of a version definition entry.
+    const SIZE: usize = 20;
+
+    /// Size of a version definition auxiliary entry.
+    const AUXSIZE: usize = 8;
+
+    /// Parses the given number (`sh_info`) of version definitions of a `.gnu.version_d` section.
+    pub(crate) fn all(data: &[u8], offset: usize, endian: Endian, strtab: &StringTable, count: usize) -> Result<Vec<Self>, ELFError> {
+        // Get the read functions.
+        let read16: fn(&[u8]) -> u16 = match endian {
+            Endian::Little => little16,
+            Endian::Big => big16,
+        };
+
+        let read32: fn(&[u8]) -> u32 = match endian {
+            Endian::Little => little32,
+            Endian::Big => big32,
+        };
+
+        // List of all definitions.
+        let mut definitions = Vec::new();
+
+        // Empty sections have no entries.
+        if data.is_empty() {
+            return Ok( definitions );
+        }
+
+        // Offset of the current entry.
+        let mut i = 0;
+
+        for _ in 0..count {
+            let entry = read::slice(data, i, Self::SIZE).map_err(|e| e.shift(offset))?;
+
+            // Get the version information.
+            let flags = read16( &entry[0x02..0x04] );
+            let index = read16( &entry[0x04..0x06] );
+            let auxnum = read16( &entry[0x06..0x08] ) as usize;
+            let aux    = read32( &entry[0x0C..0x10] ) as usize;
+            let next   = read32( &entry[0x10..0x14] ) as usize;
+
+            // Get the names of the version and its parents.
+            let mut names = Vec::new();
+            let mut j = i + aux;
+
+            for _ in 0..auxnum {
+                let auxiliary = read::slice(data, j, Self::AUXSIZE).map_err(|e| e.shift(offset))?;
+
+                names.push( String::from_utf8_lossy( strtab.get( read32( &auxiliary[0..4] ) as usize )? ).into_owned() );
+
+                match read32( &auxiliary[4..8] ) as usize {
+                    0 => break,
+                    n => j += n,
+                }
+            }
+
+            let mut names = names.into_iter();
+
+            definitions.push( Self {
+                index,
+                flags,
+                name: names.next().unwrap_or_default(),
+                parents: names.collect(),
+            });
+
+            match next {
+                0 => break,
+                n => i += n,
+            }
+        }
+
+        Ok( definitions )
+    }
+}
+
+impl core::fmt::Display for ELFVersionDefinition {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        // Build the output string.
+        let mut string = String::from("ELF Version Definition\n");
+
+        // Add version information.
+        string += &format!("|- Index  : {}\n", self.index);
+        string += &format!("|- Flags  : {:#X}\n", self.flags);
+        string += &format!("|- Name   : {}\n", self.name);
+        string += &format!("|- Parents: {}\n\n", self.parents.join(", "));
+
+        f.write_str(&string)
+    }
+}
+
+
+
+/// Contains the versions required from a file in `.gnu.version_r`.
+pub struct ELFVersionRequirement {
+    /// Name of the file the versions are required from.
+    pub file: String,
+
+    /// Versions required from the file.
+    pub versions: Vec<ELFVersionNeeded>,
+}
+
+/// Contains a version required from another file.
+pub struct ELFVersionNeeded {
+    /// Version index referenced by `.gnu.version`.
+    pub index: u16,
+
+    /// Version flags.
+    pub flags: u16,
+
+    /// Name of the version.
+    pub name: String,
+}
+
+impl ELFVersionRequirement {
+    /// Size of a version requirement entry.
+    const SIZE: usize = 16;
+
+    /// Size of a version requirement auxiliary entry.
+    const AUXSIZE: usize = 16;
+
+    /// Parses the given number (`sh_info`) of version requirements of a `.gnu.version_r` section.
+    pub(crate) fn all(data: &[u8], offset: usize, endian: Endian, strtab: &StringTable, count: usize) -> Result<Vec<Self>, ELFError> {
+        // Get the read functions.
+        let read16: fn(&[u8]) -> u16 = match endian {
+            Endian::Little => little16,
+            Endian::Big => big16,
+        };
+
+        let read32: fn(&[u8]) -> u32 = match endian {
+            Endian::Little => little32,
+            Endian::Big => big32,
+        };
+
+        // List of all requirements.
+        let mut requirements = Vec::new();
+
+        // Empty sections have no entries.
+        if data.is_empty() {
+            return Ok( requirements );
+        }
+
+        // Offset of the current entry.
+        let mut i = 0;
+
+        for _ in 0..count {
+            let entry = read::slice(data, i, Self::SIZE).map_err(|e| e.shift(offset))?;
+
+            // Get the requirement information.
+            let auxnum = read16( &entry[0x02..0x04] ) as usize;
+            let file   = read32( &entry[0x04..0x08] ) as usize;
+            let aux    = read32( &entry[0x08..0x0C] ) as usize;
+            let next   = read32( &entry[0x0C..0x10] ) as usize;
+
+            // Get the required versions.
+            let mut versions = Vec::new();
+            let mut j = i + aux;
+
+            for _ in 0..auxnum {
+                let auxiliary = read::slice(data, j, Self::AUXSIZE).map_err(|e| e.shift(offset))?;
+
+                versions.push( ELFVersionNeeded {
+                    flags: read16( &auxiliary[0x04..0x06] ),
+                    index: read16( &auxiliary[0x06..0x08] ),
+                    name: String::from_utf8_lossy( strtab.get( read32( &auxiliary[0x08..0x0C] ) as usize )? ).into_owned(),
+                });
+
+                match read32( &auxiliary[0x0C..0x10] ) as usize {
+                    0 => break,
+                    n => j += n,
+                }
+            }
+
+            requirements.push( Self {
+                file: String::from_utf8_lossy( strtab.get( file )? ).into_owned(),
+                versions,
+            });
+
+            match next {
+                0 => break,
+                n => i += n,
+            }
+        }
+
+        Ok( requirements )
+    }
+}
+
+impl core::fmt::Display for ELFVersionRequirement {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        // Build the output string.
+        let mut string = String::from("ELF Version Requirement\n");
+
+        // Add file information.
+        string += &format!("|- File: {}\n", self.file);
+
+        // Add all the versions.
+        for version in self.versions.iter() {
+            string += &format!("|- Version {}\n", version.index);
+            string += &format!("|  |- Name : {}\n", version.name);
+            string += &format!("|  |- Flags: {:#X}\n", version.flags);
+        }
+
+        string += "\n";
+
+        f.write_str(&string)
+    }
+}
+
+
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::elf::ELFContent;
+
+    /// Shared library with versioned symbols. Built by `tests/fixtures/build.sh`.
+    const LIBRARY: &[u8] = include_bytes!("../../../tests/fixtures/libsample.so");
+
+    /// Builds a little endian version definition with a single name and the given offset to the next one.
+    fn definition(index: u16, name: u32, next: u32) -> Vec<u8> {
+        let mut entry = vec![0u8; 28];
+
+        entry[0x00..0x02].copy_from_slice(&1u16.to_le_bytes());
+        entry[0x04..0x06].copy_from_slice(&index.to_le_bytes());
+        entry[0x06..0x08].copy_from_slice(&1u16.to_le_bytes());
+        entry[0x0C..0x10].copy_from_slice(&20u32.to_le_bytes());
+        entry[0x10..0x14].copy_from_slice(&next.to_le_bytes());
+        entry[0x14..0x18].copy_from_slice(&name.to_le_bytes());
+
+        entry
+    }
+
+    /// Builds a little endian version requirement of a single version, with the given offset to the next one.
+    fn requirement(file: u32, index: u16, name: u32, next: u32) -> Vec<u8> {
+        let mut entry = vec![0u8; 32];
+
+        entry[0x00..0x02].copy_from_slice(&1u16.to_le_bytes());
+        entry[0x02..0x04].copy_from_slice(&1u16.to_le_bytes());
+        entry[0x04..0x08].copy_from_slice(&file.to_le_bytes());
+        entry[0x08..0x0C].copy_from_slice(&16u32.to_le_bytes());
+        entry[0x0C..0x10].copy_from_slice(&next.to_le_bytes());
+        entry[0x16..0x18].copy_from_slice(&index.to_le_bytes());
+        entry[0x18..0x1C].copy_from_slice(&name.to_le_bytes());
+
+        entry
+    }
+
+    #[test]
+    fn definitions() {
+        let strtab = StringTable::new(b"\0lib.so\0V1\0", 0);
+
+        // The chain does not terminate, so only the `sh_info` entries are read.
+        let data = [definition(1, 1, 28), definition(2, 8, 28)].concat();
+        let definitions = ELFVersionDefinition::all(&data, 0x100, Endian::Little, &strtab, 2).unwrap();
+
+        assert_eq!(definitions.iter().map(|d| (d.index, d.name.as_str())).collect::<Vec<_>>(), [(1, "lib.so"), (2, "V1")]);
+        assert_eq!(ELFVersionDefinition::all(&data, 0x100, Endian::Little, &strtab, 3).err(), Some(ELFError::OutOfBounds { offset: 0x138, size: 20, filesize: 56 }));
+
+        // Empty sections have no entries.
+        assert!(ELFVersionDefinition::all(&[], 0x100, Endian::Little, &strtab, 1).unwrap().is_empty());
+    }
+
+    #[test]
+    fn requirements() {
+        let strtab = StringTable::new(b"\0libc.so.6\0GLIBC_2.14\0", 0);
+
+        let data = [requirement(1, 5, 11, 32), requirement(1, 6, 11, 32)].concat();
+        let requirements = ELFVersionRequirement::all(&data, 0, Endian::Little, &strtab, 1).unwrap();
+
+        assert_eq!(requirements.len(), 1);
+        assert_eq!(requirements[0].file, "libc.so.6");
+        assert_eq!((requirements[0].versions[0].index, requirements[0].versions[0].name.as_str()), (5, "GLIBC_2.14"));
+
+        assert!(ELFVersionRequirement::all(&[], 0, Endian::Little, &strtab, 1).unwrap().is_empty());
+    }
+
+    #[test]
+    fn symbol_versions() {
+        let elf = ELFContent::parse(LIBRARY.to_vec()).unwrap();
+
+        assert_eq!(elf.verdef.iter().map(|d| d.name.as_str()).collect::<Vec<_>>(), ["libsample.so.1", "LIB_1.0", "LIB_2.0"]);
+        assert_eq!(elf.verdef[2].parents, ["LIB_1.0"]);
+        assert_eq!(elf.verneed[0].file, "libc.so.6");
+
+        // Get the versions of the dynamic symbols.
+        let versions = elf.dynsym().unwrap().symbols.iter()
+            .filter_map(|s| Some( format!("{}{}", s.name(), s.version()?) ))
+            .collect::<Vec<_>>();
+
+        assert!(versions.contains(&String::from("copy@LIB_1.0")));
+        assert!(versions.contains(&String::from("copy@@LIB_2.0")));
+        assert!(versions.contains(&String::from("memcpy@GLIBC_2.14")));
+        assert!(versions.contains(&String::from("increment@@LIB_1.0")));
+    }
+
+    #[test]
+    fn malformed_definitions() {
+        let mut raw = LIBRARY.to_vec();
+        let elf = ELFContent::parse(raw.clone()).unwrap();
+
+        // Point the names of the first definition past the end of the file.
+        let (offset, _) = elf.sections[ elf.find_section(".gnu.version_d").unwrap() ].phys();
+        raw[offset+0x0C..offset+0x10].copy_from_slice(&0xFFFFu32.to_le_bytes());
+
+        let elf = ELFContent::parse(raw).unwrap();
+
+        assert!(elf.verdef.is_empty());
+        assert!(matches!(elf.errors[..], [ELFError::OutOfBounds { .. }]));
+        assert!(!elf.verneed.is_empty());
+    }
+}
@@ -0,0 +1,160 @@
+//! Lazy iterator over the entries of an ELF table.
+
+
+
+use crate::{
+    elf::{
+        common::{
+            Endian, InstructionSet,
+        },
+
+        traits::{
+            DynamicEntry, ProgramHeader, Relocation, SectionHeader, Symbol,
+        },
+
+        ELFDynamic, ELFError, ELFRelocation, ELFSection, ELFSegment, ELFSymbol,
+    },
+};
+
+
+
+/// Parse function of a single table entry.
+pub(crate) type EntryParser<T> = fn(&[u8], Endian, InstructionSet, usize) -> Result<T, ELFError>;
+
+
+
+/// Entry of a table of a 32-bit or a 64-bit file, parsed by value.
+/// Dereferences to the common trait of the entry.
+#[derive(Clone, Debug)]
+pub enum Entry<A, B> {
+    /// Entry of a 32-bit file.
+    X32(A),
+
+    /// Entry of a 64-bit file.
+    X64(B),
+}
+
+/// Implements the access to the common trait of an entry of both pointer widths.
+macro_rules! entry {
+    ($(#[$meta:meta])* $name:ident, $entry:ident, $trait:ident) => {
+        $(#[$meta])*
+        pub type $name = Entry<$entry<u32>, $entry<u64>>;
+
+        impl core::ops::Deref for $name {
+            type Target = dyn $trait;
+
+            fn deref(&self) -> &Self::Target {
+                match self {
+                    Entry::X32(entry) => entry,
+                    Entry::X64(entry) => entry,
+                }
+            }
+        }
+
+        impl AsRef<dyn $trait> for $name {
+            fn as_ref(&self) -> &(dyn $trait + 'static) {
+                &**self
+            }
+        }
+
+        impl From<$name> for Box<dyn $trait> {
+            fn from(entry: $name) -> Self {
+                match entry {
+                    Entry::X32(entry) => Box::new( entry ),
+                    Entry::X64(entry) => Box::new( entry ),
+                }
+            }
+        }
+    };
+}
+
+entry!(
+    /// Program Header of a file of any pointer width.
+    AnySegment, ELFSegment, ProgramHeader
+);
+
+entry!(
+    /// Section Header of a file of any pointer width.
+    AnySection, ELFSection, SectionHeader
+);
+
+entry!(
+    /// Symbol of a file of any pointer width.
+    AnySymbol, ELFSymbol, Symbol
+);
+
+entry!(
+    /// Relocation entry of a file of any pointer width.
+    AnyRelocation, ELFRelocation, Relocation
+);
+
+entry!(
+    /// Dynamic entry of a file of any pointer width.
+    AnyDynamic, ELFDynamic, DynamicEntry
+);
+
+
+
+/// Iterator that parses the entries of a table as they are requested.
+pub struct Entries<'data, T> {
+    /// Contents of the table.
+    table: &'data [u8],
+
+    /// File offset of the table.
+    offset: usize,
+
+    /// Size of each entry.
+    size: usize,
+
+    /// Index of the next entry.
+    index: usize,
+
+    /// Endianness of the entries.
+    endian: Endian,
+
+    /// Target Instruction Set Architecture of the entries.
+    isa: InstructionSet,
+
+    /// Parse function of a single entry.
+    parse: EntryParser<T>,
+}
+
+impl<'data, T> Entries<'data, T> {
+    /// Creates an iterator over the given table.
+    pub(crate) fn new(table: &'data [u8], offset: usize, size: usize, (endian, isa): (Endian, InstructionSet), parse: EntryParser<T>) -> Self {
+        Self { table, offset, size, index: 0, endian, isa, parse }
+    }
+
+    /// Returns the total number of entries in the table.
+    pub fn total(&self) -> usize {
+        self.table.len().checked_div(self.size).unwrap_or(0)
+    }
+}
+
+impl<'data, T> Iterator for Entries<'data, T> {
+    type Item = Result<T, ELFError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Check if there are entries left.
+        if self.index >= self.total() {
+            return None;
+        }
+
+        // Get the entry.
+        let start = self.index * self.size;
+        let chunk = &self.table[start..start + self.size];
+
+        let entry = (self.parse)(chunk, self.endian, self.isa, self.index)
+            .map_err(|e| e.shift(self.offset + start));
+
+        self.index += 1;
+
+        Some( entry )
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let left = self.total().saturating_sub(self.index);
+
+        (left, Some(left))
+    }
+}
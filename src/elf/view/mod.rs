@@ -0,0 +1,667 @@
+//! Zero-copy view of an ELF file borrowed from its raw data.
+
+
+
+mod entries;
+mod relocate;
+mod shndx;
+mod strtab;
+
+
+
+pub use entries::{
+    AnyDynamic, AnyRelocation, AnySection, AnySegment, AnySymbol, Entries, Entry,
+};
+pub use shndx::SectionIndices;
+pub use strtab::StringTable;
+
+use entries::EntryParser;
+
+use super::relocation::{
+    aps2, relr,
+};
+
+use crate::{
+    elf::{
+        common::{
+            read::{
+                slice,
+                big16, little16,
+            },
+
+            Endian, InstructionSet, SectionType, SegmentType, SymbolSection,
+        },
+
+        traits::{
+            DynamicEntry, FileHeader, ProgramHeader, Relocation, SectionHeader, Symbol,
+        },
+
+        CompressionHeader, ELFAttributes, ELFDynamic, ELFError, ELFGNUHash, ELFHeader, ELFSectionGroup, ELFRelocation, ELFSection, ELFSegment, ELFSymbol,
+        ELFSysVHash, ELFVersionDefinition, ELFVersionRequirement, NoteData, Notes,
+    },
+};
+
+use std::{
+    borrow::{
+        Cow,
+    },
+};
+
+
+
+/// Borrowed view of an ELF file.
+/// Only the file header is parsed on creation, all other headers are parsed
+/// when requested and all names and contents are borrowed from the raw data.
+pub struct ELFFile<'data> {
+    /// Raw ELF data.
+    raw: &'data [u8],
+
+    /// File header of the ELF.
+    header: Box<dyn FileHeader>,
+
+    /// Parse function of a Program Header.
+    segment: EntryParser<AnySegment>,
+
+    /// Parse function of a Section Header.
+    section: EntryParser<AnySection>,
+
+    /// Parse function of a Symbol.
+    symbol: EntryParser<AnySymbol>,
+
+    /// Size of a Symbol.
+    symbolsize: usize,
+
+    /// Parse function of a REL entry.
+    rel: EntryParser<AnyRelocation>,
+
+    /// Parse function of a RELA entry.
+    rela: EntryParser<AnyRelocation>,
+
+    /// Size of a REL and a RELA entry.
+    relsize: (usize, usize),
+
+    /// Parse function of a dynamic entry.
+    dynamicentry: EntryParser<AnyDynamic>,
+
+    /// Size of a dynamic entry.
+    dynamicsize: usize,
+}
+
+impl<'data> ELFFile<'data> {
+    /// Parses the file header of the given data and builds a view over it.
+    pub fn parse(raw: &'data [u8]) -> Result<Self, ELFError> {
+        // Check the identification bytes are present.
+        if raw.len() < 0x10 {
+            return Err( ELFError::Truncated { offset: 0, expected: 0x10, found: raw.len() } );
+        }
+
+        // Check the magic.
+        if raw[0..4] != [0x7F, 0x45, 0x4C, 0x46] {
+            return Err( ELFError::BadMagic( [raw[0], raw[1], raw[2], raw[3]] ) );
+        }
+
+        // Check the pointer width.
+        match raw[4] {
+            1 => Ok( Self {
+                raw,
+                header: Box::new( ELFHeader::<u32>::parse(raw)? ),
+                segment: |chunk, endian, _, i| Ok( Entry::X32( ELFSegment::<u32>::parse(chunk, endian, i)? ) ),
+                section: |chunk, endian, isa, i| Ok( Entry::X32( ELFSection::<u32>::parse(chunk, endian, isa, i)? ) ),
+                symbol: |chunk, endian, _, _| Ok( Entry::X32( ELFSymbol::<u32>::parse(chunk, endian)? ) ),
+                symbolsize: ELFSymbol::<u32>::entrysize(),
+                rel: |chunk, endian, isa, i| Ok( Entry::X32( ELFRelocation::<u32>::parse(chunk, endian, isa, false, i)? ) ),
+                rela: |chunk, endian, isa, i| Ok( Entry::X32( ELFRelocation::<u32>::parse(chunk, endian, isa, true, i)? ) ),
+                relsize: (ELFRelocation::<u32>::entrysize(false), ELFRelocation::<u32>::entrysize(true)),
+                dynamicentry: |chunk, endian, _, i| Ok( Entry::X32( ELFDynamic::<u32>::parse(chunk, endian, i)? ) ),
+                dynamicsize: ELFDynamic::<u32>::entrysize(),
+            }),
+
+            2 => Ok( Self {
+                raw,
+                header: Box::new( ELFHeader::<u64>::parse(raw)? ),
+                segment: |chunk, endian, _, i| Ok( Entry::X64( ELFSegment::<u64>::parse(chunk, endian, i)? ) ),
+                section: |chunk, endian, isa, i| Ok( Entry::X64( ELFSection::<u64>::parse(chunk, endian, isa, i)? ) ),
+                symbol: |chunk, endian, _, _| Ok( Entry::X64( ELFSymbol::<u64>::parse(chunk, endian)? ) ),
+                symbolsize: ELFSymbol::<u64>::entrysize(),
+                rel: |chunk, endian, isa, i| Ok( Entry::X64( ELFRelocation::<u64>::parse(chunk, endian, isa, false, i)? ) ),
+                rela: |chunk, endian, isa, i| Ok( Entry::X64( ELFRelocation::<u64>::parse(chunk, endian, isa, true, i)? ) ),
+                relsize: (ELFRelocation::<u64>::entrysize(false), ELFRelocation::<u64>::entrysize(true)),
+                dynamicentry: |chunk, endian, _, i| Ok( Entry::X64( ELFDynamic::<u64>::parse(chunk, endian, i)? ) ),
+                dynamicsize: ELFDynamic::<u64>::entrysize(),
+            }),
+
+            _ => Err( ELFError::BadPointerWidth( raw[4] ) ),
+        }
+    }
+
+    /// Returns the raw data of the file.
+    pub fn raw(&self) -> &'data [u8] {
+        self.raw
+    }
+
+    /// Returns the file header.
+    pub fn header(&self) -> &dyn FileHeader {
+        self.header.as_ref()
+    }
+
+    /// Internal endianness and ISA of the entries of the file.
+    fn layout(&self) -> (Endian, InstructionSet) {
+        (self.header.endian(), self.header.isa())
+    }
+
+    /// Internal endianness, ISA and word size of the file.
+    fn wordlayout(&self) -> (Endian, InstructionSet, usize) {
+        let word = match self.raw[4] {
+            1 => 4,
+            _ => 8,
+        };
+
+        (self.header.endian(), self.header.isa(), word)
+    }
+
+    /// Consumes the view and returns the file header.
+    pub fn into_header(self) -> Box<dyn FileHeader> {
+        self.header
+    }
+
+    /// Returns a lazy iterator over the Program Headers.
+    pub fn segments(&self) -> Result<Entries<'data, AnySegment>, ELFError> {
+        let (offset, size, num) = (self.header.phtoffset(), self.header.phsize(), self.header.phnum());
+
+        Ok( Entries::new( slice(self.raw, offset, self.tablesize(offset, size, num)?)?, offset, size, self.layout(), self.segment ) )
+    }
+
+    /// Returns a lazy iterator over the Section Headers.
+    pub fn sections(&self) -> Result<Entries<'data, AnySection>, ELFError> {
+        let (offset, size, num) = (self.header.shtoffset(), self.header.shsize(), self.header.shnum());
+
+        Ok( Entries::new( slice(self.raw, offset, self.tablesize(offset, size, num)?)?, offset, size, self.layout(), self.section ) )
+    }
+
+    /// Internal size of a table of headers. The counts of extended numbering are read from
+    /// 64-bit fields, so the size is checked for overflow.
+    fn tablesize(&self, offset: usize, size: usize, num: usize) -> Result<usize, ELFError> {
+        size.checked_mul(num).ok_or( ELFError::OutOfBounds { offset, size: usize::MAX, filesize: self.raw.len() } )
+    }
+
+    /// Parses the Section Header at the given index.
+    pub fn section(&self, index: usize) -> Result<AnySection, ELFError> {
+        let (offset, size, num) = (self.header.shtoffset(), self.header.shsize(), self.header.shnum());
+
+        if index >= num {
+            return Err( ELFError::BadSectionIndex { offset, index, count: num } );
+        }
+
+        // Check the size of the table, so the offset of the entry inside it does not overflow.
+        let table = self.tablesize(offset, size, num)?;
+
+        let start = offset.checked_add(index * size)
+            .ok_or( ELFError::OutOfBounds { offset, size: table, filesize: self.raw.len() } )?;
+
+        (self.section)( slice(self.raw, start, size)?, self.header.endian(), self.header.isa(), index )
+            .map_err(|e| e.shift(start))
+    }
+
+    /// Returns the section header string table, if the file has one.
+    pub fn shstrtab(&self) -> Result<Option<StringTable<'data>>, ELFError> {
+        match self.header.shstrndx() {
+            0 => Ok( None ),
+            i => Ok( Some( self.strtab( self.section(i)?.as_ref() )? ) ),
+        }
+    }
+
+    /// Returns the name of the given section.
+    pub fn section_name(&self, section: &dyn SectionHeader) -> Result<&'data [u8], ELFError> {
+        match self.shstrtab()? {
+            Some(shstrtab) => shstrtab.get( section.strndx() ),
+            _ => Ok( &[] ),
+        }
+    }
+
+    /// Finds the first section with the given name.
+    pub fn section_by_name(&self, name: &str) -> Result<Option<AnySection>, ELFError> {
+        // Get the section header string table.
+        let shstrtab = match self.shstrtab()? {
+            Some(shstrtab) => shstrtab,
+            _ => return Ok( None ),
+        };
+
+        for section in self.sections()? {
+            let section = section?;
+
+            if shstrtab.get( section.strndx() )? == name.as_bytes() {
+                return Ok( Some( section ) );
+            }
+        }
+
+        Ok( None )
+    }
+
+    /// Returns the contents of the given section in the file image.
+    pub fn section_data(&self, section: &dyn SectionHeader) -> Result<&'data [u8], ELFError> {
+        // Sections with no data in the file image.
+        if section.stype() == SectionType::Bss {
+            return Ok( &[] );
+        }
+
+        // Get the offset and size of the section.
+        let (o, s) = section.phys();
+
+        slice(self.raw, o, s)
+    }
+
+    /// Returns the compression header of the given section if its contents are compressed,
+    /// either with `SHF_COMPRESSED` or as a legacy `.zdebug_*` section.
+    pub fn compression(&self, section: &dyn SectionHeader) -> Result<Option<CompressionHeader>, ELFError> {
+        if section.flags().compressed() {
+            let data = self.section_data(section)?;
+
+            return CompressionHeader::parse(data, self.header.endian(), self.wordlayout().2)
+                .map( Some )
+                .map_err(|e| e.shift(section.phys().0));
+        }
+
+        if self.section_name(section)?.starts_with(b".zdebug") {
+            return Ok( CompressionHeader::zdebug( self.section_data(section)? ) );
+        }
+
+        Ok( None )
+    }
+
+    /// Returns the contents of the given section, decompressed if the section is compressed.
+    /// The decompression algorithms are enabled by the `zlib` and `zstd` features.
+    pub fn section_contents(&self, section: &dyn SectionHeader) -> Result<Cow<'data, [u8]>, ELFError> {
+        let data = self.section_data(section)?;
+
+        match self.compression(section)? {
+            Some(header) => Ok( Cow::Owned( header.decompress(data, section.phys().0)? ) ),
+            _ => Ok( Cow::Borrowed( data ) ),
+        }
+    }
+
+    /// Returns the given section as a string table.
+    pub fn strtab(&self, section: &dyn SectionHeader) -> Result<StringTable<'data>, ELFError> {
+        Ok( StringTable::new( self.section_data(section)?, section.phys().0 ) )
+    }
+
+    /// Returns a lazy iterator over the symbols of the given symbol table section.
+    pub fn symbols(&self, table: &dyn SectionHeader) -> Result<Entries<'data, AnySymbol>, ELFError> {
+        self.entries(table, self.symbolsize, self.symbol)
+    }
+
+    /// Parses the symbol at the given index of the given symbol table section.
+    pub fn symbol(&self, table: &dyn SectionHeader, index: usize) -> Result<AnySymbol, ELFError> {
+        let (offset, size) = table.phys();
+
+        // Check the table contains symbols of this format.
+        if table.entrysize() != self.symbolsize {
+            return Err( ELFError::BadEntrySize { offset, expected: self.symbolsize, found: table.entrysize() } );
+        }
+
+        let count = size / self.symbolsize;
+
+        if index >= count {
+            return Err( ELFError::BadSymbolIndex { offset, index, count } );
+        }
+
+        let start = offset + (index * self.symbolsize);
+
+        (self.symbol)( slice(self.raw, start, self.symbolsize)?, self.header.endian(), self.header.isa(), index )
+            .map_err(|e| e.shift(start))
+    }
+
+    /// Returns the name of the given symbol of the given symbol table, from the string table linked to it.
+    pub fn symbol_name(&self, table: &dyn SectionHeader, symbol: &dyn Symbol) -> Result<&'data str, ELFError> {
+        self.strtab( self.section( table.link() )?.as_ref() )?.get_str( symbol.strndx() )
+    }
+
+    /// Returns a lazy iterator over the entries of the given relocation section.
+    pub fn relocations(&self, table: &dyn SectionHeader) -> Result<Entries<'data, AnyRelocation>, ELFError> {
+        // Get the format of the entries.
+        let (entry, parse) = match table.stype() {
+            SectionType::Relocation => (self.relsize.0, self.rel),
+            SectionType::RelocationA => (self.relsize.1, self.rela),
+
+            _ => return Err( ELFError::BadSectionType { offset: table.phys().0, index: table.index() } ),
+        };
+
+        self.entries(table, entry, parse)
+    }
+
+    /// Returns a lazy iterator over the entries of the given dynamic section.
+    pub fn dynamic(&self, table: &dyn SectionHeader) -> Result<Entries<'data, AnyDynamic>, ELFError> {
+        self.entries(table, self.dynamicsize, self.dynamicentry)
+    }
+
+    /// Internal lazy iterator over a table section with entries of the given size.
+    fn entries<T>(&self, table: &dyn SectionHeader, entry: usize, parse: EntryParser<T>) -> Result<Entries<'data, T>, ELFError> {
+        // Get the location of the table.
+        let (offset, size) = table.phys();
+
+        // Check the table contains entries of this format.
+        if table.entrysize() != entry {
+            return Err( ELFError::BadEntrySize { offset, expected: entry, found: table.entrysize() } );
+        }
+
+        // Check the table contains only complete entries.
+        if (size % entry) != 0 {
+            return Err( ELFError::Truncated { offset: offset + (size - (size % entry)), expected: entry, found: size % entry } );
+        }
+
+        Ok( Entries::new( slice(self.raw, offset, size)?, offset, entry, self.layout(), parse ) )
+    }
+
+    /// Parses the version definitions of the given `.gnu.version_d` section.
+    pub fn version_definitions(&self, section: &dyn SectionHeader) -> Result<Vec<ELFVersionDefinition>, ELFError> {
+        let strtab = self.strtab( self.section( section.link() )?.as_ref() )?;
+
+        ELFVersionDefinition::all( self.section_data(section)?, section.phys().0, self.header.endian(), &strtab, section.info() )
+    }
+
+    /// Parses the version requirements of the given `.gnu.version_r` section.
+    pub fn version_requirements(&self, section: &dyn SectionHeader) -> Result<Vec<ELFVersionRequirement>, ELFError> {
+        let strtab = self.strtab( self.section( section.link() )?.as_ref() )?;
+
+        ELFVersionRequirement::all( self.section_data(section)?, section.phys().0, self.header.endian(), &strtab, section.info() )
+    }
+
+    /// Returns the version indices of the given `.gnu.version` section, one per symbol.
+    pub fn version_symbols(&self, section: &dyn SectionHeader) -> Result<Vec<u16>, ELFError> {
+        let read: fn(&[u8]) -> u16 = match self.header.endian() {
+            Endian::Little => little16,
+            Endian::Big => big16,
+        };
+
+        Ok( self.section_data(section)?.chunks_exact(2).map(read).collect() )
+    }
+
+    /// Expands the entries of the given packed relocation section: RELR tables
+    /// (`SHT_RELR`, `SHT_ANDROID_RELR`) and Android APS2 tables (`SHT_ANDROID_REL`, `SHT_ANDROID_RELA`).
+    pub fn packed_relocations(&self, table: &dyn SectionHeader) -> Result<Vec<Box<dyn Relocation>>, ELFError> {
+        let data = self.section_data(table)?;
+        let offset = table.phys().0;
+
+        match table.stype() {
+            SectionType::RelativeRelocation | SectionType::AndroidRelativeRelocation => Ok( relr(data, self.wordlayout()) ),
+            SectionType::AndroidRelocation => aps2(data, offset, self.wordlayout(), false),
+            SectionType::AndroidRelocationA => aps2(data, offset, self.wordlayout(), true),
+
+            _ => Err( ELFError::BadSectionType { offset, index: table.index() } ),
+        }
+    }
+
+    /// Parses the given section group, resolving its signature from the symbol
+    /// table (`sh_link`) and symbol index (`sh_info`) of the group section.
+    pub fn group(&self, section: &dyn SectionHeader) -> Result<ELFSectionGroup, ELFError> {
+        if section.stype() != SectionType::Group {
+            return Err( ELFError::BadSectionType { offset: section.phys().0, index: section.index() } );
+        }
+
+        // Get the signature symbol.
+        let symtab = self.section( section.link() )?;
+        let symbol = self.symbol( symtab.as_ref(), section.info() )?;
+
+        let mut name = self.strtab( self.section( symtab.link() )?.as_ref() )?.get( symbol.strndx() )?;
+
+        // Section symbols are unnamed and take the name of their section.
+        if let (true, SymbolSection::Index(index)) = (name.is_empty(), symbol.section()) {
+            name = self.section_name( self.section( index )?.as_ref() )?;
+        }
+
+        let signature = String::from_utf8_lossy(name).into_owned();
+
+        ELFSectionGroup::parse( self.section_data(section)?, section.phys().0, self.header.endian(), section.index(), signature )
+    }
+
+    /// Parses the given build attribute section (`SHT_ARM_ATTRIBUTES`, `SHT_RISCV_ATTRIBUTES`
+    /// or `SHT_GNU_ATTRIBUTES`).
+    pub fn attributes(&self, section: &dyn SectionHeader) -> Result<ELFAttributes, ELFError> {
+        match section.stype() {
+            SectionType::ARMAttributes | SectionType::RISCVAttributes | SectionType::GNUAttributes => (),
+            _ => return Err( ELFError::BadSectionType { offset: section.phys().0, index: section.index() } ),
+        }
+
+        ELFAttributes::parse( self.section_data(section)?, section.phys().0, self.header.endian(), section.index() )
+    }
+
+    /// Parses the given SysV symbol hash table (`.hash`) section.
+    pub fn sysv_hash(&self, section: &dyn SectionHeader) -> Result<ELFSysVHash, ELFError> {
+        if section.stype() != SectionType::Hash {
+            return Err( ELFError::BadSectionType { offset: section.phys().0, index: section.index() } );
+        }
+
+        ELFSysVHash::parse( self.section_data(section)?, section.phys().0, self.header.endian(), section.link() )
+    }
+
+    /// Parses the given GNU symbol hash table (`.gnu.hash`) section.
+    pub fn gnu_hash(&self, section: &dyn SectionHeader) -> Result<ELFGNUHash, ELFError> {
+        if section.stype() != SectionType::GNUHash {
+            return Err( ELFError::BadSectionType { offset: section.phys().0, index: section.index() } );
+        }
+
+        let (endian, _, word) = self.wordlayout();
+
+        ELFGNUHash::parse( self.section_data(section)?, section.phys().0, endian, word, section.link() )
+    }
+
+    /// Returns an iterator over the notes of the given note section.
+    pub fn notes(&self, section: &dyn SectionHeader) -> Result<Notes<'data>, ELFError> {
+        if section.stype() != SectionType::Notes {
+            return Err( ELFError::BadSectionType { offset: section.phys().0, index: section.index() } );
+        }
+
+        Ok( Notes::new( self.section_data(section)?, section.phys().0, self.wordlayout(), section.align() ) )
+    }
+
+    /// Returns an iterator over the notes of the given note segment.
+    pub fn segment_notes(&self, segment: &dyn ProgramHeader) -> Result<Notes<'data>, ELFError> {
+        let (offset, size) = segment.phys();
+
+        Ok( Notes::new( slice(self.raw, offset, size)?, offset, self.wordlayout(), segment.align() ) )
+    }
+
+    /// Returns the GNU build ID of the file, searching the note sections and then the note segments.
+    pub fn build_id(&self) -> Result<Option<&'data [u8]>, ELFError> {
+        // Search the note sections.
+        for section in self.sections()? {
+            let section = section?;
+
+            if section.stype() == SectionType::Notes {
+                if let Some(id) = Self::find_build_id( self.notes(section.as_ref())? )? {
+                    return Ok( Some( id ) );
+                }
+            }
+        }
+
+        // Search the note segments of files without sections.
+        for segment in self.segments()? {
+            let segment = segment?;
+
+            if segment.stype() == SegmentType::Note {
+                if let Some(id) = Self::find_build_id( self.segment_notes(segment.as_ref())? )? {
+                    return Ok( Some( id ) );
+                }
+            }
+        }
+
+        Ok( None )
+    }
+
+    /// Internal search of the build ID in a list of notes.
+    fn find_build_id(notes: Notes<'data>) -> Result<Option<&'data [u8]>, ELFError> {
+        for note in notes {
+            if let NoteData::BuildID(id) = note?.data()? {
+                return Ok( Some( id ) );
+            }
+        }
+
+        Ok( None )
+    }
+
+    /// Returns the extended section index table associated with the given symbol table, if any.
+    pub fn extended_indices(&self, table: &dyn SectionHeader) -> Result<Option<SectionIndices<'data>>, ELFError> {
+        for section in self.sections()? {
+            let section = section?;
+
+            if (section.stype() == SectionType::SymbolTableX) && (section.link() == table.index()) {
+                return Ok( Some( SectionIndices::new( self.section_data(section.as_ref())?, section.phys().0, self.header.endian() ) ) );
+            }
+        }
+
+        Ok( None )
+    }
+
+    /// Creates a `gimli` DWARF reader that borrows the debug sections from the raw data.
+    /// Fails with `gimli::Error::Io` on compressed debug sections, which cannot be borrowed.
+    pub fn dwarf(&self) -> Result<gimli::read::Dwarf<gimli::EndianSlice<'data, gimli::RunTimeEndian>>, gimli::Error> {
+        // Translate the endianness.
+        let endian = match self.header.endian() {
+            Endian::Little => gimli::RunTimeEndian::Little,
+            Endian::Big => gimli::RunTimeEndian::Big,
+        };
+
+        // Error translation.
+        let eof = |e: ELFError| gimli::Error::UnexpectedEof( gimli::ReaderOffsetId( e.offset() as u64 ) );
+
+        // Get the sections and their names once.
+        let shstrtab = self.shstrtab().map_err(eof)?;
+
+        let sections = self.sections().map_err(eof)?
+            .collect::<Result<Vec<_>, _>>().map_err(eof)?;
+
+        // Create the load closure.
+        let load = |id: gimli::SectionId| -> Result<gimli::EndianSlice<'data, gimli::RunTimeEndian>, gimli::Error> {
+            let shstrtab = match shstrtab {
+                Some(shstrtab) => shstrtab,
+                _ => return Ok( gimli::EndianSlice::new( &[], endian ) ),
+            };
+
+            for section in sections.iter() {
+                if shstrtab.get( section.strndx() ).map_err(eof)? == id.name().as_bytes() {
+                    // Compressed sections cannot be borrowed.
+                    if section.flags().compressed() {
+                        return Err( gimli::Error::Io );
+                    }
+
+                    return Ok( gimli::EndianSlice::new( self.section_data( section.as_ref() ).map_err(eof)?, endian ) );
+                }
+            }
+
+            Ok( gimli::EndianSlice::new( &[], endian ) )
+        };
+
+        // Load all the sections.
+        gimli::read::Dwarf::load( load )
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::elf::fixture::{
+        Image, Section,
+    };
+
+    /// Relocatable objects of both pointer widths. Built by `tests/fixtures/build.sh`.
+    const OBJECTS: [&[u8]; 2] = [
+        include_bytes!("../../../tests/fixtures/elf32be.o"),
+        include_bytes!("../../../tests/fixtures/elf64le.o"),
+    ];
+
+    /// Builds a 64-bit little endian file whose first Section Header declares the given number of sections.
+    fn extended(shnum: u64) -> Vec<u8> {
+        let mut raw = Image::new(1, 0)
+            .section( Section::default() )
+            .build();
+
+        // Escape `e_shnum` and write the real number of sections in `sh_size` of the first Section Header.
+        raw[0x3C..0x3E].copy_from_slice(&0u16.to_le_bytes());
+        raw[0x60..0x68].copy_from_slice(&shnum.to_le_bytes());
+
+        raw
+    }
+
+    #[test]
+    fn extended_numbering() {
+        let raw = extended(1);
+        let file = ELFFile::parse(&raw).unwrap();
+
+        assert_eq!(file.header().shnum(), 1);
+        assert_eq!(file.sections().unwrap().count(), 1);
+        assert!(file.section(0).is_ok());
+        assert!(matches!(file.section(1), Err(ELFError::BadSectionIndex { index: 1, count: 1, .. })));
+    }
+
+    #[test]
+    fn overflowing_section_count() {
+        let raw = extended(0x0400_0000_0000_0000);
+        let file = ELFFile::parse(&raw).unwrap();
+
+        assert!(matches!(file.sections(), Err(ELFError::OutOfBounds { offset: 0x40, .. })));
+        assert!(matches!(file.section(5), Err(ELFError::OutOfBounds { offset: 0x40, .. })));
+        assert!(crate::elf::ELFContent::parse(raw).is_err());
+    }
+
+    #[test]
+    fn entries_by_value() {
+        let file = ELFFile::parse(OBJECTS[0]).unwrap();
+        let section = file.section(2).unwrap();
+
+        assert!(matches!(section, Entry::X32(_)));
+        assert_eq!(file.section_name(section.as_ref()).unwrap(), b".text");
+        assert!(file.segments().unwrap().next().is_none());
+
+        let file = ELFFile::parse(OBJECTS[1]).unwrap();
+        let section = file.section(2).unwrap();
+
+        assert!(matches!(section, Entry::X64(_)));
+        assert_eq!(section.stype(), SectionType::ProgramData);
+
+        // Entries can be kept after the iterator is dropped.
+        let relocations = file.relocations( file.section(3).unwrap().as_ref() ).unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        assert!(relocations.iter().all(|r| matches!(r, Entry::X64(_))));
+        assert_eq!(relocations.len(), 1);
+    }
+
+    #[test]
+    fn symbol_name() {
+        for raw in OBJECTS {
+            let file = ELFFile::parse(raw).unwrap();
+            let symtab = file.section_by_name(".symtab").unwrap().unwrap();
+
+            let names = file.symbols(symtab.as_ref()).unwrap()
+                .map(|symbol| file.symbol_name(symtab.as_ref(), symbol.unwrap().as_ref()))
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap();
+
+            assert_eq!(names, ["", "ptr", "f", "main"]);
+
+            // The name of a single symbol.
+            let symbol = file.symbol(symtab.as_ref(), 3).unwrap();
+            assert_eq!(file.symbol_name(symtab.as_ref(), symbol.as_ref()), Ok( "main" ));
+        }
+    }
+
+    #[test]
+    fn symbol_name_out_of_bounds() {
+        let mut raw = OBJECTS[1].to_vec();
+
+        let file = ELFFile::parse(OBJECTS[1]).unwrap();
+        let symtab = file.section_by_name(".symtab").unwrap().unwrap();
+        let strtab = file.section( symtab.link() ).unwrap();
+
+        // Point the name of the last symbol past the end of the string table.
+        let name = symtab.phys().0 + (3 * symtab.entrysize());
+        raw[name..name+4].copy_from_slice(&0x1000u32.to_le_bytes());
+
+        let file = ELFFile::parse(&raw).unwrap();
+        let symbol = file.symbol(symtab.as_ref(), 3).unwrap();
+
+        assert_eq!(file.symbol_name(symtab.as_ref(), symbol.as_ref()), Err( ELFError::StringOutOfBounds { offset: strtab.phys().0, strndx: 0x1000, size: strtab.phys().1 } ));
+    }
+}
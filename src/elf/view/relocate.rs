@@ -0,0 +1,181 @@
+//! Application of relocations to the contents of sections.
+
+
+
+use byteorder::{
+    BigEndian, LittleEndian,
+    ByteOrder,
+};
+
+use crate::{
+    elf::{
+        common::{
+            DataOperation, Endian, SectionType,
+        },
+
+        traits::{
+            SectionHeader,
+        },
+
+        ELFError,
+    },
+};
+
+use super::ELFFile;
+
+
+
+impl<'data> ELFFile<'data> {
+    /// Applies the relocations that target the given section to a copy of its contents.
+    /// Only relocations that patch plain data are applied, with all sections placed at
+    /// address zero, which is enough to resolve the offsets of debug sections in
+    /// relocatable files. Other relocations are left untouched.
+    pub fn relocate(&self, section: &dyn SectionHeader, data: &mut [u8]) -> Result<(), ELFError> {
+        let endian = self.header.endian();
+
+        for table in self.sections()? {
+            let table = table?;
+
+            // Only relocation tables that target this section.
+            if ((table.stype() != SectionType::Relocation) && (table.stype() != SectionType::RelocationA)) || (table.info() != section.index()) {
+                continue;
+            }
+
+            // Get the values of the symbols of the associated symbol table.
+            let symtab = self.section( table.link() )?;
+
+            let values = self.symbols( symtab.as_ref() )?
+                .map(|symbol| symbol.map(|s| s.address() as u64))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            for relocation in self.relocations( table.as_ref() )? {
+                let relocation = relocation?;
+
+                // File offset of the entry, for error reporting.
+                let entry = table.phys().0 + (relocation.index() * table.entrysize());
+
+                // Get the operation of the relocation.
+                let (operation, width) = match relocation.rtype().data() {
+                    Some(op @ (DataOperation::Set(w) | DataOperation::Add(w) | DataOperation::Sub(w))) => (op, w),
+                    _ => continue,
+                };
+
+                // Get the value of the symbol.
+                let symbol = match values.get( relocation.symbol() ) {
+                    Some(value) => *value,
+                    _ => return Err( ELFError::BadSymbolIndex { offset: entry, index: relocation.symbol(), count: values.len() } ),
+                };
+
+                // Get the location to relocate.
+                let offset = relocation.offset();
+
+                let place = match offset.checked_add(width).and_then(|end| data.get_mut(offset..end)) {
+                    Some(place) => place,
+                    _ => return Err( ELFError::OutOfBounds { offset: section.phys().0 + offset, size: width, filesize: section.phys().0 + data.len() } ),
+                };
+
+                let current = read(place, endian);
+
+                // REL entries store the addend in the location, so it is only counted once.
+                let result = match (operation, relocation.addend()) {
+                    (DataOperation::Set(_), Some(addend)) => symbol.wrapping_add(addend as u64),
+                    (DataOperation::Set(_), None) => symbol.wrapping_add(current),
+                    (DataOperation::Add(_), addend) => current.wrapping_add(symbol).wrapping_add(addend.unwrap_or(0) as u64),
+                    (DataOperation::Sub(_), addend) => current.wrapping_sub(symbol).wrapping_sub(addend.unwrap_or(0) as u64),
+                };
+
+                write(place, endian, result);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+
+
+/// Reads an integer of the size of the given location.
+fn read(place: &[u8], endian: Endian) -> u64 {
+    match endian {
+        Endian::Little => LittleEndian::read_uint(place, place.len()),
+        Endian::Big => BigEndian::read_uint(place, place.len()),
+    }
+}
+
+/// Writes the given value truncated to the size of the given location.
+fn write(place: &mut [u8], endian: Endian, value: u64) {
+    // Truncate the value to the width of the location.
+    let value = match place.len() {
+        8 => value,
+        n => value & ((1u64 << (8 * n)) - 1),
+    };
+
+    match endian {
+        Endian::Little => LittleEndian::write_uint(place, value, place.len()),
+        Endian::Big => BigEndian::write_uint(place, value, place.len()),
+    }
+}
+
+
+
+#[cfg(test)]
+mod test {
+    use crate::elf::ELFContent;
+
+    /// Relocatable objects with debug information, using REL (32-bit) and RELA (64-bit) entries.
+    /// Built by `tests/fixtures/build.sh`.
+    const DEBUG32: &[u8] = include_bytes!("../../../tests/fixtures/debug32.o");
+    const DEBUG64: &[u8] = include_bytes!("../../../tests/fixtures/debug64.o");
+
+    /// Returns the names of the debug information entries, read through the relocated sections.
+    fn names(elf: &ELFContent) -> Vec<String> {
+        let dwarf = elf.dwarf().unwrap();
+        let dwarf = dwarf.borrow(|section| gimli::EndianSlice::new(section, gimli::RunTimeEndian::Little));
+
+        let mut names = Vec::new();
+        let mut units = dwarf.units();
+
+        while let Some(header) = units.next().unwrap() {
+            let unit = dwarf.unit(header).unwrap();
+            let mut entries = unit.entries();
+
+            while let Some((_, entry)) = entries.next_dfs().unwrap() {
+                if let Some(name) = entry.attr_value(gimli::DW_AT_name).unwrap() {
+                    names.push( dwarf.attr_string(&unit, name).unwrap().to_string_lossy().into_owned() );
+                }
+            }
+        }
+
+        names
+    }
+
+    #[test]
+    fn relocate() {
+        for raw in [DEBUG32, DEBUG64] {
+            let elf = ELFContent::parse(raw.to_vec()).unwrap();
+
+            assert_eq!(names(&elf), ["debug.c", "counter", "int", "increment", "step"]);
+        }
+    }
+
+    #[test]
+    fn implicit_addend() {
+        let mut raw = DEBUG32.to_vec();
+        let elf = ELFContent::parse(raw.clone()).unwrap();
+
+        // Relabel the object as RISC-V, where `R_386_32` is `R_RISCV_32`, and turn the relocations of
+        // `.debug_info` into `R_RISCV_ADD32`. The symbols are at address zero, so the in-place addends
+        // are kept as long as they are only counted once.
+        let (offset, size) = elf.sections[ elf.find_section(".rel.debug_info").unwrap() ].phys();
+
+        raw[0x12..0x14].copy_from_slice(&0xF3u16.to_le_bytes());
+
+        for entry in raw[offset..offset+size].chunks_mut(8) {
+            entry[4] = 35;
+        }
+
+        let elf = ELFContent::parse(raw).unwrap();
+
+        assert_eq!(names(&elf), ["debug.c", "counter", "int", "increment", "step"]);
+    }
+}
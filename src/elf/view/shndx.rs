@@ -0,0 +1,56 @@
+//! Borrowed access to an extended section index table.
+
+
+
+use crate::{
+    elf::{
+        common::{
+            read::{
+                big32, little32,
+            },
+
+            Endian,
+        },
+
+        ELFError,
+    },
+};
+
+
+
+/// An extended section index table (`SHT_SYMTAB_SHNDX`) borrowed from the raw data.
+/// Contains the real section index of each symbol whose `st_shndx` is `SHN_XINDEX`.
+#[derive(Clone, Copy, Debug)]
+pub struct SectionIndices<'data> {
+    /// Contents of the table.
+    data: &'data [u8],
+
+    /// File offset of the table.
+    offset: usize,
+
+    /// Endianness of the entries.
+    endian: Endian,
+}
+
+impl<'data> SectionIndices<'data> {
+    /// Creates an extended section index table from its contents and file offset.
+    pub fn new(data: &'data [u8], offset: usize, endian: Endian) -> Self {
+        Self { data, offset, endian }
+    }
+
+    /// Returns the section index of the symbol with the given index.
+    pub fn get(&self, symbol: usize) -> Result<u32, ELFError> {
+        // Get the read function.
+        let read32: fn(&[u8]) -> u32 = match self.endian {
+            Endian::Little => little32,
+            Endian::Big => big32,
+        };
+
+        let start = symbol * 4;
+
+        match self.data.get(start..start + 4) {
+            Some(entry) => Ok( read32(entry) ),
+            _ => Err( ELFError::Truncated { offset: self.offset + start, expected: 4, found: self.data.len().saturating_sub(start) } ),
+        }
+    }
+}
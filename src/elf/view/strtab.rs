@@ -0,0 +1,66 @@
+//! Borrowed access to the strings of an ELF string table.
+
+
+
+use crate::{
+    elf::{
+        ELFError,
+    },
+};
+
+
+
+/// A string table borrowed from the raw ELF data.
+#[derive(Clone, Copy, Debug)]
+pub struct StringTable<'data> {
+    /// Contents of the string table.
+    data: &'data [u8],
+
+    /// File offset of the string table.
+    offset: usize,
+}
+
+impl<'data> StringTable<'data> {
+    /// Creates a string table from its contents and file offset.
+    pub fn new(data: &'data [u8], offset: usize) -> Self {
+        Self { data, offset }
+    }
+
+    /// Returns the raw contents of the string table.
+    pub fn data(&self) -> &'data [u8] {
+        self.data
+    }
+
+    /// Returns the file offset of the string table.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns the bytes of the string at the given offset, without the NULL terminator.
+    pub fn get(&self, strndx: usize) -> Result<&'data [u8], ELFError> {
+        // Get the start of the string.
+        let start = match self.data.get(strndx..) {
+            Some(start) => start,
+            _ => return Err( self.outofbounds(strndx) ),
+        };
+
+        // The string must be terminated inside the table.
+        match start.iter().position(|b| *b == 0) {
+            Some(end) => Ok( &start[..end] ),
+            _ => Err( self.outofbounds(strndx) ),
+        }
+    }
+
+    /// Returns the string at the given offset.
+    pub fn get_str(&self, strndx: usize) -> Result<&'data str, ELFError> {
+        match core::str::from_utf8( self.get(strndx)? ) {
+            Ok(string) => Ok(string),
+            _ => Err( ELFError::NonUTF8String { offset: self.offset, strndx } ),
+        }
+    }
+
+    /// Internal error builder.
+    fn outofbounds(&self, strndx: usize) -> ELFError {
+        ELFError::StringOutOfBounds { offset: self.offset, strndx, size: self.data.len() }
+    }
+}
@@ -0,0 +1,470 @@
+//! Serialization of ELF files.
+
+
+
+use byteorder::{
+    BigEndian, LittleEndian,
+    ByteOrder,
+};
+
+use crate::{
+    elf::{
+        common::{
+            Endian, SectionType, SegmentType, SymbolSection,
+        },
+
+        traits::{
+            SectionHeader,
+        },
+
+        ELFContent, ELFError,
+    },
+};
+
+use std::{
+    borrow::{
+        Cow,
+    },
+
+    collections::{
+        hash_map::Entry,
+        HashMap,
+    },
+
+    ffi::{
+        CStr,
+    },
+};
+
+
+
+/// Start of the reserved range of section indices.
+const SHN_LORESERVE: usize = 0xFF00;
+
+/// Section index of symbols whose real index is in the extended section index table.
+const SHN_XINDEX: u16 = 0xFFFF;
+
+/// Escape value of `e_phnum` when the number of Program Headers is in the first Section Header.
+const PN_XNUM: usize = 0xFFFF;
+
+
+
+impl ELFContent {
+    /// Serializes this ELF file. Unmodified files are written back byte for byte.
+    ///
+    /// Section names, symbol tables and section groups are rebuilt from the parsed model, adding
+    /// new names at the end of their string tables. When sections are added, removed or resized,
+    /// the sections that are not mapped by a loadable segment are placed again after the mapped
+    /// part of the file, followed by the Section Header Table. Mapped sections cannot grow.
+    pub fn write(&self) -> Result<Vec<u8>, ELFError> {
+        let endian = self.header.endian();
+
+        // Get the write functions.
+        let write32: fn(&mut [u8], u32) = match endian {
+            Endian::Little => LittleEndian::write_u32,
+            Endian::Big => BigEndian::write_u32,
+        };
+
+        let write64: fn(&mut [u8], u64) = match endian {
+            Endian::Little => LittleEndian::write_u64,
+            Endian::Big => BigEndian::write_u64,
+        };
+
+        // Get the word size and the size of the File, Program and Section Headers.
+        let (word, ehsize, phsize, shsize) = match self.raw[0x04] {
+            2 => (8, 0x40, 0x38, 0x40),
+            _ => (4, 0x34, 0x20, 0x28),
+        };
+
+        // String tables rebuilt with the section and symbol names.
+        let mut strings = HashMap::new();
+
+        // Get the name offsets of the sections.
+        let shstrndx = self.header.shstrndx();
+
+        let names = self.sections.iter()
+            .map(|section| Ok( match self.strings(&mut strings, shstrndx)? {
+                Some(strtab) => strtab.offset(section.strndx(), section.name()),
+                _ => section.strndx(),
+            }))
+            .collect::<Result<Vec<_>, ELFError>>()?;
+
+        // Serialize the symbol tables and their extended section index tables, unless their contents were replaced.
+        let mut tables = HashMap::new();
+
+        for table in self.symbols.iter().filter(|t| !self.edits.contains_key(&t.section)) {
+            let section = self.sections.get(table.section)
+                .ok_or( ELFError::BadSectionIndex { offset: self.header.shtoffset(), index: table.section, count: self.sections.len() } )?;
+
+            let (offset, entry) = (section.phys().0, section.entrysize());
+            let mut data = vec![0u8; table.symbols.len() * entry];
+
+            // Get the extended section index table of the symbol table.
+            let xindex = self.sections.iter()
+                .position(|s| (s.stype() == SectionType::SymbolTableX) && (s.link() == table.section));
+
+            let mut indices = xindex.map(|_| vec![0u8; table.symbols.len() * 4]);
+
+            for (i, (symbol, chunk)) in table.symbols.iter().zip(data.chunks_mut(entry)).enumerate() {
+                // Get the offset of the name.
+                let strndx = match self.strings(&mut strings, table.strtab)? {
+                    Some(strtab) => strtab.offset(symbol.strndx(), symbol.name()),
+                    _ => symbol.strndx(),
+                };
+
+                // Escape the section indices in the reserved range.
+                let shndx = match (symbol.section(), indices.as_mut()) {
+                    (SymbolSection::Index(index), Some(indices)) if index >= SHN_LORESERVE => {
+                        write32( &mut indices[i*4..(i+1)*4], index as u32 );
+                        SHN_XINDEX
+                    },
+
+                    (SymbolSection::Index(index), None) if index >= SHN_LORESERVE => {
+                        return Err( ELFError::BadSectionIndex { offset: offset + (i * entry), index, count: self.sections.len() } );
+                    },
+
+                    (section, _) => section.index() as u16,
+                };
+
+                symbol.write(chunk, endian, strndx, shndx);
+            }
+
+            tables.insert(table.section, data);
+
+            if let (Some(xindex), Some(indices)) = (xindex, indices) {
+                tables.insert(xindex, indices);
+            }
+        }
+
+        // Serialize the section groups.
+        for group in &self.groups {
+            let mut data = vec![0u8; (group.members.len() + 1) * 4];
+
+            write32( &mut data[0..4], group.flags );
+
+            for (member, chunk) in group.members.iter().zip(data[4..].chunks_mut(4)) {
+                write32( chunk, *member as u32 );
+            }
+
+            tables.insert(group.section, data);
+        }
+
+        // Get the contents of the sections.
+        let contents = self.sections.iter()
+            .enumerate()
+            .map(|(i, section)| match (section.stype(), tables.remove(&i), strings.remove(&i)) {
+                (SectionType::Null | SectionType::Bss, ..) => Ok( Cow::Borrowed( &[][..] ) ),
+                (_, _, Some(strtab)) => Ok( strtab.data ),
+                (_, Some(data), _) if !self.edits.contains_key(&i) => Ok( Cow::Owned( data ) ),
+                _ => self.section_data(section.as_ref()).map(Cow::Borrowed),
+            })
+            .collect::<Result<Vec<_>, ELFError>>()?;
+
+        // Start from the original file, so the bytes outside of the sections and tables are kept.
+        let mut out = self.raw.to_vec();
+
+        // Sections mapped by loadable segments cannot move.
+        let loadable = self.segments.iter().any(|s| s.stype() == SegmentType::Load);
+        let fixed = |section: &dyn SectionHeader| loadable && section.flags().alloc();
+
+        // Sections added by the editing methods.
+        let new = |i: usize| self.edits.contains_key(&i) && (self.sections[i].phys().0 == 0);
+
+        // Place the movable sections again if sections were added, removed or resized.
+        let relayout = (self.sections.len() != self.header.shnum()) || self.sections.iter()
+            .zip(contents.iter())
+            .enumerate()
+            .filter(|(_, (s, _))| !fixed(s.as_ref()) && !matches!(s.stype(), SectionType::Null | SectionType::Bss))
+            .any(|(i, (s, data))| new(i) || (s.phys().1 != data.len()));
+
+        // Place the contents of the fixed sections, or of all sections if the layout is kept.
+        let mut phys = self.sections.iter().map(|s| s.phys()).collect::<Vec<_>>();
+
+        for (i, (section, data)) in self.sections.iter().zip(contents.iter()).enumerate() {
+            let (offset, size) = section.phys();
+
+            match section.stype() {
+                // Sections with no data in the file image.
+                SectionType::Null | SectionType::Bss => (),
+
+                // Sections placed again after the fixed part of the file.
+                _ if relayout && !fixed(section.as_ref()) => (),
+
+                // Contents that fit in the original place, zero filling the rest.
+                _ if data.len() <= size => {
+                    let place = out.get_mut(offset..offset.saturating_add(size))
+                        .ok_or( ELFError::OutOfBounds { offset, size, filesize: self.raw.len() } )?;
+
+                    place[..data.len()].copy_from_slice(data);
+                    place[data.len()..].fill(0);
+
+                    phys[i] = (offset, data.len());
+                },
+
+                _ => return Err( ELFError::UnmovableSection { offset, index: i } ),
+            }
+        }
+
+        if relayout {
+            // Get the end of the headers, the segments and the fixed sections.
+            let start = self.segments.iter()
+                .map(|s| s.phys().0.saturating_add(s.phys().1))
+                .chain( self.sections.iter().zip(phys.iter()).filter(|(s, _)| fixed(s.as_ref()) && (s.stype() != SectionType::Bss)).map(|(_, (o, s))| o.saturating_add(*s)) )
+                .chain( [ehsize, self.header.phtoffset().saturating_add(self.header.phnum().saturating_mul(phsize))] )
+                .max()
+                .unwrap_or(0)
+                .min(self.raw.len());
+
+            out.truncate(start);
+
+            // Place the movable sections in their original order, followed by the new sections.
+            let mut order = (0..self.sections.len())
+                .filter(|&i| !fixed(self.sections[i].as_ref()))
+                .collect::<Vec<_>>();
+
+            order.sort_by_key(|&i| (new(i), self.sections[i].phys().0, i));
+
+            for i in order {
+                let (section, data) = (&self.sections[i], &contents[i]);
+
+                // Alignments must be powers of two, and cannot exceed the size of the file.
+                let error = ELFError::BadAlignment { offset: section.phys().0, index: i, align: section.align() };
+
+                let start = match section.align() {
+                    a if (a > 1) && (!a.is_power_of_two() || (a > self.raw.len())) => return Err( error ),
+                    a => align(out.len(), a).ok_or(error)?,
+                };
+
+                match section.stype() {
+                    SectionType::Null => (),
+
+                    SectionType::Bss => phys[i] = (start, section.phys().1),
+
+                    _ => {
+                        out.resize(start, 0);
+                        out.extend_from_slice(data);
+
+                        phys[i] = (start, data.len());
+                    },
+                }
+            }
+        }
+
+        // Place the Section Header Table at the end of the file if the sections were placed again.
+        let shnum = self.sections.len();
+
+        let shoffset = match shnum {
+            0 => 0,
+            _ if !relayout => self.header.shtoffset(),
+            _ => {
+                let start = align(out.len(), word)
+                    .ok_or( ELFError::OutOfBounds { offset: out.len(), size: word, filesize: out.len() } )?;
+
+                let end = shnum.checked_mul(shsize).and_then(|size| start.checked_add(size))
+                    .ok_or( ELFError::OutOfBounds { offset: start, size: usize::MAX, filesize: out.len() } )?;
+
+                out.resize(end, 0);
+                start
+            },
+        };
+
+        for (i, section) in self.sections.iter().enumerate() {
+            let chunk = &mut out[shoffset + (i * shsize)..shoffset + ((i + 1) * shsize)];
+
+            section.write(chunk, endian, names[i], phys[i]);
+        }
+
+        // Place the Program Header Table, in its original place if the number of segments did not change.
+        let phnum = self.segments.len();
+
+        let phoffset = match phnum {
+            n if n == self.header.phnum() => self.header.phtoffset(),
+            _ => {
+                let start = align(out.len(), word)
+                    .ok_or( ELFError::OutOfBounds { offset: out.len(), size: word, filesize: out.len() } )?;
+
+                let end = phnum.checked_mul(phsize).and_then(|size| start.checked_add(size))
+                    .ok_or( ELFError::OutOfBounds { offset: start, size: usize::MAX, filesize: out.len() } )?;
+
+                out.resize(end, 0);
+                start
+            },
+        };
+
+        for (i, segment) in self.segments.iter().enumerate() {
+            segment.write(&mut out[phoffset + (i * phsize)..phoffset + ((i + 1) * phsize)], endian);
+        }
+
+        // Store the values that do not fit in the header in the first Section Header.
+        if shnum > 0 {
+            let first = &mut out[shoffset..shoffset + shsize];
+            let s = word;
+
+            if shnum >= SHN_LORESERVE {
+                match s {
+                    8 => write64( &mut first[0x08 + (3 * s)..0x08 + (4 * s)], shnum as u64 ),
+                    _ => write32( &mut first[0x08 + (3 * s)..0x08 + (4 * s)], shnum as u32 ),
+                }
+            }
+
+            if shstrndx >= SHN_LORESERVE {
+                write32( &mut first[0x08 + (4 * s)..0x0C + (4 * s)], shstrndx as u32 );
+            }
+
+            if phnum >= PN_XNUM {
+                write32( &mut first[0x0C + (4 * s)..0x10 + (4 * s)], phnum as u32 );
+            }
+        }
+
+        // Write the File Header.
+        self.header.write(&mut out, (phoffset, shoffset), (phnum, shnum), shstrndx);
+
+        Ok( out )
+    }
+
+    /// Returns the rebuilt string table of the given section, if it is a string table.
+    fn strings<'a, 'b>(&'a self, strings: &'b mut HashMap<usize, Strings<'a>>, index: usize) -> Result<Option<&'b mut Strings<'a>>, ELFError> {
+        // Get the string table section.
+        let section = match self.sections.get(index) {
+            Some(section) if (index != 0) && (section.stype() == SectionType::StringTable) => section,
+            _ => return Ok( None ),
+        };
+
+        let strtab = match strings.entry(index) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert( Strings { data: Cow::Borrowed( self.section_data(section.as_ref())? ) } ),
+        };
+
+        Ok( Some( strtab ) )
+    }
+}
+
+
+
+/// String table rebuilt with the names of the objects that reference it.
+struct Strings<'data> {
+    /// Contents of the string table, copied when a name is added.
+    data: Cow<'data, [u8]>,
+}
+
+impl<'data> Strings<'data> {
+    /// Returns the offset of a name, keeping the original offset when the name did not change
+    /// and reusing any other occurrence of the name before adding it at the end of the table.
+    fn offset(&mut self, strndx: usize, name: &str) -> usize {
+        // Names are empty when the string table could not be read.
+        if name.is_empty() || self.matches(strndx, name) {
+            return strndx;
+        }
+
+        // Search the name, which may be the suffix of another string.
+        let mut string = Vec::from( name.as_bytes() );
+        string.push( 0 );
+
+        if let Some(i) = self.data.windows(string.len()).position(|w| w == string) {
+            return i;
+        }
+
+        // Add the name, keeping the empty string at the start of the table.
+        let data = self.data.to_mut();
+
+        if data.last() != Some( &0 ) {
+            data.push( 0 );
+        }
+
+        let offset = data.len();
+        data.extend_from_slice(&string);
+
+        offset
+    }
+
+    /// Returns `true` if the string at the given offset was read as the given name.
+    fn matches(&self, strndx: usize, name: &str) -> bool {
+        let string = match self.data.get(strndx..).map(CStr::from_bytes_until_nul) {
+            Some(Ok(string)) => string.to_bytes(),
+            _ => return false,
+        };
+
+        // Empty and invalid strings are given placeholder names when they are read.
+        match core::str::from_utf8(string) {
+            Ok("") => name == "NULL",
+            Ok(string) => string == name,
+            _ => name == "CORRUPTED",
+        }
+    }
+}
+
+
+
+/// Rounds an offset up to the given alignment, or returns `None` if the result overflows.
+fn align(offset: usize, align: usize) -> Option<usize> {
+    match align {
+        0 | 1 => Some( offset ),
+        a => offset.checked_next_multiple_of(a),
+    }
+}
+
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Relocatable objects with a section group, relocations and a compressed section.
+    /// Built by `tests/fixtures/build.sh`.
+    const FIXTURES: [(&str, &[u8]); 4] = [
+        ("elf32le.o", include_bytes!("../../../tests/fixtures/elf32le.o")),
+        ("elf32be.o", include_bytes!("../../../tests/fixtures/elf32be.o")),
+        ("elf64le.o", include_bytes!("../../../tests/fixtures/elf64le.o")),
+        ("elf64be.o", include_bytes!("../../../tests/fixtures/elf64be.o")),
+    ];
+
+    #[test]
+    fn round_trip() {
+        for (name, raw) in FIXTURES {
+            let elf = ELFContent::parse(raw.to_vec()).unwrap();
+
+            assert!(elf.errors.is_empty(), "{}", name);
+            assert_eq!(elf.groups.len(), 1, "{}", name);
+            assert!(elf.sections.iter().any(|s| elf.compression(s.as_ref()).unwrap().is_some()), "{}", name);
+
+            assert_eq!(elf.write().unwrap(), raw, "{}", name);
+        }
+    }
+
+    #[test]
+    fn extended_numbering() {
+        let mut elf = ELFContent::parse(FIXTURES[0].1.to_vec()).unwrap();
+
+        // Add sections up to the reserved range, followed by a new section name string table.
+        while elf.sections.len() < SHN_LORESERVE {
+            elf.add_section(".s", SectionType::ProgramData, 0, Vec::new());
+        }
+
+        let shstrtab = elf.add_section(".shstrtab", SectionType::StringTable, 0, vec![0]);
+        elf.header.setshstrndx(shstrtab);
+
+        // The number of sections and the string section index are escaped.
+        let raw = elf.write().unwrap();
+
+        assert_eq!(u16::from_le_bytes([raw[0x30], raw[0x31]]), 0);
+        assert_eq!(u16::from_le_bytes([raw[0x32], raw[0x33]]), 0xFFFF);
+
+        let elf = ELFContent::parse(raw.clone()).unwrap();
+
+        assert_eq!(elf.sections.len(), SHN_LORESERVE + 1);
+        assert_eq!(elf.header.shstrndx(), SHN_LORESERVE);
+        assert_eq!(elf.find_section(".text.f"), Some(7));
+        assert_eq!(elf.sections[SHN_LORESERVE - 1].name(), ".s");
+
+        // Files with extended numbering are written back byte for byte.
+        assert_eq!(elf.write().unwrap(), raw);
+    }
+
+    #[test]
+    fn alignment() {
+        assert_eq!(align(5, 0), Some(5));
+        assert_eq!(align(9, 8), Some(16));
+        assert_eq!(align(16, 8), Some(16));
+        assert_eq!(align(usize::MAX, 8), None);
+    }
+}
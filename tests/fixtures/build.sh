@@ -0,0 +1,18 @@
+#!/bin/sh
+# Builds the test fixtures with GNU as for x86 and x86-64.
+# The big endian objects are byte-swapped copies relabelled as PowerPC64 (21) and MIPS (8).
+set -e
+cd "$(dirname "$0")"
+
+as --64 --compress-debug-sections=zlib-gabi -o elf64le.o objects64.s
+as --32 --compress-debug-sections=zlib-gabi -o elf32le.o objects32.s
+
+python3 swap.py elf64le.o elf64be.o 21
+python3 swap.py elf32le.o elf32be.o 8
+
+gcc -m32 -g -O0 -fno-pic -fno-asynchronous-unwind-tables -fcf-protection=none -c -o debug32.o debug.c
+gcc -g -O0 -fno-pic -fno-asynchronous-unwind-tables -fcf-protection=none -c -o debug64.o debug.c
+
+gcc -shared -fPIC -O1 -fno-asynchronous-unwind-tables -fcf-protection=none \
+    -Wl,--version-script=library.map -Wl,-z,now -Wl,-z,noseparate-code -Wl,--hash-style=both \
+    -Wl,--build-id=sha1 -Wl,-soname,libsample.so.1 -o libsample.so library.c
@@ -0,0 +1,8 @@
+/* Relocatable object with debug information. Built by build.sh. */
+
+int counter;
+
+int increment(int step) {
+    counter += step;
+    return counter;
+}
@@ -0,0 +1,23 @@
+/* Shared library with versioned symbols. Built by build.sh. */
+
+#include <string.h>
+
+int counter = 1;
+
+__thread int local;
+
+int increment(int step) {
+    counter += step;
+    return counter;
+}
+
+__asm__(".symver copy_old, copy@LIB_1.0");
+__asm__(".symver copy_new, copy@@LIB_2.0");
+
+void copy_old(char *dst, const char *src) {
+    strcpy(dst, src);
+}
+
+void copy_new(char *dst, const char *src, unsigned long n) {
+    memcpy(dst, src, n);
+}
@@ -0,0 +1,25 @@
+# Relocatable object with a COMDAT group, relocations and a compressed debug section.
+# Built by build.sh.
+
+	.section .text.f,"axG",@progbits,f,comdat
+	.globl f
+	.type f,@function
+f:
+	ret
+	.size f,1
+
+	.text
+	.globl main
+	.type main,@function
+main:
+	call f
+	ret
+	.size main,.-main
+
+	.data
+	.align 4
+ptr:
+	.long main
+
+	.section .debug_str,"MS",@progbits,1
+	.asciz "hello hello hello hello hello hello hello hello"
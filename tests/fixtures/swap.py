@@ -0,0 +1,53 @@
+#!/usr/bin/env python3
+"""Converts a little endian relocatable object into the equivalent big endian object.
+
+Usage: swap.py <input> <output> <e_machine>
+
+Swaps the File Header, the Section Headers, the symbol tables, the relocation tables,
+the section groups and the compression headers. Other section contents are copied as is.
+"""
+
+import struct
+import sys
+
+SHT_SYMTAB, SHT_RELA, SHT_REL, SHT_GROUP = 2, 4, 9, 17
+SHF_COMPRESSED = 0x800
+
+src, dst, machine = sys.argv[1], sys.argv[2], int(sys.argv[3])
+data = bytearray(open(src, 'rb').read())
+
+wide = data[4] == 2
+word = 'Q' if wide else 'I'
+
+
+def swap(offset, fmt):
+    """Rewrites the little endian fields at the given offset as big endian."""
+    values = struct.unpack_from('<' + fmt, data, offset)
+    struct.pack_into('>' + fmt, data, offset, *values)
+    return values
+
+
+# File Header.
+header = swap(0x10, 'HHI' + (word * 3) + 'I' + ('H' * 6))
+shoff, shentsize, shnum = header[5], header[10], header[11]
+
+data[5] = 2
+struct.pack_into('>H', data, 0x12, machine)
+
+# Section Headers and contents.
+sections = [swap(shoff + (i * shentsize), 'IIQQQQIIQQ' if wide else 'I' * 10) for i in range(shnum)]
+
+for (_, stype, flags, _, offset, size, _, _, _, entsize) in sections:
+    if stype == SHT_GROUP:
+        swap(offset, 'I' * (size // 4))
+    elif stype in (SHT_RELA, SHT_REL):
+        for entry in range(offset, offset + size, entsize):
+            swap(entry, word * (3 if stype == SHT_RELA else 2))
+    elif stype == SHT_SYMTAB:
+        for entry in range(offset, offset + size, entsize):
+            swap(entry, 'IBBHQQ' if wide else 'IIIBBH')
+
+    if flags & SHF_COMPRESSED:
+        swap(offset, 'IIQQ' if wide else 'III')
+
+open(dst, 'wb').write(data)